@@ -5,6 +5,7 @@ use sqlparser::ast::{Function, FunctionArg, FunctionArgExpr};
 
 use super::BoundExpr;
 use crate::binder::{BindError, Binder};
+use crate::types::{ScalarValue, MAX_DECIMAL_PRECISION};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AggFunc {
@@ -12,6 +13,7 @@ pub enum AggFunc {
     Sum,
     Min,
     Max,
+    Avg,
 }
 
 impl fmt::Display for AggFunc {
@@ -21,6 +23,7 @@ impl fmt::Display for AggFunc {
             AggFunc::Sum => write!(f, "Sum"),
             AggFunc::Min => write!(f, "Min"),
             AggFunc::Max => write!(f, "Max"),
+            AggFunc::Avg => write!(f, "Avg"),
         }
     }
 }
@@ -30,6 +33,8 @@ pub struct BoundAggFunc {
     pub func: AggFunc,
     pub exprs: Vec<BoundExpr>,
     pub return_type: DataType,
+    /// aggregate the distinct values only, e.g. `count(distinct c1)`
+    pub distinct: bool,
 }
 
 impl Binder {
@@ -46,31 +51,60 @@ impl Binder {
                     args.push(expr);
                 }
                 FunctionArgExpr::QualifiedWildcard(_) => todo!(),
-                FunctionArgExpr::Wildcard => todo!(),
+                // count(*) counts all rows, which is the same as counting a non-null constant
+                FunctionArgExpr::Wildcard
+                    if func.name.to_string().eq_ignore_ascii_case("count") =>
+                {
+                    args.push(BoundExpr::Constant(ScalarValue::Int32(Some(1))));
+                }
+                FunctionArgExpr::Wildcard => {
+                    return Err(BindError::InvalidFunctionArgs(func.to_string()))
+                }
             }
         }
 
-        let expr = match func.name.to_string().to_lowercase().as_str() {
+        // the distinct strings are counted by the session collation. min and max still compare the
+        // strings in binary order, as they return the original strings rather than the sort keys,
+        // and the accumulators don't carry the collation.
+        let name = func.name.to_string().to_lowercase();
+        if func.distinct && name == "count" {
+            args = args.into_iter().map(|arg| self.collate(arg)).collect();
+        }
+        let expr = match name.as_str() {
             "count" => BoundAggFunc {
                 func: AggFunc::Count,
+                distinct: func.distinct,
                 exprs: args.clone(),
                 return_type: DataType::Int64,
             },
             "sum" => BoundAggFunc {
                 func: AggFunc::Sum,
+                distinct: func.distinct,
                 exprs: args.clone(),
-                return_type: args[0].return_type().unwrap(),
+                // the sum of decimal keeps the scale and widens to max precision
+                return_type: match args[0].return_type().unwrap() {
+                    DataType::Decimal(_, s) => DataType::Decimal(MAX_DECIMAL_PRECISION as usize, s),
+                    t => t,
+                },
             },
             "min" => BoundAggFunc {
                 func: AggFunc::Min,
+                distinct: func.distinct,
                 exprs: args.clone(),
                 return_type: args[0].return_type().unwrap(),
             },
             "max" => BoundAggFunc {
                 func: AggFunc::Max,
+                distinct: func.distinct,
                 exprs: args.clone(),
                 return_type: args[0].return_type().unwrap(),
             },
+            "avg" => BoundAggFunc {
+                func: AggFunc::Avg,
+                distinct: func.distinct,
+                exprs: args.clone(),
+                return_type: DataType::Float64,
+            },
             _ => unimplemented!("not implmented agg func {}", func.name),
         };
         Ok(BoundExpr::AggFunc(expr))
@@ -84,6 +118,11 @@ impl fmt::Debug for BoundAggFunc {
         } else {
             format!("{:?}", self.exprs)
         };
-        write!(f, "{}({}):{}", self.func, expr, self.return_type)
+        let distinct = if self.distinct { "DISTINCT " } else { "" };
+        write!(
+            f,
+            "{}({}{}):{}",
+            self.func, distinct, expr, self.return_type
+        )
     }
 }
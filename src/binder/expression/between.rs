@@ -0,0 +1,60 @@
+use std::fmt;
+
+use sqlparser::ast::Expr;
+
+use super::in_list::coerce_compared_exprs;
+use super::BoundExpr;
+use crate::binder::{BindError, Binder};
+
+/// `expr BETWEEN low AND high` is equal to `expr >= low AND expr <= high`, we keep it as a
+/// standalone expr so that the target expr is evaluated only once.
+#[derive(Clone, PartialEq)]
+pub struct BoundBetween {
+    pub expr: Box<BoundExpr>,
+    pub negated: bool,
+    pub low: Box<BoundExpr>,
+    pub high: Box<BoundExpr>,
+}
+
+impl Binder {
+    pub fn bind_between(
+        &mut self,
+        expr: &Expr,
+        negated: bool,
+        low: &Expr,
+        high: &Expr,
+    ) -> Result<BoundExpr, BindError> {
+        let exprs = vec![
+            self.bind_expr(expr)?,
+            self.bind_expr(low)?,
+            self.bind_expr(high)?,
+        ];
+        // the target and bounds are compared in their common supertype, and by the collation
+        let exprs = coerce_compared_exprs(exprs)?
+            .into_iter()
+            .map(|e| self.collate(e))
+            .collect::<Vec<_>>();
+        let [expr, low, high]: [BoundExpr; 3] = exprs.try_into().unwrap();
+        Ok(BoundExpr::Between(BoundBetween {
+            expr: Box::new(expr),
+            negated,
+            low: Box::new(low),
+            high: Box::new(high),
+        }))
+    }
+}
+
+impl fmt::Debug for BoundBetween {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = if self.negated {
+            "NOT BETWEEN"
+        } else {
+            "BETWEEN"
+        };
+        write!(
+            f,
+            "{:?} {} {:?} AND {:?}",
+            self.expr, op, self.low, self.high
+        )
+    }
+}
@@ -3,8 +3,9 @@ use std::fmt;
 use arrow::datatypes::DataType;
 use sqlparser::ast::{BinaryOperator, Expr};
 
-use super::BoundExpr;
+use super::{cast_to, cast_to_string, common_supertype, BoundExpr};
 use crate::binder::{BindError, Binder, BoundTypeCast};
+use crate::types::{decimal_arithmetic_type, integer_decimal_type};
 
 #[derive(Clone, PartialEq)]
 pub struct BoundBinaryOp {
@@ -24,51 +25,40 @@ impl Binder {
         let mut left_expr = self.bind_expr(left)?;
         let mut right_expr = self.bind_expr(right)?;
 
-        let left_return_type = match (left_expr.return_type(), right_expr.return_type()) {
-            (None, None) => None,
-            (Some(left_type), Some(right_type)) => {
-                if left_type == right_type {
-                    Some(left_type)
-                } else {
-                    let mut return_type = left_type.clone();
-                    match (left_type.clone(), right_type.clone()) {
-                        // big type to small type, cast right to big type
-                        (DataType::Int64, DataType::Int32)
-                        | (DataType::Float64, DataType::Int32 | DataType::Int64) => {
-                            right_expr = BoundExpr::TypeCast(BoundTypeCast {
-                                expr: Box::new(right_expr),
-                                cast_type: left_type,
-                            });
-                        }
-                        // small type to big type, cast left to big type
-                        (DataType::Int32, DataType::Int64)
-                        | (DataType::Int32 | DataType::Int64, DataType::Float64) => {
-                            left_expr = BoundExpr::TypeCast(BoundTypeCast {
-                                expr: Box::new(left_expr),
-                                cast_type: right_type.clone(),
-                            });
-                            return_type = right_type;
-                        }
-                        _ => todo!("not implmented type conversion"),
-                    }
-                    Some(return_type)
-                }
-            }
-            (left, right) => {
-                return Err(BindError::BinaryOpTypeMismatch(
-                    format!("{:?}", left),
-                    format!("{:?}", right),
-                ))
-            }
-        };
+        // both sides of `||` are casted to string
+        if *op == BinaryOperator::StringConcat {
+            left_expr = cast_to_string(left_expr);
+            right_expr = cast_to_string(right_expr);
+        }
+
+        let (mut left_expr, mut right_expr) = coerce_binary_operands(left_expr, right_expr)?;
+        let left_return_type = left_expr.return_type();
 
         use BinaryOperator as Op;
 
+        if matches!(
+            op,
+            Op::Gt | Op::GtEq | Op::Lt | Op::LtEq | Op::Eq | Op::NotEq
+        ) {
+            left_expr = self.collate(left_expr);
+            right_expr = self.collate(right_expr);
+        }
+
         let return_type = match op {
-            Op::Plus | Op::Minus | Op::Multiply | Op::Divide | Op::Modulo => left_return_type,
+            Op::Plus | Op::Minus | Op::Multiply | Op::Divide | Op::Modulo => {
+                match (left_expr.return_type(), right_expr.return_type()) {
+                    (Some(DataType::Decimal(p1, s1)), Some(DataType::Decimal(p2, s2))) => {
+                        let (p, s) =
+                            decimal_arithmetic_type(op, (p1 as u8, s1 as u8), (p2 as u8, s2 as u8));
+                        Some(DataType::Decimal(p as usize, s as usize))
+                    }
+                    _ => left_return_type,
+                }
+            }
             Op::Gt | Op::GtEq | Op::Lt | Op::LtEq | Op::Eq | Op::NotEq | Op::And | Op::Or => {
                 Some(DataType::Boolean)
             }
+            Op::StringConcat => Some(DataType::Utf8),
             o => todo!("not supported binary operator: {:?}", o),
         };
         Ok(BoundExpr::BinaryOp(BoundBinaryOp {
@@ -80,6 +70,55 @@ impl Binder {
     }
 }
 
+/// Cast the operands of binary operator to the same type, the narrower operand is wrapped in
+/// `TypeCast`. The decimal operands are the exception: the integer side is casted to a decimal
+/// without losing digits, and the decimals of different precision and scale are computed as is.
+fn coerce_binary_operands(
+    left: BoundExpr,
+    right: BoundExpr,
+) -> Result<(BoundExpr, BoundExpr), BindError> {
+    let (left_type, right_type) = match (left.return_type(), right.return_type()) {
+        (None, None) => return Ok((left, right)),
+        (Some(l), Some(r)) => (l, r),
+        (l, r) => {
+            return Err(BindError::BinaryOpTypeMismatch(
+                format!("{:?}", l),
+                format!("{:?}", r),
+            ))
+        }
+    };
+    match (&left_type, &right_type) {
+        (DataType::Decimal(..), DataType::Decimal(..)) => Ok((left, right)),
+        // the interval is added to the timestamp as is
+        (DataType::Timestamp(..), DataType::Interval(_)) => Ok((left, right)),
+        (DataType::Decimal(..), DataType::Int32 | DataType::Int64) => {
+            Ok((left, cast_integer_to_decimal(right, &right_type)))
+        }
+        (DataType::Int32 | DataType::Int64, DataType::Decimal(..)) => {
+            Ok((cast_integer_to_decimal(left, &left_type), right))
+        }
+        _ => match common_supertype(&left_type, &right_type) {
+            Some(t) => Ok((cast_to(left, t.clone()), cast_to(right, t))),
+            None => Err(BindError::BinaryOpTypeMismatch(
+                left_type.to_string(),
+                right_type.to_string(),
+            )),
+        },
+    }
+}
+
+fn cast_integer_to_decimal(expr: BoundExpr, integer_type: &DataType) -> BoundExpr {
+    let bits = match integer_type {
+        DataType::Int32 => 32,
+        _ => 64,
+    };
+    let (precision, scale) = integer_decimal_type(bits);
+    BoundExpr::TypeCast(BoundTypeCast {
+        expr: Box::new(expr),
+        cast_type: DataType::Decimal(precision as usize, scale as usize),
+    })
+}
+
 impl fmt::Debug for BoundBinaryOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?} {} {:?}", self.left, self.op, self.right)
@@ -0,0 +1,118 @@
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::{BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, Value};
+
+use super::{common_supertype, common_type, BoundExpr};
+use crate::binder::{BindError, Binder, BoundTypeCast};
+
+#[derive(Clone, PartialEq)]
+pub struct BoundCase {
+    /// the (when, then) branches, when expr is evaluated to a boolean mask.
+    pub when_then: Vec<(BoundExpr, BoundExpr)>,
+    /// the else expr, NULL if absent.
+    pub else_expr: Option<Box<BoundExpr>>,
+    pub return_type: DataType,
+}
+
+impl Binder {
+    pub fn bind_case(
+        &mut self,
+        operand: Option<&Expr>,
+        conditions: &[Expr],
+        results: &[Expr],
+        else_result: Option<&Expr>,
+    ) -> Result<BoundExpr, BindError> {
+        let mut when_then = vec![];
+        for (condition, result) in conditions.iter().zip(results.iter()) {
+            let when = match operand {
+                // simple case `CASE a WHEN b THEN ...` is equal to `CASE WHEN a = b THEN ...`
+                Some(operand) => self.bind_binary_op(operand, &BinaryOperator::Eq, condition)?,
+                None => self.bind_expr(condition)?,
+            };
+            let then = self.bind_expr(result)?;
+            when_then.push((when, then));
+        }
+        let else_expr = else_result.map(|e| self.bind_expr(e)).transpose()?;
+
+        // the branches are unified to their common type, e.g. `THEN 1 ELSE 1.5` is Float64, and the
+        // NULL branch takes the type of the others. Every branch is casted to it.
+        let branch_types = when_then
+            .iter()
+            .map(|(_, then)| then)
+            .chain(else_expr.iter())
+            .filter_map(|e| e.return_type())
+            .collect::<Vec<_>>();
+        let return_type = branch_types
+            .iter()
+            .try_fold(DataType::Null, |common, t| common_supertype(&common, t))
+            .ok_or_else(|| BindError::CaseTypeMismatch(format!("{:?}", branch_types)))?;
+        let cast_branch = |expr: BoundExpr| match expr.return_type() {
+            Some(t) if t != DataType::Null && t != return_type => {
+                BoundExpr::TypeCast(BoundTypeCast {
+                    expr: Box::new(expr),
+                    cast_type: return_type.clone(),
+                })
+            }
+            _ => expr,
+        };
+        let when_then = when_then
+            .into_iter()
+            .map(|(when, then)| (when, cast_branch(then)))
+            .collect();
+        let else_expr = else_expr.map(|e| Box::new(cast_branch(e)));
+
+        Ok(BoundExpr::Case(BoundCase {
+            when_then,
+            else_expr,
+            return_type,
+        }))
+    }
+
+    /// `NULLIF(a, b)` is equal to `CASE WHEN a = b THEN NULL ELSE a END`, which is commonly used
+    /// as `x / NULLIF(y, 0)` to get NULL instead of a divide-by-zero error.
+    pub fn bind_nullif(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        let args = func
+            .args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => match arg {
+                    FunctionArgExpr::Expr(expr) => Ok(expr.clone()),
+                    _ => Err(BindError::InvalidFunctionArgs(func.name.to_string())),
+                },
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if args.len() != 2 {
+            return Err(BindError::InvalidFunctionArgs(func.name.to_string()));
+        }
+        // both arguments must be comparable, e.g. `NULLIF(1, 'a')` is invalid
+        let bound_args = vec![self.bind_expr(&args[0])?, self.bind_expr(&args[1])?];
+        if common_type(&bound_args).is_none() {
+            return Err(BindError::InvalidFunctionArgs(func.name.to_string()));
+        }
+        let condition = Expr::BinaryOp {
+            left: Box::new(args[0].clone()),
+            op: BinaryOperator::Eq,
+            right: Box::new(args[1].clone()),
+        };
+        self.bind_case(
+            None,
+            &[condition],
+            &[Expr::Value(Value::Null)],
+            Some(&args[0]),
+        )
+    }
+}
+
+impl fmt::Debug for BoundCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CASE")?;
+        for (when, then) in &self.when_then {
+            write!(f, " WHEN {:?} THEN {:?}", when, then)?;
+        }
+        if let Some(else_expr) = &self.else_expr {
+            write!(f, " ELSE {:?}", else_expr)?;
+        }
+        write!(f, " END")
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::Expr;
+
+use super::{cast_to, common_type, BoundExpr};
+use crate::binder::{BindError, Binder};
+
+#[derive(Clone, PartialEq)]
+pub struct BoundInList {
+    pub expr: Box<BoundExpr>,
+    pub list: Vec<BoundExpr>,
+    pub negated: bool,
+}
+
+impl Binder {
+    pub fn bind_in_list(
+        &mut self,
+        expr: &Expr,
+        list: &[Expr],
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let mut exprs = vec![self.bind_expr(expr)?];
+        for item in list {
+            exprs.push(self.bind_expr(item)?);
+        }
+        let mut exprs = coerce_compared_exprs(exprs)?
+            .into_iter()
+            .map(|e| self.collate(e));
+        let expr = exprs.next().unwrap();
+        Ok(BoundExpr::InList(BoundInList {
+            expr: Box::new(expr),
+            list: exprs.collect(),
+            negated,
+        }))
+    }
+}
+
+/// Cast the target and the exprs compared with it to their common supertype, so that neither side
+/// is narrowed, e.g. `int_col IN (2.5)` is compared as Float64. The NULL exprs are kept as is and
+/// aligned to the target type by the evaluator. The integers compared with a decimal target are
+/// cast to the decimal type, as there is no common supertype of them.
+pub(super) fn coerce_compared_exprs(exprs: Vec<BoundExpr>) -> Result<Vec<BoundExpr>, BindError> {
+    let target_type = exprs[0].return_type();
+    let common = match common_type(&exprs) {
+        Some(common) => common,
+        None => match &target_type {
+            Some(DataType::Decimal(..))
+                if exprs[1..].iter().all(|e| {
+                    matches!(
+                        e.return_type(),
+                        Some(DataType::Int32 | DataType::Int64 | DataType::Null)
+                    ) || e.return_type() == target_type
+                }) =>
+            {
+                target_type.clone().unwrap()
+            }
+            _ => {
+                let types = exprs.iter().map(|e| e.return_type()).collect::<Vec<_>>();
+                return Err(BindError::BinaryOpTypeMismatch(
+                    format!("{:?}", types[0]),
+                    format!("{:?}", &types[1..]),
+                ));
+            }
+        },
+    };
+    Ok(exprs
+        .into_iter()
+        .map(|e| match e.return_type() {
+            Some(DataType::Null) | None => e,
+            _ => cast_to(e, common.clone()),
+        })
+        .collect())
+}
+
+impl fmt::Debug for BoundInList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = if self.negated { "NOT IN" } else { "IN" };
+        write!(f, "{:?} {} {:?}", self.expr, op, self.list)
+    }
+}
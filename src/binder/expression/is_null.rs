@@ -0,0 +1,34 @@
+use std::fmt;
+
+use sqlparser::ast::Expr;
+
+use super::BoundExpr;
+use crate::binder::{BindError, Binder};
+
+/// `expr IS NULL`, or `expr IS NOT NULL` if negated.
+#[derive(Clone, PartialEq)]
+pub struct BoundIsNull {
+    pub expr: Box<BoundExpr>,
+    pub negated: bool,
+}
+
+impl Binder {
+    pub fn bind_is_null(&mut self, expr: &Expr, negated: bool) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        Ok(BoundExpr::IsNull(BoundIsNull {
+            expr: Box::new(expr),
+            negated,
+        }))
+    }
+}
+
+impl fmt::Debug for BoundIsNull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = if self.negated {
+            "IS NOT NULL"
+        } else {
+            "IS NULL"
+        };
+        write!(f, "{:?} {}", self.expr, op)
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::Expr;
+
+use super::BoundExpr;
+use crate::binder::{BindError, Binder};
+
+/// `expr [NOT] LIKE pattern`, the pattern supports `%` to match any sequence of characters and
+/// `_` to match any single character.
+#[derive(Clone, PartialEq)]
+pub struct BoundLike {
+    pub expr: Box<BoundExpr>,
+    pub pattern: Box<BoundExpr>,
+    pub negated: bool,
+}
+
+impl Binder {
+    pub fn bind_like(
+        &mut self,
+        expr: &Expr,
+        pattern: &Expr,
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let pattern = self.bind_expr(pattern)?;
+        let is_string =
+            |e: &BoundExpr| matches!(e.return_type(), Some(DataType::Utf8) | Some(DataType::Null));
+        if !is_string(&expr) || !is_string(&pattern) {
+            return Err(BindError::BinaryOpTypeMismatch(
+                format!("{:?}", expr.return_type()),
+                format!("{:?}", pattern.return_type()),
+            ));
+        }
+        Ok(BoundExpr::Like(BoundLike {
+            expr: Box::new(expr),
+            pattern: Box::new(pattern),
+            negated,
+        }))
+    }
+}
+
+impl fmt::Debug for BoundLike {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = if self.negated { "NOT LIKE" } else { "LIKE" };
+        write!(f, "{:?} {} {:?}", self.expr, op, self.pattern)
+    }
+}
@@ -1,16 +1,40 @@
 mod agg_func;
+mod between;
 mod binary_op;
+mod case;
+mod in_list;
+mod is_null;
+mod like;
+mod not;
+mod scalar_func;
+mod subquery;
+mod window_func;
+
 use std::{fmt, slice};
 
 pub use agg_func::*;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, TimeUnit};
+pub use between::*;
 pub use binary_op::*;
+pub use case::*;
+pub use in_list::*;
+pub use is_null::*;
 use itertools::Itertools;
-use sqlparser::ast::{Expr, Ident};
+pub use like::*;
+pub use not::*;
+pub use scalar_func::*;
+use sqlparser::ast::{
+    BinaryOperator, DataType as SqlDataType, DateTimeField, Expr, Ident, UnaryOperator, Value,
+};
+pub use subquery::*;
+pub use window_func::*;
 
 use super::{BindError, Binder};
 use crate::catalog::ColumnCatalog;
-use crate::types::ScalarValue;
+use crate::types::{
+    parse_date, parse_decimal, parse_interval, parse_timestamp, rescale_decimal, ScalarValue,
+    MAX_DECIMAL_PRECISION,
+};
 
 #[derive(Clone, PartialEq)]
 pub enum BoundExpr {
@@ -21,6 +45,15 @@ pub enum BoundExpr {
     BinaryOp(BoundBinaryOp),
     TypeCast(BoundTypeCast),
     AggFunc(BoundAggFunc),
+    Case(BoundCase),
+    InList(BoundInList),
+    Between(BoundBetween),
+    Like(BoundLike),
+    IsNull(BoundIsNull),
+    Not(BoundNot),
+    ScalarFunc(BoundScalarFunc),
+    Subquery(BoundSubquery),
+    WindowFunc(BoundWindowFunc),
 }
 
 impl BoundExpr {
@@ -34,6 +67,15 @@ impl BoundExpr {
             BoundExpr::BinaryOp(binary_op) => binary_op.return_type.clone(),
             BoundExpr::TypeCast(tc) => Some(tc.cast_type.clone()),
             BoundExpr::AggFunc(agg) => Some(agg.return_type.clone()),
+            BoundExpr::Case(case) => Some(case.return_type.clone()),
+            BoundExpr::InList(_) => Some(DataType::Boolean),
+            BoundExpr::Between(_) => Some(DataType::Boolean),
+            BoundExpr::Like(_) => Some(DataType::Boolean),
+            BoundExpr::IsNull(_) => Some(DataType::Boolean),
+            BoundExpr::Not(_) => Some(DataType::Boolean),
+            BoundExpr::ScalarFunc(func) => Some(func.return_type.clone()),
+            BoundExpr::Subquery(subquery) => Some(subquery.return_type.clone()),
+            BoundExpr::WindowFunc(func) => Some(func.return_type.clone()),
         }
     }
 }
@@ -65,10 +107,102 @@ impl Binder {
                 self.bind_column_ref_from_identifiers(slice::from_ref(ident))
             }
             Expr::CompoundIdentifier(idents) => self.bind_column_ref_from_identifiers(idents),
+            Expr::BinaryOp {
+                left,
+                op: op @ (BinaryOperator::Like | BinaryOperator::NotLike),
+                right,
+            } => self.bind_like(left, right, *op == BinaryOperator::NotLike),
             Expr::BinaryOp { left, op, right } => self.bind_binary_op(left, op, right),
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => self.bind_not(expr),
+            // `-expr` is equal to `0 - expr`
+            Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr,
+            } => self.bind_binary_op(
+                &Expr::Value(Value::Number("0".to_string(), false)),
+                &BinaryOperator::Minus,
+                expr,
+            ),
+            Expr::UnaryOp {
+                op: UnaryOperator::Plus,
+                expr,
+            } => self.bind_expr(expr),
             Expr::UnaryOp { op: _, expr: _ } => todo!(),
+            Expr::Nested(expr) => self.bind_expr(expr),
+            Expr::Value(Value::Interval {
+                value,
+                leading_field,
+                last_field,
+                ..
+            }) => bind_interval(value, leading_field.as_ref(), last_field.as_ref()),
             Expr::Value(v) => Ok(BoundExpr::Constant(v.into())),
+            Expr::Function(func) if func.over.is_some() => self.bind_window_func(func),
+            Expr::Function(func) if func.name.to_string().eq_ignore_ascii_case("nullif") => {
+                self.bind_nullif(func)
+            }
+            Expr::Function(func) if func.name.to_string().eq_ignore_ascii_case("date_part") => {
+                self.bind_date_part(func)
+            }
+            Expr::Function(func) if func.name.to_string().eq_ignore_ascii_case("random") => {
+                self.bind_random(func)
+            }
+            Expr::Function(func) if is_current_time_func(&func.name.to_string()) => {
+                self.bind_current_time(func)
+            }
+            Expr::Function(func) if ScalarFunc::from_name(&func.name.to_string()).is_some() => {
+                self.bind_scalar_func(func)
+            }
             Expr::Function(func) => self.bind_agg_func(func),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.bind_case(
+                operand.as_deref(),
+                conditions,
+                results,
+                else_result.as_deref(),
+            ),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => self.bind_in_list(expr, list, *negated),
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => self.bind_between(expr, *negated, low, high),
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => self.bind_substring(expr, substring_from.as_deref(), substring_for.as_deref()),
+            Expr::Trim { expr, trim_where } => self.bind_trim(expr, trim_where.as_ref()),
+            Expr::IsNull(expr) => self.bind_is_null(expr, false),
+            Expr::IsNotNull(expr) => self.bind_is_null(expr, true),
+            Expr::Cast { expr, data_type } => Ok(BoundExpr::TypeCast(BoundTypeCast {
+                expr: Box::new(self.bind_expr(expr)?),
+                cast_type: bind_data_type(data_type)?,
+            })),
+            Expr::Extract { field, expr } => self.bind_extract(&field.to_string(), expr),
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => self.bind_at_time_zone(timestamp, time_zone),
+            Expr::TypedString { data_type, value } => bind_typed_string(data_type, value),
+            Expr::Array(array) => self.bind_array(&array.elem),
+            Expr::ArrayIndex { obj, indexs } => self.bind_array_index(obj, indexs),
+            Expr::Subquery(query) => self.bind_scalar_subquery(query),
+            Expr::Exists { subquery, negated } => self.bind_exists_subquery(subquery, *negated),
+            Expr::InSubquery { .. } => Err(BindError::UnsupportedStmt(
+                "IN subquery outside the conjunction of WHERE".to_string(),
+            )),
             _ => todo!("unsupported expr {:?}", expr),
         }
     }
@@ -98,28 +232,119 @@ impl Binder {
         };
 
         if let Some(table) = table_name {
-            let table_catalog = self.context.tables.get(table).unwrap();
+            let table_catalog = self
+                .context
+                .tables
+                .get(table)
+                .ok_or_else(|| BindError::InvalidTable(table.clone()))?;
             let column_catalog = table_catalog
                 .get_column_by_name(column_name)
                 .ok_or_else(|| BindError::InvalidColumn(column_name.clone()))?;
+            let column_catalog = self.resolve_using_column(column_catalog);
             Ok(BoundExpr::ColumnRef(BoundColumnRef { column_catalog }))
         } else {
-            let mut got_column = None;
-            for table_catalog in self.context.tables.values() {
-                // TODO: add ambiguous column check
-                got_column = Some(
-                    table_catalog
-                        .get_column_by_name(column_name)
-                        .ok_or_else(|| BindError::InvalidColumn(column_name.clone()))?,
-                );
+            // the bare column should be found in exactly one table, the shared column of `USING`
+            // or `NATURAL` join is found as the left key
+            let mut got_columns = self
+                .context
+                .tables
+                .values()
+                .filter_map(|table_catalog| table_catalog.get_column_by_name(column_name))
+                .filter(|column| !self.is_merged_using_column(column));
+            let column_catalog = got_columns
+                .next()
+                .ok_or_else(|| BindError::InvalidColumn(column_name.clone()))?;
+            if got_columns.next().is_some() {
+                return Err(BindError::AmbiguousColumn(column_name.clone()));
             }
-            let column_catalog =
-                got_column.ok_or_else(|| BindError::InvalidColumn(column_name.clone()))?;
             Ok(BoundExpr::ColumnRef(BoundColumnRef { column_catalog }))
         }
     }
 }
 
+/// Convert the sql data type into arrow data type.
+pub fn bind_data_type(data_type: &SqlDataType) -> Result<DataType, BindError> {
+    Ok(match data_type {
+        SqlDataType::Boolean => DataType::Boolean,
+        SqlDataType::Int(_) => DataType::Int32,
+        SqlDataType::BigInt(_) => DataType::Int64,
+        SqlDataType::Float(_) | SqlDataType::Real | SqlDataType::Double => DataType::Float64,
+        SqlDataType::Char(_)
+        | SqlDataType::Varchar(_)
+        | SqlDataType::Text
+        | SqlDataType::String => DataType::Utf8,
+        SqlDataType::Date => DataType::Date32,
+        SqlDataType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        // the default precision is the max one and the default scale is 0
+        SqlDataType::Decimal(precision, scale) => {
+            let precision = precision.unwrap_or(MAX_DECIMAL_PRECISION as u64);
+            let scale = scale.unwrap_or(0);
+            if precision == 0 || precision > MAX_DECIMAL_PRECISION as u64 || scale > precision {
+                return Err(BindError::UnsupportedDataType(data_type.to_string()));
+            }
+            DataType::Decimal(precision as usize, scale as usize)
+        }
+        _ => return Err(BindError::UnsupportedDataType(data_type.to_string())),
+    })
+}
+
+/// bind the literal with type name like `DECIMAL '12.50'` and `DATE '2020-01-01'`
+fn bind_typed_string(data_type: &SqlDataType, value: &str) -> Result<BoundExpr, BindError> {
+    let invalid_literal = || BindError::InvalidLiteral(format!("{} '{}'", data_type, value));
+    let scalar = match data_type {
+        // the precision and scale of literal are used if not specified
+        SqlDataType::Decimal(None, None) => {
+            let (v, p, s) = parse_decimal(value).ok_or_else(invalid_literal)?;
+            ScalarValue::Decimal128(Some(v), p, s)
+        }
+        SqlDataType::Decimal(..) => {
+            let (v, _, literal_scale) = parse_decimal(value).ok_or_else(invalid_literal)?;
+            let (p, s) = match bind_data_type(data_type)? {
+                DataType::Decimal(p, s) => (p as u8, s as u8),
+                _ => unreachable!(),
+            };
+            let v = rescale_decimal(v, literal_scale, s).ok_or_else(invalid_literal)?;
+            ScalarValue::Decimal128(Some(v), p, s)
+        }
+        SqlDataType::Date => {
+            ScalarValue::Date32(Some(parse_date(value).ok_or_else(invalid_literal)?))
+        }
+        SqlDataType::Timestamp => {
+            ScalarValue::Timestamp(Some(parse_timestamp(value).ok_or_else(invalid_literal)?))
+        }
+        _ => match bind_data_type(data_type)? {
+            DataType::Utf8 => ScalarValue::String(Some(value.to_string())),
+            _ => return Err(invalid_literal()),
+        },
+    };
+    Ok(BoundExpr::Constant(scalar))
+}
+
+/// bind the interval literal like `INTERVAL '1 month 2 days'` or `INTERVAL '3' DAY`, the range of
+/// fields like `INTERVAL '1-2' YEAR TO MONTH` is not supported.
+fn bind_interval(
+    value: &Expr,
+    leading_field: Option<&DateTimeField>,
+    last_field: Option<&DateTimeField>,
+) -> Result<BoundExpr, BindError> {
+    let value = match value {
+        Expr::Value(Value::SingleQuotedString(s)) => s,
+        _ => return Err(BindError::InvalidLiteral(format!("INTERVAL {}", value))),
+    };
+    let literal = match leading_field {
+        Some(field) => format!("{} {}", value, field),
+        None => value.clone(),
+    };
+    let invalid_literal = || BindError::InvalidLiteral(format!("INTERVAL '{}'", literal));
+    if last_field.is_some() {
+        return Err(invalid_literal());
+    }
+    let interval = parse_interval(&literal).ok_or_else(invalid_literal)?;
+    Ok(BoundExpr::Constant(ScalarValue::IntervalMonthDayNano(
+        Some(interval),
+    )))
+}
+
 impl fmt::Debug for BoundExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -129,6 +354,15 @@ impl fmt::Debug for BoundExpr {
             BoundExpr::BinaryOp(binary_op) => write!(f, "{:?}", binary_op),
             BoundExpr::TypeCast(type_cast) => write!(f, "{:?}", type_cast),
             BoundExpr::AggFunc(agg_func) => write!(f, "{:?}", agg_func),
+            BoundExpr::Case(case) => write!(f, "{:?}", case),
+            BoundExpr::InList(in_list) => write!(f, "{:?}", in_list),
+            BoundExpr::Between(between) => write!(f, "{:?}", between),
+            BoundExpr::Like(like) => write!(f, "{:?}", like),
+            BoundExpr::IsNull(is_null) => write!(f, "{:?}", is_null),
+            BoundExpr::Not(not) => write!(f, "{:?}", not),
+            BoundExpr::ScalarFunc(func) => write!(f, "{:?}", func),
+            BoundExpr::Subquery(subquery) => write!(f, "{:?}", subquery),
+            BoundExpr::WindowFunc(func) => write!(f, "{:?}", func),
         }
     }
 }
@@ -0,0 +1,44 @@
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::Expr;
+
+use super::BoundExpr;
+use crate::binder::{BindError, Binder, BoundTypeCast};
+
+/// Logical `NOT expr`, NULL input produces NULL.
+#[derive(Clone, PartialEq)]
+pub struct BoundNot {
+    pub expr: Box<BoundExpr>,
+}
+
+impl Binder {
+    pub fn bind_not(&mut self, expr: &Expr) -> Result<BoundExpr, BindError> {
+        let mut expr = self.bind_expr(expr)?;
+        match expr.return_type() {
+            Some(DataType::Boolean) => {}
+            // `NOT NULL` is NULL of boolean type
+            Some(DataType::Null) => {
+                expr = BoundExpr::TypeCast(BoundTypeCast {
+                    expr: Box::new(expr),
+                    cast_type: DataType::Boolean,
+                })
+            }
+            t => {
+                return Err(BindError::BinaryOpTypeMismatch(
+                    format!("{:?}", t),
+                    format!("{:?}", DataType::Boolean),
+                ))
+            }
+        }
+        Ok(BoundExpr::Not(BoundNot {
+            expr: Box::new(expr),
+        }))
+    }
+}
+
+impl fmt::Debug for BoundNot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NOT {:?}", self.expr)
+    }
+}
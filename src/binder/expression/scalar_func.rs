@@ -0,0 +1,583 @@
+use std::fmt;
+use std::time::UNIX_EPOCH;
+
+use arrow::datatypes::{DataType, Field};
+use itertools::Itertools;
+use sqlparser::ast::{Expr, Function, FunctionArg, FunctionArgExpr, TrimWhereField, Value};
+
+use super::BoundExpr;
+use crate::binder::{BindError, Binder, BoundTypeCast};
+use crate::types::{format_time_zone, parse_time_zone, Collation, ScalarValue, SessionRng};
+
+const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+pub fn is_current_time_func(name: &str) -> bool {
+    matches!(
+        name.to_lowercase().as_str(),
+        "current_date" | "current_timestamp" | "now"
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarFunc {
+    Concat,
+    Upper,
+    Lower,
+    Length,
+    Substring,
+    Trim,
+    Ltrim,
+    Rtrim,
+    Abs,
+    Ceil,
+    Floor,
+    Round,
+    Greatest,
+    Least,
+    Coalesce,
+    Extract(DateTimeField),
+    /// `ts AT TIME ZONE zone` of the zone in seconds east of UTC, which takes the naive timestamp
+    /// as the local time of zone and produces the timestamp with zone, or the reverse
+    AtTimeZone(i32),
+    Random(SessionRng),
+    MakeArray,
+    ArrayLength,
+    ArrayElement,
+    /// the sort key of string by the session collation, which the string comparisons are made on
+    CollationKey(Collation),
+}
+
+/// The field of date and timestamp which can be extracted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateTimeField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateTimeField {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "year" => Some(DateTimeField::Year),
+            "month" => Some(DateTimeField::Month),
+            "day" => Some(DateTimeField::Day),
+            "hour" => Some(DateTimeField::Hour),
+            "minute" => Some(DateTimeField::Minute),
+            "second" => Some(DateTimeField::Second),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarFunc {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "concat" => Some(ScalarFunc::Concat),
+            "upper" => Some(ScalarFunc::Upper),
+            "lower" => Some(ScalarFunc::Lower),
+            "length" => Some(ScalarFunc::Length),
+            "substring" | "substr" => Some(ScalarFunc::Substring),
+            "trim" | "btrim" => Some(ScalarFunc::Trim),
+            "ltrim" => Some(ScalarFunc::Ltrim),
+            "rtrim" => Some(ScalarFunc::Rtrim),
+            "abs" => Some(ScalarFunc::Abs),
+            "ceil" | "ceiling" => Some(ScalarFunc::Ceil),
+            "floor" => Some(ScalarFunc::Floor),
+            "round" => Some(ScalarFunc::Round),
+            "greatest" => Some(ScalarFunc::Greatest),
+            "least" => Some(ScalarFunc::Least),
+            "coalesce" => Some(ScalarFunc::Coalesce),
+            "array_length" => Some(ScalarFunc::ArrayLength),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ScalarFunc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScalarFunc::Concat => write!(f, "Concat"),
+            ScalarFunc::Upper => write!(f, "Upper"),
+            ScalarFunc::Lower => write!(f, "Lower"),
+            ScalarFunc::Length => write!(f, "Length"),
+            ScalarFunc::Substring => write!(f, "Substring"),
+            ScalarFunc::Trim => write!(f, "Trim"),
+            ScalarFunc::Ltrim => write!(f, "Ltrim"),
+            ScalarFunc::Rtrim => write!(f, "Rtrim"),
+            ScalarFunc::Abs => write!(f, "Abs"),
+            ScalarFunc::Ceil => write!(f, "Ceil"),
+            ScalarFunc::Floor => write!(f, "Floor"),
+            ScalarFunc::Round => write!(f, "Round"),
+            ScalarFunc::Greatest => write!(f, "Greatest"),
+            ScalarFunc::Least => write!(f, "Least"),
+            ScalarFunc::Coalesce => write!(f, "Coalesce"),
+            ScalarFunc::Extract(field) => write!(f, "Extract({:?})", field),
+            ScalarFunc::AtTimeZone(offset) => {
+                write!(f, "AtTimeZone({})", format_time_zone(*offset))
+            }
+            ScalarFunc::Random(_) => write!(f, "Random"),
+            ScalarFunc::MakeArray => write!(f, "MakeArray"),
+            ScalarFunc::ArrayLength => write!(f, "ArrayLength"),
+            ScalarFunc::ArrayElement => write!(f, "ArrayElement"),
+            ScalarFunc::CollationKey(collation) => write!(f, "CollationKey({})", collation),
+        }
+    }
+}
+
+/// An overload of scalar function, the arguments are casted to the parameter types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuncSignature {
+    pub params: Vec<DataType>,
+    pub return_type: DataType,
+}
+
+impl ScalarFunc {
+    /// The overloads of the function in the order of preference, empty if the function checks
+    /// its arguments by itself, e.g. the variadic ones.
+    pub fn signatures(&self) -> Vec<FuncSignature> {
+        use DataType::*;
+        let sig = |params: &[DataType], return_type: DataType| FuncSignature {
+            params: params.to_vec(),
+            return_type,
+        };
+        match self {
+            ScalarFunc::Upper | ScalarFunc::Lower => vec![sig(&[Utf8], Utf8)],
+            ScalarFunc::Length => vec![sig(&[Utf8], Int64)],
+            // the numeric functions preserve the input type, the float one goes first so that
+            // NULL input is treated as float
+            ScalarFunc::Abs | ScalarFunc::Ceil | ScalarFunc::Floor => vec![
+                sig(&[Float64], Float64),
+                sig(&[Int64], Int64),
+                sig(&[Int32], Int32),
+            ],
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct BoundScalarFunc {
+    pub func: ScalarFunc,
+    pub args: Vec<BoundExpr>,
+    pub return_type: DataType,
+}
+
+impl Binder {
+    pub fn bind_scalar_func(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        let scalar_func = ScalarFunc::from_name(&func.name.to_string())
+            .ok_or_else(|| BindError::InvalidFunctionArgs(func.name.to_string()))?;
+        let mut args = vec![];
+        for arg in &func.args {
+            let arg = match arg {
+                FunctionArg::Named { arg, .. } => arg,
+                FunctionArg::Unnamed(arg) => arg,
+            };
+            match arg {
+                FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr)?),
+                _ => return Err(BindError::InvalidFunctionArgs(func.name.to_string())),
+            }
+        }
+        build_scalar_func(scalar_func, args)
+    }
+
+    /// bind `current_date`, `current_timestamp` and `now()` to the constant of query start time.
+    pub fn bind_current_time(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        if !func.args.is_empty() {
+            return Err(BindError::InvalidFunctionArgs(func.name.to_string()));
+        }
+        let micros = self
+            .query_start
+            .duration_since(UNIX_EPOCH)
+            .expect("query start time is before UNIX epoch")
+            .as_micros() as i64;
+        let value = match func.name.to_string().to_lowercase().as_str() {
+            "current_date" => ScalarValue::Date32(Some((micros / MICROS_PER_DAY) as i32)),
+            _ => ScalarValue::Timestamp(Some(micros)),
+        };
+        Ok(BoundExpr::Constant(value))
+    }
+
+    /// bind `random()` to draw from the session rng, which yields a new float per row.
+    pub fn bind_random(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        if !func.args.is_empty() {
+            return Err(BindError::InvalidFunctionArgs(func.name.to_string()));
+        }
+        build_scalar_func(ScalarFunc::Random(self.rng.clone()), vec![])
+    }
+
+    /// Compare the string expr by the sort key of the session collation, other exprs are kept.
+    pub(crate) fn collate(&self, expr: BoundExpr) -> BoundExpr {
+        if self.collation == Collation::Binary || expr.return_type() != Some(DataType::Utf8) {
+            return expr;
+        }
+        build_scalar_func(ScalarFunc::CollationKey(self.collation), vec![expr])
+            .expect("collation key of string expr")
+    }
+
+    /// bind `ARRAY[expr, ...]` to a list of the common type of elements.
+    pub fn bind_array(&mut self, elems: &[Expr]) -> Result<BoundExpr, BindError> {
+        let args: Vec<_> = elems.iter().map(|e| self.bind_expr(e)).try_collect()?;
+        build_scalar_func(ScalarFunc::MakeArray, args)
+    }
+
+    /// bind `arr[i][j]`, the index is 1-based and out of range index produces NULL.
+    pub fn bind_array_index(
+        &mut self,
+        obj: &Expr,
+        indexes: &[Expr],
+    ) -> Result<BoundExpr, BindError> {
+        let mut array = self.bind_expr(obj)?;
+        for index in indexes {
+            let index = self.bind_expr(index)?;
+            array = build_scalar_func(ScalarFunc::ArrayElement, vec![array, index])?;
+        }
+        Ok(array)
+    }
+
+    /// bind `EXTRACT(field FROM expr)`
+    pub fn bind_extract(&mut self, field: &str, expr: &Expr) -> Result<BoundExpr, BindError> {
+        let field = DateTimeField::from_name(field)
+            .ok_or_else(|| BindError::UnsupportedDateTimeField(field.to_string()))?;
+        let args = vec![self.bind_expr(expr)?];
+        build_scalar_func(ScalarFunc::Extract(field), args)
+    }
+
+    /// bind `ts AT TIME ZONE 'zone'`, only UTC and the fixed offsets are supported
+    pub fn bind_at_time_zone(
+        &mut self,
+        expr: &Expr,
+        time_zone: &str,
+    ) -> Result<BoundExpr, BindError> {
+        let offset = parse_time_zone(time_zone)
+            .ok_or_else(|| BindError::UnsupportedTimeZone(time_zone.to_string()))?;
+        let args = vec![self.bind_expr(expr)?];
+        build_scalar_func(ScalarFunc::AtTimeZone(offset), args)
+    }
+
+    /// bind `date_part('field', expr)`, which is equal to `EXTRACT(field FROM expr)`
+    pub fn bind_date_part(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        let invalid_args = || BindError::InvalidFunctionArgs(func.name.to_string());
+        let args = func
+            .args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => match arg {
+                    FunctionArgExpr::Expr(expr) => Ok(expr),
+                    _ => Err(invalid_args()),
+                },
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match args.as_slice() {
+            [Expr::Value(Value::SingleQuotedString(field)), expr] => self.bind_extract(field, expr),
+            _ => Err(invalid_args()),
+        }
+    }
+
+    /// bind `SUBSTRING(expr FROM start FOR length)`, the start is 1 if absent.
+    pub fn bind_substring(
+        &mut self,
+        expr: &Expr,
+        substring_from: Option<&Expr>,
+        substring_for: Option<&Expr>,
+    ) -> Result<BoundExpr, BindError> {
+        let mut args = vec![self.bind_expr(expr)?];
+        match substring_from {
+            Some(from) => args.push(self.bind_expr(from)?),
+            None => args.push(BoundExpr::Constant(ScalarValue::Int64(Some(1)))),
+        }
+        if let Some(len) = substring_for {
+            args.push(self.bind_expr(len)?);
+        }
+        build_scalar_func(ScalarFunc::Substring, args)
+    }
+
+    /// bind `TRIM([BOTH | LEADING | TRAILING] [characters FROM] expr)`
+    pub fn bind_trim(
+        &mut self,
+        expr: &Expr,
+        trim_where: Option<&(TrimWhereField, Box<Expr>)>,
+    ) -> Result<BoundExpr, BindError> {
+        let mut args = vec![self.bind_expr(expr)?];
+        let func = match trim_where {
+            Some((field, characters)) => {
+                args.push(self.bind_expr(characters)?);
+                match field {
+                    TrimWhereField::Both => ScalarFunc::Trim,
+                    TrimWhereField::Leading => ScalarFunc::Ltrim,
+                    TrimWhereField::Trailing => ScalarFunc::Rtrim,
+                }
+            }
+            None => ScalarFunc::Trim,
+        };
+        build_scalar_func(func, args)
+    }
+}
+
+/// Check the arguments and resolve the return type of scalar function.
+fn build_scalar_func(func: ScalarFunc, args: Vec<BoundExpr>) -> Result<BoundExpr, BindError> {
+    let invalid_args = || BindError::InvalidFunctionArgs(func.to_string());
+    let (args, return_type) = match func {
+        // concat accepts any number of arguments of any type, which are casted to string
+        ScalarFunc::Concat => {
+            if args.is_empty() {
+                return Err(invalid_args());
+            }
+            let args = args.into_iter().map(cast_to_string).collect();
+            (args, DataType::Utf8)
+        }
+        ScalarFunc::Upper
+        | ScalarFunc::Lower
+        | ScalarFunc::Length
+        | ScalarFunc::Abs
+        | ScalarFunc::Ceil
+        | ScalarFunc::Floor => resolve_signature(&func, args)?,
+        // substring(str, start [, length])
+        ScalarFunc::Substring => {
+            if !(2..=3).contains(&args.len())
+                || !is_string(&args[0])
+                || !args[1..].iter().all(is_integer)
+            {
+                return Err(invalid_args());
+            }
+            let mut args = args.into_iter();
+            let mut new_args = vec![cast_to_string(args.next().unwrap())];
+            new_args.extend(args.map(|arg| cast_to(arg, DataType::Int64)));
+            (new_args, DataType::Utf8)
+        }
+        // trim(str [, characters])
+        ScalarFunc::Trim | ScalarFunc::Ltrim | ScalarFunc::Rtrim => {
+            if !(1..=2).contains(&args.len()) || !args.iter().all(is_string) {
+                return Err(invalid_args());
+            }
+            let args = args.into_iter().map(cast_to_string).collect();
+            (args, DataType::Utf8)
+        }
+        // round(x [, digits])
+        ScalarFunc::Round => {
+            if !(1..=2).contains(&args.len())
+                || !is_numeric(&args[0])
+                || !args[1..].iter().all(is_integer)
+            {
+                return Err(invalid_args());
+            }
+            let return_type = numeric_return_type(&args[0]);
+            let mut args = args.into_iter();
+            let mut new_args = vec![cast_to(args.next().unwrap(), return_type.clone())];
+            new_args.extend(args.map(|arg| cast_to(arg, DataType::Int64)));
+            (new_args, return_type)
+        }
+        // extract(field from date or timestamp)
+        ScalarFunc::Extract(_) => {
+            if args.len() != 1 || !is_temporal(&args[0]) {
+                return Err(invalid_args());
+            }
+            (args, DataType::Int64)
+        }
+        // the naive timestamp becomes the one with zone, and the timestamp with zone becomes the
+        // naive local time of the zone
+        ScalarFunc::AtTimeZone(offset) => {
+            let return_type = match args.first().and_then(|arg| arg.return_type()) {
+                Some(DataType::Timestamp(unit, None)) if args.len() == 1 => {
+                    DataType::Timestamp(unit, Some(format_time_zone(*offset)))
+                }
+                Some(DataType::Timestamp(unit, Some(_))) if args.len() == 1 => {
+                    DataType::Timestamp(unit, None)
+                }
+                _ => return Err(invalid_args()),
+            };
+            (args, return_type)
+        }
+        ScalarFunc::Random(_) => {
+            if !args.is_empty() {
+                return Err(invalid_args());
+            }
+            (args, DataType::Float64)
+        }
+        // the elements are casted to their common type
+        ScalarFunc::MakeArray => {
+            let item_type = match common_type(&args) {
+                Some(t) if !args.is_empty() => t,
+                _ => return Err(invalid_args()),
+            };
+            let args = args
+                .into_iter()
+                .map(|arg| cast_to(arg, item_type.clone()))
+                .collect();
+            (args, list_type(item_type))
+        }
+        ScalarFunc::ArrayLength => {
+            if args.len() != 1 || list_item_type(&args[0]).is_none() {
+                return Err(invalid_args());
+            }
+            (args, DataType::Int64)
+        }
+        // array_element(arr, index)
+        ScalarFunc::ArrayElement => {
+            let item_type = match args.first().and_then(list_item_type) {
+                Some(t) if args.len() == 2 && is_integer(&args[1]) => t,
+                _ => return Err(invalid_args()),
+            };
+            let mut args = args.into_iter();
+            let new_args = vec![
+                args.next().unwrap(),
+                cast_to(args.next().unwrap(), DataType::Int64),
+            ];
+            (new_args, item_type)
+        }
+        ScalarFunc::CollationKey(_) => {
+            if args.len() != 1 || !is_string(&args[0]) {
+                return Err(invalid_args());
+            }
+            (args, DataType::Utf8)
+        }
+        // greatest/least/coalesce(x, y, ...) casts all arguments to their common type
+        ScalarFunc::Greatest | ScalarFunc::Least | ScalarFunc::Coalesce => {
+            let return_type = match common_type(&args) {
+                Some(t) if !args.is_empty() => t,
+                _ => return Err(invalid_args()),
+            };
+            let args = args
+                .into_iter()
+                .map(|arg| cast_to(arg, return_type.clone()))
+                .collect();
+            (args, return_type)
+        }
+    };
+    Ok(BoundExpr::ScalarFunc(BoundScalarFunc {
+        func,
+        args,
+        return_type,
+    }))
+}
+
+/// Pick the overload whose parameter types are exactly the argument types, or else the first one
+/// that all arguments can be implicitly casted to, i.e. the parameter type is the common
+/// supertype of the argument type and itself. Errors if no overload matches.
+fn resolve_signature(
+    func: &ScalarFunc,
+    args: Vec<BoundExpr>,
+) -> Result<(Vec<BoundExpr>, DataType), BindError> {
+    let arg_types: Option<Vec<DataType>> = args.iter().map(|arg| arg.return_type()).collect();
+    let signatures = func.signatures();
+    let castable = |sig: &&FuncSignature| match &arg_types {
+        Some(types) if types.len() == sig.params.len() => types
+            .iter()
+            .zip(sig.params.iter())
+            .all(|(t, p)| common_supertype(t, p).as_ref() == Some(p)),
+        _ => false,
+    };
+    let signature = signatures
+        .iter()
+        .find(|sig| arg_types.as_ref() == Some(&sig.params))
+        .or_else(|| signatures.iter().find(castable))
+        .ok_or_else(|| {
+            let arg_types = args
+                .iter()
+                .map(|arg| arg.return_type().map(|t| t.to_string()).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(", ");
+            BindError::InvalidFunctionArgs(format!("{}({})", func, arg_types))
+        })?;
+    let args = args
+        .into_iter()
+        .zip(signature.params.iter())
+        .map(|(arg, param)| cast_to(arg, param.clone()))
+        .collect();
+    Ok((args, signature.return_type.clone()))
+}
+
+fn is_string(expr: &BoundExpr) -> bool {
+    matches!(
+        expr.return_type(),
+        Some(DataType::Utf8) | Some(DataType::Null)
+    )
+}
+
+fn is_integer(expr: &BoundExpr) -> bool {
+    matches!(
+        expr.return_type(),
+        Some(DataType::Int32) | Some(DataType::Int64) | Some(DataType::Null)
+    )
+}
+
+fn is_temporal(expr: &BoundExpr) -> bool {
+    matches!(
+        expr.return_type(),
+        Some(DataType::Date32) | Some(DataType::Timestamp(..))
+    )
+}
+
+/// The item type if the expr is a list.
+fn list_item_type(expr: &BoundExpr) -> Option<DataType> {
+    match expr.return_type() {
+        Some(DataType::List(field)) => Some(field.data_type().clone()),
+        _ => None,
+    }
+}
+
+pub fn list_type(item_type: DataType) -> DataType {
+    DataType::List(Box::new(Field::new("item", item_type, true)))
+}
+
+fn is_numeric(expr: &BoundExpr) -> bool {
+    is_integer(expr) || matches!(expr.return_type(), Some(DataType::Float64))
+}
+
+fn numeric_return_type(expr: &BoundExpr) -> DataType {
+    match expr.return_type() {
+        Some(t @ (DataType::Int32 | DataType::Int64)) => t,
+        _ => DataType::Float64,
+    }
+}
+
+/// The common type that all exprs can be casted to, the numeric types are widened to the biggest
+/// one and NULL is castable to any type. Returns None if the types are incompatible.
+pub fn common_type(exprs: &[BoundExpr]) -> Option<DataType> {
+    let mut common = DataType::Null;
+    for expr in exprs {
+        common = common_supertype(&common, &expr.return_type()?)?;
+    }
+    Some(common)
+}
+
+/// The smallest type that both types can be casted to without losing the integral part, e.g.
+/// `Int32` and `Int64` is `Int64`, `Int64` and `Float64` is `Float64`.
+pub fn common_supertype(left: &DataType, right: &DataType) -> Option<DataType> {
+    match (left, right) {
+        (t, DataType::Null) | (DataType::Null, t) => Some(t.clone()),
+        (l, r) if l == r => Some(l.clone()),
+        (DataType::Int32, DataType::Int64) | (DataType::Int64, DataType::Int32) => {
+            Some(DataType::Int64)
+        }
+        (DataType::Int32 | DataType::Int64 | DataType::Decimal(..), DataType::Float64)
+        | (DataType::Float64, DataType::Int32 | DataType::Int64 | DataType::Decimal(..)) => {
+            Some(DataType::Float64)
+        }
+        _ => None,
+    }
+}
+
+/// Cast the expr to target type if it is not.
+pub fn cast_to(expr: BoundExpr, data_type: DataType) -> BoundExpr {
+    match expr.return_type() {
+        Some(t) if t == data_type => expr,
+        _ => BoundExpr::TypeCast(BoundTypeCast {
+            expr: Box::new(expr),
+            cast_type: data_type,
+        }),
+    }
+}
+
+/// Cast the expr to Utf8 if it is not a string.
+pub fn cast_to_string(expr: BoundExpr) -> BoundExpr {
+    cast_to(expr, DataType::Utf8)
+}
+
+impl fmt::Debug for BoundScalarFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({:?}):{}", self.func, self.args, self.return_type)
+    }
+}
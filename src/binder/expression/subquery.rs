@@ -0,0 +1,86 @@
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::{Query, SetExpr};
+
+use super::BoundExpr;
+use crate::binder::{BindError, Binder, BoundSelect};
+
+/// The uncorrelated subquery used as an expression, e.g. `(select max(a) from t)` or
+/// `EXISTS (select ...)`. It refers to the `index`-th subquery of the clause it's bound in, and
+/// is resolved to a constant by executing the subquery once.
+#[derive(Clone, PartialEq)]
+pub struct BoundSubquery {
+    pub index: usize,
+    pub kind: SubqueryKind,
+    pub return_type: DataType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubqueryKind {
+    /// the single value of the subquery of at most one row, NULL if no row
+    Scalar,
+    /// whether the subquery returns any row
+    Exists { negated: bool },
+}
+
+impl Binder {
+    pub fn bind_scalar_subquery(&mut self, query: &Query) -> Result<BoundExpr, BindError> {
+        let subquery = self.bind_uncorrelated_subquery(query)?;
+        if subquery.select_list.len() != 1 {
+            return Err(BindError::InvalidSubquery(query.to_string()));
+        }
+        let return_type = subquery.select_list[0]
+            .return_type()
+            .unwrap_or(DataType::Null);
+        Ok(self.push_subquery(subquery, SubqueryKind::Scalar, return_type))
+    }
+
+    pub fn bind_exists_subquery(
+        &mut self,
+        query: &Query,
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let subquery = self.bind_uncorrelated_subquery(query)?;
+        Ok(self.push_subquery(
+            subquery,
+            SubqueryKind::Exists { negated },
+            DataType::Boolean,
+        ))
+    }
+
+    fn bind_uncorrelated_subquery(&mut self, query: &Query) -> Result<BoundSelect, BindError> {
+        if !matches!(query.body, SetExpr::Select(_)) {
+            return Err(BindError::InvalidSubquery(query.to_string()));
+        }
+        // the subquery is uncorrelated, so it is bound with its own tables
+        let outer_context = std::mem::take(&mut self.context);
+        let subquery = self.bind_select(query);
+        self.context = outer_context;
+        subquery
+    }
+
+    fn push_subquery(
+        &mut self,
+        subquery: BoundSelect,
+        kind: SubqueryKind,
+        return_type: DataType,
+    ) -> BoundExpr {
+        self.context.subqueries.push(subquery);
+        BoundExpr::Subquery(BoundSubquery {
+            index: self.context.subqueries.len() - 1,
+            kind,
+            return_type,
+        })
+    }
+}
+
+impl fmt::Debug for BoundSubquery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            SubqueryKind::Scalar => write!(f, "Subquery#{}:{}", self.index, self.return_type),
+            SubqueryKind::Exists { negated: false } => write!(f, "Exists#{}", self.index),
+            SubqueryKind::Exists { negated: true } => write!(f, "NotExists#{}", self.index),
+        }
+    }
+}
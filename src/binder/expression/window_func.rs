@@ -0,0 +1,252 @@
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::{
+    Function, FunctionArg, FunctionArgExpr, WindowFrameBound, WindowFrameUnits, WindowSpec,
+};
+
+use super::{cast_to, common_supertype, AggFunc, BoundExpr};
+use crate::binder::{BindError, Binder, BoundOrderBy};
+use crate::types::ScalarValue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowFunc {
+    RowNumber,
+    /// the rank of the first peer, leaving gaps after the peers
+    Rank,
+    /// the rank of the peer group, without gaps
+    DenseRank,
+    /// the aggregate over the frame of each row, which is the whole partition by default
+    Agg(AggFunc),
+    /// the value of the row at the offset before the current row, or the default if absent
+    Lag(usize),
+    /// the value of the row at the offset after the current row, or the default if absent
+    Lead(usize),
+    /// the bucket number of the row, the rows of partition are split into n buckets in order
+    Ntile(usize),
+}
+
+impl fmt::Display for WindowFunc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFunc::RowNumber => write!(f, "row_number"),
+            WindowFunc::Rank => write!(f, "rank"),
+            WindowFunc::DenseRank => write!(f, "dense_rank"),
+            WindowFunc::Agg(func) => write!(f, "{}", func),
+            WindowFunc::Lag(_) => write!(f, "lag"),
+            WindowFunc::Lead(_) => write!(f, "lead"),
+            WindowFunc::Ntile(_) => write!(f, "ntile"),
+        }
+    }
+}
+
+/// The function over the window of `OVER (PARTITION BY ... ORDER BY ...)`, which is computed for
+/// each row by the rows of the same partition in order, rather than evaluated on a batch.
+#[derive(Clone, PartialEq)]
+pub struct BoundWindowFunc {
+    pub func: WindowFunc,
+    pub args: Vec<BoundExpr>,
+    pub partition_by: Vec<BoundExpr>,
+    pub order_by: Vec<BoundOrderBy>,
+    /// the rows aggregated for each row, which is ignored by the ranking functions
+    pub frame: BoundWindowFrame,
+    pub return_type: DataType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameUnits {
+    /// the offsets are counted in rows
+    Rows,
+    /// the current row stands for all its peers, which are equal on the order keys
+    Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+/// `{ROWS | RANGE} BETWEEN start AND end`, both bounds are inclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundWindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl BoundWindowFrame {
+    /// The frame without frame clause, which is the whole partition if there is no ORDER BY,
+    /// otherwise the rows up to the peers of current row.
+    fn default_of(spec: &WindowSpec) -> Self {
+        if spec.order_by.is_empty() {
+            Self {
+                units: FrameUnits::Rows,
+                start: FrameBound::UnboundedPreceding,
+                end: FrameBound::UnboundedFollowing,
+            }
+        } else {
+            Self {
+                units: FrameUnits::Range,
+                start: FrameBound::UnboundedPreceding,
+                end: FrameBound::CurrentRow,
+            }
+        }
+    }
+}
+
+impl Binder {
+    pub fn bind_window_func(&mut self, func: &Function) -> Result<BoundExpr, BindError> {
+        let spec = func
+            .over
+            .as_ref()
+            .expect("window function should have OVER clause");
+        let frame = match &spec.window_frame {
+            Some(_) => bind_window_frame(spec)?,
+            None => BoundWindowFrame::default_of(spec),
+        };
+        let (window_func, args, return_type) = match func.name.to_string().to_lowercase().as_str() {
+            "row_number" if func.args.is_empty() => {
+                (WindowFunc::RowNumber, vec![], DataType::Int64)
+            }
+            "rank" if func.args.is_empty() => (WindowFunc::Rank, vec![], DataType::Int64),
+            "dense_rank" if func.args.is_empty() => {
+                (WindowFunc::DenseRank, vec![], DataType::Int64)
+            }
+            "lag" => self.bind_offset_window_func(func, WindowFunc::Lag)?,
+            "lead" => self.bind_offset_window_func(func, WindowFunc::Lead)?,
+            "ntile" => (
+                WindowFunc::Ntile(self.bind_ntile_buckets(func)?),
+                vec![],
+                DataType::Int64,
+            ),
+            "count" | "sum" | "min" | "max" | "avg" if !func.distinct => {
+                match self.bind_agg_func(func)? {
+                    BoundExpr::AggFunc(agg) => {
+                        (WindowFunc::Agg(agg.func), agg.exprs, agg.return_type)
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => return Err(BindError::InvalidFunctionArgs(func.to_string())),
+        };
+        let partition_by = spec
+            .partition_by
+            .iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let order_by = spec
+            .order_by
+            .iter()
+            .map(|expr| self.bind_order_by(expr))
+            .try_collect()?;
+        Ok(BoundExpr::WindowFunc(BoundWindowFunc {
+            func: window_func,
+            args,
+            partition_by,
+            order_by,
+            frame,
+            return_type,
+        }))
+    }
+
+    /// Bind `lag(expr [, offset [, default]])` or `lead`, the offset is a non-negative integer
+    /// constant which is 1 if absent, and the default is cast to the type of expr.
+    fn bind_offset_window_func(
+        &mut self,
+        func: &Function,
+        window_func: fn(usize) -> WindowFunc,
+    ) -> Result<(WindowFunc, Vec<BoundExpr>, DataType), BindError> {
+        let invalid_args = || BindError::InvalidFunctionArgs(func.to_string());
+        let mut args = vec![];
+        for arg in &func.args {
+            match arg {
+                FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => match arg {
+                    FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr)?),
+                    _ => return Err(invalid_args()),
+                },
+            }
+        }
+        if args.is_empty() || args.len() > 3 {
+            return Err(invalid_args());
+        }
+        let offset = match args.get(1) {
+            None => 1,
+            Some(BoundExpr::Constant(ScalarValue::Int32(Some(v)))) if *v >= 0 => *v as usize,
+            Some(BoundExpr::Constant(ScalarValue::Int64(Some(v)))) if *v >= 0 => *v as usize,
+            _ => return Err(invalid_args()),
+        };
+        let return_type = args[0].return_type().ok_or_else(invalid_args)?;
+        let mut bound_args = vec![args[0].clone()];
+        if let Some(default) = args.get(2) {
+            let default_type = default.return_type().ok_or_else(invalid_args)?;
+            if common_supertype(&default_type, &return_type).is_none() {
+                return Err(invalid_args());
+            }
+            bound_args.push(cast_to(default.clone(), return_type.clone()));
+        }
+        Ok((window_func(offset), bound_args, return_type))
+    }
+
+    /// The number of buckets of `ntile(n)`, which is a positive integer constant.
+    fn bind_ntile_buckets(&mut self, func: &Function) -> Result<usize, BindError> {
+        let invalid_args = || BindError::InvalidFunctionArgs(func.to_string());
+        let arg = match func.args.as_slice() {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))] => self.bind_expr(expr)?,
+            _ => return Err(invalid_args()),
+        };
+        match arg {
+            BoundExpr::Constant(ScalarValue::Int32(Some(n))) if n > 0 => Ok(n as usize),
+            BoundExpr::Constant(ScalarValue::Int64(Some(n))) if n > 0 => Ok(n as usize),
+            _ => Err(invalid_args()),
+        }
+    }
+}
+
+/// Bind the frame clause, the offsets of RANGE are not supported since they are computed on the
+/// values of order key rather than the positions, and neither is GROUPS.
+fn bind_window_frame(spec: &WindowSpec) -> Result<BoundWindowFrame, BindError> {
+    let frame = spec.window_frame.as_ref().unwrap();
+    let unsupported = || BindError::UnsupportedStmt(spec.to_string());
+    let units = match frame.units {
+        WindowFrameUnits::Rows => FrameUnits::Rows,
+        WindowFrameUnits::Range => FrameUnits::Range,
+        WindowFrameUnits::Groups => return Err(unsupported()),
+    };
+    let bind_bound = |bound: &WindowFrameBound| match bound {
+        WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+        WindowFrameBound::Preceding(None) => FrameBound::UnboundedPreceding,
+        WindowFrameBound::Preceding(Some(n)) => FrameBound::Preceding(*n),
+        WindowFrameBound::Following(None) => FrameBound::UnboundedFollowing,
+        WindowFrameBound::Following(Some(n)) => FrameBound::Following(*n),
+    };
+    let start = bind_bound(&frame.start_bound);
+    // the shorthand `ROWS n PRECEDING` ends at the current row
+    let end = frame
+        .end_bound
+        .as_ref()
+        .map_or(FrameBound::CurrentRow, bind_bound);
+
+    let is_offset =
+        |bound: FrameBound| matches!(bound, FrameBound::Preceding(_) | FrameBound::Following(_));
+    if start == FrameBound::UnboundedFollowing
+        || end == FrameBound::UnboundedPreceding
+        || (units == FrameUnits::Range && (is_offset(start) || is_offset(end)))
+    {
+        return Err(unsupported());
+    }
+    Ok(BoundWindowFrame { units, start, end })
+}
+
+impl fmt::Debug for BoundWindowFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({:?}) over (partition by {:?} order by {:?}):{}",
+            self.func, self.args, self.partition_by, self.order_by, self.return_type
+        )
+    }
+}
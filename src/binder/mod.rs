@@ -3,17 +3,29 @@ mod statement;
 mod table;
 
 use std::collections::HashMap;
+use std::time::SystemTime;
 
 pub use expression::*;
-use sqlparser::ast::{Ident, Statement};
+use sqlparser::ast::{Cte, Ident, Statement};
 pub use statement::*;
 pub use table::*;
 
-use crate::catalog::{RootCatalogRef, TableCatalog};
+use crate::catalog::{ColumnCatalog, SchemaProviderRef, TableCatalog, TableFunctionRegistry};
+use crate::types::{Collation, SessionRng};
 
 pub struct Binder {
-    catalog: RootCatalogRef,
+    catalog: SchemaProviderRef,
     context: BinderContext,
+    /// the time when the query starts, so `current_date` and `now()` are consistent in one query.
+    query_start: SystemTime,
+    /// the rng of the session, which `random()` draws from.
+    rng: SessionRng,
+    /// the collation of the session, which the string comparisons and sorts follow.
+    collation: Collation,
+    /// the CTEs of `WITH` visible to the query being bound, by name.
+    ctes: HashMap<String, Cte>,
+    /// the table functions callable in FROM.
+    table_functions: TableFunctionRegistry,
 }
 
 #[derive(Default)]
@@ -21,22 +33,62 @@ struct BinderContext {
     /// table_name == table_id
     /// table_id -> table_catalog
     tables: HashMap<String, TableCatalog>,
+    /// the visible names of tables in FROM order, used to expand wildcard
+    table_names: Vec<String>,
+    /// alias -> the bound expr of select list, used to resolve the alias in GROUP BY
+    aliases: HashMap<String, BoundExpr>,
+    /// the subqueries bound in current clause, referenced by `BoundSubquery::index`
+    subqueries: Vec<BoundSelect>,
+    /// the right key of `USING` or `NATURAL` join -> the left key it's merged into
+    using_columns: Vec<(ColumnCatalog, ColumnCatalog)>,
 }
 
 impl Binder {
-    pub fn new(catalog: RootCatalogRef) -> Self {
+    pub fn new(catalog: SchemaProviderRef) -> Self {
         Self {
             catalog,
             context: BinderContext::default(),
+            query_start: SystemTime::now(),
+            rng: SessionRng::default(),
+            collation: Collation::default(),
+            ctes: HashMap::new(),
+            table_functions: TableFunctionRegistry::default(),
         }
     }
 
+    pub fn with_rng(mut self, rng: SessionRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn with_query_start(mut self, query_start: SystemTime) -> Self {
+        self.query_start = query_start;
+        self
+    }
+
+    pub fn with_collation(mut self, collation: Collation) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    pub fn with_table_functions(mut self, table_functions: TableFunctionRegistry) -> Self {
+        self.table_functions = table_functions;
+        self
+    }
+
     pub fn bind(&mut self, stmt: &Statement) -> Result<BoundStatement, BindError> {
         match stmt {
-            Statement::Query(query) => {
-                let bound_select = self.bind_select(query)?;
-                Ok(BoundStatement::Select(bound_select))
-            }
+            Statement::Query(query) => self.bind_query(query),
+            Statement::Explain {
+                analyze,
+                verbose,
+                statement,
+                ..
+            } => Ok(BoundStatement::Explain(BoundExplain {
+                statement: Box::new(self.bind(statement)?),
+                analyze: *analyze,
+                verbose: *verbose,
+            })),
             _ => Err(BindError::UnsupportedStmt(stmt.to_string())),
         }
     }
@@ -52,8 +104,30 @@ pub enum BindError {
     InvalidTableName(Vec<Ident>),
     #[error("invalid column {0}")]
     InvalidColumn(String),
+    #[error("ambiguous column {0}")]
+    AmbiguousColumn(String),
     #[error("binary operator types mismatch: {0} != {1}")]
     BinaryOpTypeMismatch(String, String),
+    #[error("invalid arguments for function {0}")]
+    InvalidFunctionArgs(String),
+    #[error("unsupported data type {0}")]
+    UnsupportedDataType(String),
+    #[error("invalid literal {0}")]
+    InvalidLiteral(String),
+    #[error("unsupported date time field {0}")]
+    UnsupportedDateTimeField(String),
+    #[error("UNION types mismatch: {0}")]
+    UnionTypeMismatch(String),
+    #[error("CASE branch types mismatch: {0}")]
+    CaseTypeMismatch(String),
+    #[error("invalid subquery {0}, expected uncorrelated select of single column")]
+    InvalidSubquery(String),
+    #[error("invalid join condition {0}, expected equalities between both sides")]
+    InvalidJoinCondition(String),
+    #[error("column {0} must appear in GROUP BY or be used in an aggregate function")]
+    ColumnNotGrouped(String),
+    #[error("unsupported time zone {0}, expected UTC or a fixed offset like +08:00")]
+    UnsupportedTimeZone(String),
 }
 
 #[cfg(test)]
@@ -63,6 +137,7 @@ mod binder_test {
     use std::sync::Arc;
 
     use arrow::datatypes::DataType;
+    use sqlparser::ast::{Expr, SelectItem, SetExpr};
 
     use super::*;
     use crate::catalog::{ColumnCatalog, ColumnDesc, RootCatalog};
@@ -75,6 +150,7 @@ mod binder_test {
         columns.insert(
             "c1".to_string(),
             ColumnCatalog {
+                table_id: table_id.clone(),
                 id: "c1".to_string(),
                 desc: ColumnDesc {
                     name: "c1".to_string(),
@@ -85,6 +161,7 @@ mod binder_test {
         columns.insert(
             "c2".to_string(),
             ColumnCatalog {
+                table_id: table_id.clone(),
                 id: "c2".to_string(),
                 desc: ColumnDesc {
                     name: "c2".to_string(),
@@ -113,9 +190,9 @@ mod binder_test {
         match bound_stmt {
             BoundStatement::Select(select) => {
                 assert_eq!(select.select_list.len(), 2);
-                assert!(select.from_table.is_some());
-                assert_eq!(select.from_table.unwrap().table_catalog.id, "t1");
+                assert_matches!(select.from_table, Some(BoundTableRef::Table(table)) if table.id == "t1");
             }
+            _ => unreachable!(),
         }
     }
 
@@ -131,6 +208,7 @@ mod binder_test {
                 assert_eq!(select.select_list.len(), 1);
                 assert!(select.from_table.is_none());
             }
+            _ => unreachable!(),
         }
     }
 
@@ -145,6 +223,7 @@ mod binder_test {
             BoundStatement::Select(select) => {
                 assert!(select.where_clause.is_some());
             }
+            _ => unreachable!(),
         }
     }
 
@@ -160,6 +239,564 @@ mod binder_test {
                 assert_matches!(select.select_list[0], BoundExpr::AggFunc(_));
                 assert_matches!(select.select_list[1], BoundExpr::AggFunc(_));
             }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_bind_string_func_rejects_non_string_arg() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select upper(c1) from t1").unwrap();
+
+        let result = binder.bind(&stats[0]);
+        assert_matches!(result, Err(BindError::InvalidFunctionArgs(_)));
+    }
+
+    #[test]
+    fn test_bind_abs_resolves_overload_by_arg_type() {
+        let catalog = build_test_catalog();
+        let cases = [
+            ("select abs(c1) from t1", DataType::Int32),
+            ("select abs(c1 + 1.5) from t1", DataType::Float64),
+            ("select abs(null) from t1", DataType::Float64),
+        ];
+        for (sql, return_type) in cases {
+            let mut binder = Binder::new(Arc::new(catalog.clone()));
+            let stats = parse(sql).unwrap();
+            match binder.bind(&stats[0]).unwrap() {
+                BoundStatement::Select(select) => {
+                    assert_eq!(select.select_list[0].return_type(), Some(return_type));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select abs('a') from t1").unwrap();
+        let result = binder.bind(&stats[0]);
+        assert_matches!(result, Err(BindError::InvalidFunctionArgs(msg)) if msg == "Abs(Utf8)");
+    }
+
+    #[test]
+    fn test_bind_coalesce_and_nullif_reject_incompatible_args() {
+        let catalog = build_test_catalog();
+        for sql in [
+            "select coalesce(c1, 'a') from t1",
+            "select nullif(c1, 'a') from t1",
+        ] {
+            let mut binder = Binder::new(Arc::new(catalog.clone()));
+            let stats = parse(sql).unwrap();
+            let result = binder.bind(&stats[0]);
+            assert_matches!(result, Err(BindError::InvalidFunctionArgs(_)));
+        }
+    }
+
+    #[test]
+    fn test_bind_extract_rejects_unsupported_field() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select extract(week from c1) from t1").unwrap();
+
+        let result = binder.bind(&stats[0]);
+        assert_matches!(result, Err(BindError::UnsupportedDateTimeField(_)));
+    }
+
+    #[test]
+    fn test_bind_at_time_zone_rejects_named_zone_and_non_timestamp() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog.clone()));
+        let stats =
+            parse("select timestamp '2020-01-01' at time zone 'Asia/Shanghai' from t1").unwrap();
+        let result = binder.bind(&stats[0]);
+        assert_matches!(result, Err(BindError::UnsupportedTimeZone(_)));
+
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select c1 at time zone 'UTC' from t1").unwrap();
+        let result = binder.bind(&stats[0]);
+        assert_matches!(result, Err(BindError::InvalidFunctionArgs(_)));
+    }
+
+    #[test]
+    fn test_bind_union_all_rejects_mismatched_columns() {
+        let catalog = build_test_catalog();
+        for sql in [
+            "select c1 from t1 union all select c1, c2 from t1",
+            "select c1 from t1 union all select 'a' from t1",
+        ] {
+            let mut binder = Binder::new(Arc::new(catalog.clone()));
+            let stats = parse(sql).unwrap();
+            let result = binder.bind(&stats[0]);
+            assert_matches!(result, Err(BindError::UnionTypeMismatch(_)));
+        }
+    }
+
+    #[test]
+    fn test_bind_union_all_unifies_column_types() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats =
+            parse("select c1, c2 from t1 union all select 10000000000, null from t1").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Union(union) => {
+                assert_eq!(union.types, vec![DataType::Int64, DataType::Int32]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_bind_case_unifies_branch_types() {
+        let catalog = build_test_catalog();
+        let bind_case = |sql: &str| {
+            let mut binder = Binder::new(Arc::new(catalog.clone()));
+            let stats = parse(sql).unwrap();
+            match binder.bind(&stats[0])? {
+                BoundStatement::Select(mut select) => match select.select_list.remove(0) {
+                    BoundExpr::Case(case) => Ok(case),
+                    expr => unreachable!("unexpected expr {:?}", expr),
+                },
+                _ => unreachable!(),
+            }
+        };
+
+        let case = bind_case("select case when c1 > 1 then c1 else null end from t1").unwrap();
+        assert_eq!(case.return_type, DataType::Int32);
+        let case = bind_case("select case when c1 > 1 then null else c1 end from t1").unwrap();
+        assert_eq!(case.return_type, DataType::Int32);
+
+        // the integer branch is widened to the float branch
+        let case = bind_case("select case when c1 > 1 then c1 else 2.5 end from t1").unwrap();
+        assert_eq!(case.return_type, DataType::Float64);
+        assert_eq!(case.when_then[0].1.return_type(), Some(DataType::Float64));
+
+        assert_matches!(
+            bind_case("select case when c1 > 1 then c1 else 'a' end from t1"),
+            Err(BindError::CaseTypeMismatch(_))
+        );
+    }
+
+    #[test]
+    fn test_bind_scalar_subquery_of_single_column() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog.clone()));
+        let stats = parse("select c1, (select max(c2) from t1) from t1").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => {
+                assert_eq!(select.subqueries.len(), 1);
+                assert_matches!(
+                    &select.select_list[1],
+                    BoundExpr::Subquery(BoundSubquery { index: 0, kind: SubqueryKind::Scalar, return_type }) if *return_type == DataType::Int32
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        for sql in [
+            "select (select c1, c2 from t1) from t1",
+            "select c1 from t1 order by (select max(c2) from t1)",
+        ] {
+            let mut binder = Binder::new(Arc::new(catalog.clone()));
+            let stats = parse(sql).unwrap();
+            assert_matches!(
+                binder.bind(&stats[0]),
+                Err(BindError::InvalidSubquery(_) | BindError::UnsupportedStmt(_))
+            );
+        }
+    }
+
+    #[test]
+    fn test_bind_exists_subquery_in_where_clause() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats =
+            parse("select c1 from t1 where not exists (select 1 from t1 where c2 = 2)").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => {
+                assert!(select.subqueries.is_empty());
+                assert_eq!(select.where_subqueries.len(), 1);
+                assert_matches!(
+                    select.where_clause,
+                    Some(BoundExpr::Subquery(BoundSubquery {
+                        index: 0,
+                        kind: SubqueryKind::Exists { negated: true },
+                        return_type: DataType::Boolean,
+                    }))
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_bind_cte_as_subquery_relation() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats =
+            parse("with c as (select c1, c2 + 1 as c3 from t1) select c1, c3 from c").unwrap();
+        let select = match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => select,
+            _ => unreachable!(),
+        };
+        let subquery = match select.from_table {
+            Some(BoundTableRef::Subquery(subquery)) => subquery,
+            _ => unreachable!(),
+        };
+        assert_eq!(subquery.catalog.id, "c");
+        assert_eq!(subquery.catalog.column_ids, vec!["c1", "c3"]);
+        assert_matches!(
+            &select.select_list[1],
+            BoundExpr::ColumnRef(col) if col.column_catalog.table_id == "c"
+        );
+
+        // the CTE is only visible in its query
+        let stats = parse("select c1 from c").unwrap();
+        assert_matches!(binder.bind(&stats[0]), Err(BindError::InvalidTable(_)));
+        let stats = parse("with c as (select c1 + 1 from t1) select * from c").unwrap();
+        assert_matches!(binder.bind(&stats[0]), Err(BindError::InvalidSubquery(_)));
+    }
+
+    #[test]
+    fn test_bind_group_by_select_alias() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select c1 + 1 as c2, count(c2) from t1 group by c2").unwrap();
+
+        let bound_stmt = binder.bind(&stats[0]).unwrap();
+        match bound_stmt {
+            BoundStatement::Select(select) => {
+                // the alias shadows the column c2 of t1
+                assert_eq!(select.group_by.len(), 1);
+                assert_eq!(select.group_by[0], select.select_list[0]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_bind_binary_op_coerces_operand_types() {
+        let catalog = build_test_catalog();
+        let bind_binary_op = |sql: &str| {
+            let mut binder = Binder::new(Arc::new(catalog.clone()));
+            let stats = parse(sql).unwrap();
+            match binder.bind(&stats[0]).unwrap() {
+                BoundStatement::Select(mut select) => match select.select_list.remove(0) {
+                    BoundExpr::BinaryOp(op) => op,
+                    expr => unreachable!("unexpected expr {:?}", expr),
+                },
+                _ => unreachable!(),
+            }
+        };
+        let cast_type = |expr: &BoundExpr| match expr {
+            BoundExpr::TypeCast(cast) => Some(cast.cast_type.clone()),
+            _ => None,
+        };
+
+        // (sql, left cast, right cast, return type)
+        let cases = [
+            ("select c1 + c2 from t1", None, None, DataType::Int32),
+            (
+                "select c1 + 2.5 from t1",
+                Some(DataType::Float64),
+                None,
+                DataType::Float64,
+            ),
+            (
+                "select 3000000000 - c1 from t1",
+                None,
+                Some(DataType::Int64),
+                DataType::Int64,
+            ),
+            (
+                "select c1 = 3000000000 from t1",
+                Some(DataType::Int64),
+                None,
+                DataType::Boolean,
+            ),
+            (
+                "select c1 * null from t1",
+                None,
+                Some(DataType::Int32),
+                DataType::Int32,
+            ),
+        ];
+        for (sql, left_cast, right_cast, return_type) in cases {
+            let op = bind_binary_op(sql);
+            assert_eq!(cast_type(&op.left), left_cast, "{}", sql);
+            assert_eq!(cast_type(&op.right), right_cast, "{}", sql);
+            assert_eq!(op.return_type, Some(return_type), "{}", sql);
+        }
+
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select c1 + 'a' from t1").unwrap();
+        assert_matches!(
+            binder.bind(&stats[0]),
+            Err(BindError::BinaryOpTypeMismatch(_, _))
+        );
+    }
+
+    #[test]
+    fn test_bind_in_subquery_is_split_from_where() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let sql = "select c1 from t1 where c1 in (select c2 + 1.5 from t1) and c2 = 1";
+        let stats = parse(sql).unwrap();
+        let select = match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => select,
+            _ => unreachable!(),
+        };
+        assert_matches!(select.where_clause, Some(BoundExpr::BinaryOp(_)));
+        assert_eq!(select.in_subqueries.len(), 1);
+        // both sides are casted to the common type
+        let in_subquery = &select.in_subqueries[0];
+        assert_eq!(in_subquery.expr.return_type(), Some(DataType::Float64));
+        assert_eq!(
+            in_subquery.subquery.select_list[0].return_type(),
+            Some(DataType::Float64)
+        );
+
+        let sql = "select c1 from t1 where c1 in (select c1, c2 from t1)";
+        let stats = parse(sql).unwrap();
+        assert_matches!(binder.bind(&stats[0]), Err(BindError::InvalidSubquery(_)));
+
+        let sql = "select c1 from t1 where c1 in (select 'a' from t1)";
+        let stats = parse(sql).unwrap();
+        assert_matches!(
+            binder.bind(&stats[0]),
+            Err(BindError::BinaryOpTypeMismatch(_, _))
+        );
+    }
+
+    #[test]
+    fn test_bind_qualified_column_ref() {
+        let mut catalog = build_test_catalog();
+        let mut t2 = catalog.get_table_by_name("t1").unwrap();
+        t2.id = "t2".to_string();
+        t2.name = "t2".to_string();
+        for column in t2.columns.values_mut() {
+            column.table_id = "t2".to_string();
+        }
+        catalog.tables.insert("t2".to_string(), t2);
+        let mut binder = Binder::new(Arc::new(catalog));
+
+        // bind the relations of FROM into context, then the column refs against them
+        let stats = parse("select t1.c1, t2.c1, c1, t3.c1 from t1, t2").unwrap();
+        let select = match &stats[0] {
+            Statement::Query(query) => match &query.body {
+                SetExpr::Select(select) => select.clone(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        for table in &select.from {
+            binder.bind_table_with_joins(table).unwrap();
+        }
+        let mut items = select.projection.iter().map(|item| match item {
+            SelectItem::UnnamedExpr(expr) => binder.bind_expr(expr),
+            _ => unreachable!(),
+        });
+
+        let table_of = |expr: Result<BoundExpr, BindError>| match expr.unwrap() {
+            BoundExpr::ColumnRef(col) => col.column_catalog.table_id,
+            expr => unreachable!("unexpected expr {:?}", expr),
+        };
+        assert_eq!(table_of(items.next().unwrap()), "t1");
+        assert_eq!(table_of(items.next().unwrap()), "t2");
+        assert_matches!(items.next().unwrap(), Err(BindError::AmbiguousColumn(_)));
+        assert_matches!(items.next().unwrap(), Err(BindError::InvalidTable(_)));
+    }
+
+    #[test]
+    fn test_bind_table_alias() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog.clone()));
+        let stats = parse("select e.c1 from t1 e where e.c2 > 1").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => {
+                assert_eq!(select.select_list.len(), 1);
+                assert_matches!(select.from_table, Some(BoundTableRef::Table(table)) if table.id == "t1");
+            }
+            _ => unreachable!(),
+        }
+
+        // the original name is hidden by the alias
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select t1.c1 from t1 e").unwrap();
+        assert_matches!(binder.bind(&stats[0]), Err(BindError::InvalidTable(_)));
+    }
+
+    #[test]
+    fn test_bind_self_join_aliases_independently() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+
+        // bind both sides of the self join, then the column refs against them
+        let stats = parse("select a.c1, b.c1 from t1 a join t1 b on a.c1 = b.c1").unwrap();
+        let select = match &stats[0] {
+            Statement::Query(query) => match &query.body {
+                SetExpr::Select(select) => select.clone(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        let from = &select.from[0];
+        binder.bind_table_ref(&from.relation).unwrap();
+        binder.bind_table_ref(&from.joins[0].relation).unwrap();
+        let columns = select
+            .projection
+            .iter()
+            .map(|item| match item {
+                SelectItem::UnnamedExpr(expr) => match binder.bind_expr(expr).unwrap() {
+                    BoundExpr::ColumnRef(col) => col.column_catalog,
+                    expr => unreachable!("unexpected expr {:?}", expr),
+                },
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(columns[0].table_id, "a");
+        assert_eq!(columns[1].table_id, "b");
+        assert_eq!(columns[0].id, columns[1].id);
+        assert_ne!(columns[0], columns[1]);
+        // the bare column is in both sides
+        let expr = Expr::Identifier(Ident::new("c1"));
+        assert_matches!(binder.bind_expr(&expr), Err(BindError::AmbiguousColumn(_)));
+    }
+
+    #[test]
+    fn test_bind_qualified_wildcard() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select e.*, c1 from t1 e").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => {
+                let names = select
+                    .select_list
+                    .iter()
+                    .map(|expr| match expr {
+                        BoundExpr::ColumnRef(col) => col.column_catalog.id.clone(),
+                        expr => unreachable!("unexpected expr {:?}", expr),
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(names, vec!["c1", "c2", "c1"]);
+            }
+            _ => unreachable!(),
+        }
+
+        let stats = parse("select t2.* from t1").unwrap();
+        assert_matches!(binder.bind(&stats[0]), Err(BindError::InvalidTable(_)));
+    }
+
+    #[test]
+    fn test_bind_group_by_expr() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select c1 / 10 + 1, count(c2) from t1 group by c1 / 10").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => {
+                assert_matches!(&select.group_by[0], BoundExpr::BinaryOp(_));
+            }
+            _ => unreachable!(),
+        }
+
+        // c1 is only grouped by the expr of it
+        let stats = parse("select c1, count(c2) from t1 group by c1 / 10").unwrap();
+        assert_matches!(
+            binder.bind(&stats[0]),
+            Err(BindError::ColumnNotGrouped(column)) if column == "c1"
+        );
+    }
+
+    #[test]
+    fn test_bind_select_and_order_by_only_reference_groups() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        for sql in [
+            "select c1, count(c2) from t1 group by c1 order by c1",
+            "select c1 as k, count(c2) from t1 group by k order by k, count(c2)",
+            "select c2 from t1 order by c1",
+        ] {
+            let stats = parse(sql).unwrap();
+            assert!(binder.bind(&stats[0]).is_ok(), "{}", sql);
+        }
+
+        for sql in [
+            "select c2, count(*) from t1 group by c1",
+            "select c1, count(c1) from t1 group by c1 order by c2",
+            "select count(c1) from t1 order by c2",
+        ] {
+            let stats = parse(sql).unwrap();
+            assert_matches!(
+                binder.bind(&stats[0]),
+                Err(BindError::ColumnNotGrouped(column)) if column == "c2"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bind_having_only_references_groups() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let stats = parse("select c1 from t1 group by c1 having c1 > 1 and count(c2) > 1").unwrap();
+        match binder.bind(&stats[0]).unwrap() {
+            BoundStatement::Select(select) => assert!(select.having.is_some()),
+            _ => unreachable!(),
+        }
+
+        // c2 is neither grouped nor aggregated
+        for sql in [
+            "select c1 from t1 group by c1 having c2 = 1",
+            "select count(c1) from t1 having c2 > max(c1)",
+        ] {
+            let stats = parse(sql).unwrap();
+            assert_matches!(
+                binder.bind(&stats[0]),
+                Err(BindError::ColumnNotGrouped(column)) if column == "c2"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bind_window_frame() {
+        let catalog = build_test_catalog();
+        let mut binder = Binder::new(Arc::new(catalog));
+        let bind_frame = |binder: &mut Binder, sql: &str| -> Result<BoundWindowFrame, BindError> {
+            let stats = parse(sql).unwrap();
+            match binder.bind(&stats[0])? {
+                BoundStatement::Select(mut select) => match select.select_list.remove(0) {
+                    BoundExpr::WindowFunc(func) => Ok(func.frame),
+                    expr => unreachable!("unexpected expr {:?}", expr),
+                },
+                _ => unreachable!(),
+            }
+        };
+
+        let frame = bind_frame(
+            &mut binder,
+            "select sum(c1) over (order by c2 rows between 1 preceding and 1 following) from t1",
+        )
+        .unwrap();
+        assert_eq!(frame.units, FrameUnits::Rows);
+        assert_eq!(
+            (frame.start, frame.end),
+            (FrameBound::Preceding(1), FrameBound::Following(1))
+        );
+        // ORDER BY implies the running frame up to the peers of current row
+        let frame = bind_frame(&mut binder, "select sum(c1) over (order by c2) from t1").unwrap();
+        assert_eq!(frame.units, FrameUnits::Range);
+        assert_eq!(
+            (frame.start, frame.end),
+            (FrameBound::UnboundedPreceding, FrameBound::CurrentRow)
+        );
+
+        for sql in [
+            "select sum(c1) over (order by c2 range between 1 preceding and current row) from t1",
+            "select sum(c1) over (order by c2 groups between 1 preceding and current row) from t1",
+        ] {
+            assert_matches!(
+                bind_frame(&mut binder, sql),
+                Err(BindError::UnsupportedStmt(_))
+            );
         }
     }
 }
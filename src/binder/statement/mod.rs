@@ -1,84 +1,549 @@
-use sqlparser::ast::SetExpr::Select;
-use sqlparser::ast::{Query, SelectItem};
+use arrow::datatypes::DataType;
+use sqlparser::ast::{
+    BinaryOperator, Expr, OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator,
+};
 
-use super::expression::BoundExpr;
+use super::expression::{cast_to, common_supertype, BoundBinaryOp, BoundExpr};
 use super::table::BoundTableRef;
 use super::{BindError, Binder, BoundColumnRef};
+use crate::optimizer::ExprVisitor;
+use crate::types::ScalarValue;
 
 #[derive(Debug)]
 pub enum BoundStatement {
     Select(BoundSelect),
+    Union(BoundUnion),
+    Explain(BoundExplain),
 }
 
+/// EXPLAIN the plan of inner statement, or execute it with metrics if `analyze` is set.
 #[derive(Debug)]
+pub struct BoundExplain {
+    pub statement: Box<BoundStatement>,
+    pub analyze: bool,
+    /// `EXPLAIN VERBOSE` also lists the optimizer rules which changed the plan
+    pub verbose: bool,
+}
+
+/// The flattened `UNION ALL` chain, e.g. `a UNION ALL b UNION ALL c` has three inputs.
+#[derive(Debug)]
+pub struct BoundUnion {
+    pub inputs: Vec<BoundSelect>,
+    /// the output column types, the common supertype of each column of all inputs
+    pub types: Vec<DataType>,
+}
+
+#[derive(Debug, Clone)]
 pub struct BoundSelect {
     pub select_list: Vec<BoundExpr>,
+    /// the output names of `expr AS alias` in select list, parallel to select_list
+    pub select_aliases: Vec<Option<String>>,
     pub from_table: Option<BoundTableRef>,
     pub where_clause: Option<BoundExpr>,
     pub group_by: Vec<BoundExpr>,
+    /// the filter over the groups, which only references the group by exprs and aggregates
+    pub having: Option<BoundExpr>,
+    pub order_by: Vec<BoundOrderBy>,
+    pub limit: Option<BoundExpr>,
+    pub offset: Option<BoundExpr>,
+    /// `FETCH FIRST n ROWS WITH TIES`, the rows tie with the last row on ORDER BY keys are also
+    /// output.
+    pub with_ties: bool,
+    /// the `IN (subquery)` conjuncts of where clause, which are planned apart from it.
+    pub in_subqueries: Vec<BoundInSubquery>,
+    /// the subqueries in select list, referenced by `BoundSubquery::index`.
+    pub subqueries: Vec<BoundSelect>,
+    /// the subqueries in where clause, e.g. `EXISTS (subquery)`, indexed apart from the ones in
+    /// select list.
+    pub where_subqueries: Vec<BoundSelect>,
+}
+
+/// `expr [NOT] IN (subquery)` with an uncorrelated subquery of single column, the subquery is
+/// evaluated once as the membership set of `expr`.
+#[derive(Debug, Clone)]
+pub struct BoundInSubquery {
+    pub expr: BoundExpr,
+    pub subquery: Box<BoundSelect>,
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundOrderBy {
+    pub expr: BoundExpr,
+    pub asc: bool,
+    pub nulls_first: bool,
 }
 
 impl Binder {
+    pub fn bind_query(&mut self, query: &Query) -> Result<BoundStatement, BindError> {
+        match &query.body {
+            SetExpr::SetOperation { .. } => Ok(BoundStatement::Union(
+                self.bind_with_ctes(query, |binder| binder.bind_union(query))?,
+            )),
+            _ => Ok(BoundStatement::Select(self.bind_select(query)?)),
+        }
+    }
+
     pub fn bind_select(&mut self, query: &Query) -> Result<BoundSelect, BindError> {
+        self.bind_with_ctes(query, |binder| binder.bind_select_without_ctes(query))
+    }
+
+    /// Make the CTEs of `WITH` visible while binding the query, the outer CTEs of the same name are
+    /// shadowed until the query is bound.
+    fn bind_with_ctes<T>(
+        &mut self,
+        query: &Query,
+        bind: impl FnOnce(&mut Self) -> Result<T, BindError>,
+    ) -> Result<T, BindError> {
+        let with = match &query.with {
+            Some(with) => with,
+            None => return bind(self),
+        };
+        if with.recursive {
+            return Err(BindError::UnsupportedStmt(with.to_string()));
+        }
+        let outer_ctes = self.ctes.clone();
+        for cte in &with.cte_tables {
+            self.ctes
+                .insert(cte.alias.name.value.to_lowercase(), cte.clone());
+        }
+        let result = bind(self);
+        self.ctes = outer_ctes;
+        result
+    }
+
+    fn bind_select_without_ctes(&mut self, query: &Query) -> Result<BoundSelect, BindError> {
         let select = match &query.body {
-            Select(select) => &**select,
+            SetExpr::Select(select) => &**select,
             _ => todo!(),
         };
+        let mut bound_select = self.bind_select_body(select)?;
+
+        bound_select.order_by = query
+            .order_by
+            .iter()
+            .map(|expr| self.bind_order_by(expr))
+            .try_collect()?;
+        // the groups are sorted after aggregation, e.g. `select c1 from t group by c1 order by c2`
+        // has no c2 to sort by
+        if is_grouping(&bound_select) {
+            for order_by in &bound_select.order_by {
+                check_grouped(&order_by.expr, &bound_select.group_by)?;
+            }
+        }
+
+        bound_select.limit = query
+            .limit
+            .as_ref()
+            .map(|expr| self.bind_expr(expr))
+            .transpose()?;
 
-        // currently, only support select one table
+        bound_select.offset = query
+            .offset
+            .as_ref()
+            .map(|offset| self.bind_expr(&offset.value))
+            .transpose()?;
+
+        // `FETCH FIRST n ROWS` is the standard form of `LIMIT n`
+        if let Some(fetch) = &query.fetch {
+            if query.limit.is_some() || fetch.percent {
+                return Err(BindError::UnsupportedStmt(fetch.to_string()));
+            }
+            if fetch.with_ties && query.order_by.is_empty() {
+                return Err(BindError::UnsupportedStmt(
+                    "WITH TIES without ORDER BY".to_string(),
+                ));
+            }
+            bound_select.limit = Some(match &fetch.quantity {
+                Some(quantity) => self.bind_expr(quantity)?,
+                None => BoundExpr::Constant(ScalarValue::Int64(Some(1))),
+            });
+            bound_select.with_ties = fetch.with_ties;
+        }
+
+        if !self.context.subqueries.is_empty() {
+            return Err(BindError::UnsupportedStmt(
+                "subquery outside of select list and where clause".to_string(),
+            ));
+        }
+        Ok(bound_select)
+    }
+
+    /// Bind the `UNION ALL` chain into a flat list of selects, so that the nested set operations
+    /// `(a UNION ALL b) UNION ALL c` are executed by one union rather than a deep tree.
+    fn bind_union(&mut self, query: &Query) -> Result<BoundUnion, BindError> {
+        if !query.order_by.is_empty()
+            || query.limit.is_some()
+            || query.offset.is_some()
+            || query.fetch.is_some()
+        {
+            return Err(BindError::UnsupportedStmt(
+                "ORDER BY or LIMIT on UNION".to_string(),
+            ));
+        }
+        let mut branches = vec![];
+        collect_union_branches(&query.body, &mut branches)?;
+
+        let input_types = |s: &BoundSelect| {
+            s.select_list
+                .iter()
+                .map(|e| e.return_type().unwrap_or(DataType::Null))
+                .collect::<Vec<_>>()
+        };
+        let mut inputs: Vec<BoundSelect> = vec![];
+        let mut types: Vec<DataType> = vec![];
+        for select in branches {
+            // each branch has its own tables
+            self.context = BinderContext::default();
+            let input = self.bind_select_body(select)?;
+            if inputs.is_empty() {
+                types = input_types(&input);
+            } else {
+                // the columns only differ by width are unified, e.g. Int32 and Int64, and the
+                // batches of each input are cast to the unified types by the union executor
+                let mismatch = || {
+                    BindError::UnionTypeMismatch(format!(
+                        "{:?} and {:?}",
+                        types,
+                        input_types(&input)
+                    ))
+                };
+                if types.len() != input.select_list.len() {
+                    return Err(mismatch());
+                }
+                types = types
+                    .iter()
+                    .zip(input_types(&input).iter())
+                    .map(|(left, right)| common_supertype(left, right))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(mismatch)?;
+            }
+            inputs.push(input);
+        }
+        Ok(BoundUnion { inputs, types })
+    }
+
+    fn bind_select_body(&mut self, select: &Select) -> Result<BoundSelect, BindError> {
         let from_table = if select.from.is_empty() {
             None
         } else {
-            Some(self.bind_table_with_joins(&select.from[0])?)
+            Some(self.bind_from(&select.from)?)
         };
 
         // bind select list
         let mut select_list = vec![];
+        let mut select_aliases = vec![];
         for item in &select.projection {
             match item {
                 SelectItem::UnnamedExpr(expr) => {
                     let expr = self.bind_expr(expr)?;
                     select_list.push(expr);
+                    select_aliases.push(None);
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    let expr = self.bind_expr(expr)?;
+                    self.context
+                        .aliases
+                        .insert(alias.value.clone(), expr.clone());
+                    select_list.push(expr);
+                    select_aliases.push(Some(alias.value.clone()));
+                }
+                SelectItem::QualifiedWildcard(table_name) => {
+                    let table_name = match table_name.0.last() {
+                        Some(ident) => ident.value.to_lowercase(),
+                        None => return Err(BindError::InvalidTable(table_name.to_string())),
+                    };
+                    select_list.extend(self.bind_table_columns_in_context(&table_name)?);
+                    select_aliases.resize(select_list.len(), None);
                 }
-                SelectItem::ExprWithAlias { expr: _, alias: _ } => todo!(),
-                SelectItem::QualifiedWildcard(_) => todo!(),
                 SelectItem::Wildcard => {
                     select_list.extend_from_slice(self.bind_all_columns_in_context().as_slice());
+                    select_aliases.resize(select_list.len(), None);
                 }
             }
         }
 
+        // the subqueries of select list and where clause are resolved by project and filter
+        let subqueries = std::mem::take(&mut self.context.subqueries);
+
         // bind where clause
-        let where_clause = select
-            .selection
-            .as_ref()
-            .map(|expr| self.bind_expr(expr))
-            .transpose()?;
+        let mut in_subqueries = vec![];
+        let where_clause = match &select.selection {
+            Some(expr) => self.bind_where(expr, &mut in_subqueries)?,
+            None => None,
+        };
+        let where_subqueries = std::mem::take(&mut self.context.subqueries);
 
         let group_by = select
             .group_by
             .iter()
-            .map(|expr| self.bind_expr(expr))
+            .map(|expr| self.bind_group_by(expr))
             .try_collect()?;
 
-        Ok(BoundSelect {
+        let having = match &select.having {
+            Some(expr) => {
+                let having = self.bind_expr(expr)?;
+                if !self.context.subqueries.is_empty() {
+                    return Err(BindError::UnsupportedStmt(expr.to_string()));
+                }
+                check_grouped(&having, &group_by)?;
+                Some(having)
+            }
+            None => None,
+        };
+
+        let bound_select = BoundSelect {
             select_list,
+            select_aliases,
             from_table,
             where_clause,
             group_by,
+            having,
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            with_ties: false,
+            in_subqueries,
+            subqueries,
+            where_subqueries,
+        };
+        // the select list of grouping is evaluated per group, e.g. `select c1 + 1, count(c2) from t
+        // group by c1 + 1`, rather than per row
+        if is_grouping(&bound_select) {
+            for expr in &bound_select.select_list {
+                check_grouped(expr, &bound_select.group_by)?;
+            }
+        }
+        Ok(bound_select)
+    }
+
+    /// Split the `IN (subquery)` out of the top-level conjunction of where clause, the rest
+    /// conjuncts are bound and combined as the filter expr.
+    fn bind_where(
+        &mut self,
+        expr: &Expr,
+        in_subqueries: &mut Vec<BoundInSubquery>,
+    ) -> Result<Option<BoundExpr>, BindError> {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                let left = self.bind_where(left, in_subqueries)?;
+                let right = self.bind_where(right, in_subqueries)?;
+                Ok(match (left, right) {
+                    (Some(left), Some(right)) => Some(BoundExpr::BinaryOp(BoundBinaryOp {
+                        op: BinaryOperator::And,
+                        left: Box::new(left),
+                        right: Box::new(right),
+                        return_type: Some(DataType::Boolean),
+                    })),
+                    (left, right) => left.or(right),
+                })
+            }
+            Expr::Nested(expr) => self.bind_where(expr, in_subqueries),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                in_subqueries.push(self.bind_in_subquery(expr, subquery, *negated)?);
+                Ok(None)
+            }
+            _ => Ok(Some(self.bind_expr(expr)?)),
+        }
+    }
+
+    fn bind_in_subquery(
+        &mut self,
+        expr: &Expr,
+        subquery: &Query,
+        negated: bool,
+    ) -> Result<BoundInSubquery, BindError> {
+        let mut expr = self.bind_expr(expr)?;
+        if !matches!(subquery.body, SetExpr::Select(_)) {
+            return Err(BindError::InvalidSubquery(subquery.to_string()));
+        }
+        // the subquery is uncorrelated, so it is bound with its own tables
+        let outer_context = std::mem::take(&mut self.context);
+        let subquery_select = self.bind_select(subquery);
+        self.context = outer_context;
+        let mut subquery_select = subquery_select?;
+
+        if subquery_select.select_list.len() != 1 {
+            return Err(BindError::InvalidSubquery(subquery.to_string()));
+        }
+        // cast both sides to the common type, so that the membership is checked by equality
+        let (left_type, right_type) = (
+            expr.return_type(),
+            subquery_select.select_list[0].return_type(),
+        );
+        if let (Some(left), Some(right)) = (&left_type, &right_type) {
+            let common = common_supertype(left, right).ok_or_else(|| {
+                BindError::BinaryOpTypeMismatch(left.to_string(), right.to_string())
+            })?;
+            expr = self.collate(cast_to(expr, common.clone()));
+            let item = subquery_select.select_list.remove(0);
+            subquery_select
+                .select_list
+                .push(self.collate(cast_to(item, common)));
+        }
+        Ok(BoundInSubquery {
+            expr,
+            subquery: Box::new(subquery_select),
+            negated,
+        })
+    }
+
+    /// The identifier in GROUP BY is resolved to the select list alias first, then the column.
+    ///
+    /// The strings are grouped in binary order regardless of the collation, as the group keys are
+    /// output as is and matched with the select list, grouping by the sort keys would output the
+    /// sort keys rather than the original strings.
+    fn bind_group_by(&mut self, expr: &Expr) -> Result<BoundExpr, BindError> {
+        if let Expr::Identifier(ident) = expr {
+            if let Some(expr) = self.context.aliases.get(&ident.value) {
+                return Ok(expr.clone());
+            }
+        }
+        self.bind_expr(expr)
+    }
+
+    /// The identifier in ORDER BY is resolved like GROUP BY, since sort is planned before project.
+    pub(crate) fn bind_order_by(
+        &mut self,
+        order_by: &OrderByExpr,
+    ) -> Result<BoundOrderBy, BindError> {
+        let expr = self.bind_group_by(&order_by.expr)?;
+        let expr = self.collate(expr);
+        let asc = order_by.asc.unwrap_or(true);
+        // NULLs are treated as larger than any value by default, same as PostgreSQL.
+        let nulls_first = order_by.nulls_first.unwrap_or(!asc);
+        Ok(BoundOrderBy {
+            expr,
+            asc,
+            nulls_first,
         })
     }
 
+    /// Expand `*` to the columns of all tables in FROM order.
     fn bind_all_columns_in_context(&mut self) -> Vec<BoundExpr> {
         let mut columns = vec![];
-        for table_catalog in self.context.tables.values() {
-            for column in table_catalog.get_all_columns() {
-                let column_ref = BoundExpr::ColumnRef(BoundColumnRef {
-                    column_catalog: column,
+        for table_name in self.context.table_names.clone() {
+            // the shared column of `USING` or `NATURAL` join appears once, as the left key
+            let table_columns = self.context.tables[&table_name]
+                .get_all_columns()
+                .into_iter()
+                .filter(|column| !self.is_merged_using_column(column))
+                .map(|column| {
+                    BoundExpr::ColumnRef(BoundColumnRef {
+                        column_catalog: column,
+                    })
                 });
-                columns.push(column_ref);
-            }
+            columns.extend(table_columns);
         }
         columns
     }
+
+    /// Expand `t.*` to the columns of table `t` in declared order.
+    fn bind_table_columns_in_context(
+        &mut self,
+        table_name: &str,
+    ) -> Result<Vec<BoundExpr>, BindError> {
+        let table_catalog = self
+            .context
+            .tables
+            .get(table_name)
+            .ok_or_else(|| BindError::InvalidTable(table_name.to_string()))?;
+        Ok(table_catalog
+            .get_all_columns()
+            .into_iter()
+            .map(|column| {
+                BoundExpr::ColumnRef(BoundColumnRef {
+                    column_catalog: self.resolve_using_column(column),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Collect the selects of `UNION ALL` chain from left to right.
+fn collect_union_branches<'a>(
+    body: &'a SetExpr,
+    branches: &mut Vec<&'a Select>,
+) -> Result<(), BindError> {
+    match body {
+        SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all: true,
+            left,
+            right,
+        } => {
+            collect_union_branches(left, branches)?;
+            collect_union_branches(right, branches)
+        }
+        SetExpr::Select(select) => {
+            branches.push(select);
+            Ok(())
+        }
+        _ => Err(BindError::UnsupportedStmt(body.to_string())),
+    }
+}
+
+/// Check the expr only references the columns in the group by exprs or in the aggregates, which
+/// are the values of each group.
+fn check_grouped(expr: &BoundExpr, group_by: &[BoundExpr]) -> Result<(), BindError> {
+    let mut finder = UngroupedColumnFinder {
+        group_by,
+        ungrouped: None,
+    };
+    finder.visit_expr(expr);
+    match finder.ungrouped {
+        Some(column) => Err(BindError::ColumnNotGrouped(column)),
+        None => Ok(()),
+    }
+}
+
+/// The select is aggregated into groups if it has GROUP BY, HAVING or any aggregate in select list.
+fn is_grouping(select: &BoundSelect) -> bool {
+    !select.group_by.is_empty() || select.having.is_some() || contains_agg(&select.select_list)
+}
+
+fn contains_agg(exprs: &[BoundExpr]) -> bool {
+    let mut finder = AggFinder { found: false };
+    for expr in exprs {
+        finder.visit_expr(expr);
+    }
+    finder.found
+}
+
+struct AggFinder {
+    found: bool,
+}
+
+impl ExprVisitor for AggFinder {
+    fn pre_visit(&mut self, expr: &BoundExpr) {
+        self.found |= matches!(expr, BoundExpr::AggFunc(_));
+    }
+}
+
+struct UngroupedColumnFinder<'a> {
+    group_by: &'a [BoundExpr],
+    ungrouped: Option<String>,
+}
+
+impl<'a> ExprVisitor for UngroupedColumnFinder<'a> {
+    fn visit_expr(&mut self, expr: &BoundExpr) {
+        // the group by exprs and aggregates are evaluated per group, so are their columns
+        if self.group_by.contains(expr) || matches!(expr, BoundExpr::AggFunc(_)) {
+            return;
+        }
+        self.walk_expr(expr);
+    }
+
+    fn visit_column_ref(&mut self, expr: &BoundColumnRef) {
+        self.ungrouped
+            .get_or_insert_with(|| expr.column_catalog.desc.name.clone());
+    }
 }
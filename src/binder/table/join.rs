@@ -0,0 +1,215 @@
+use arrow::datatypes::DataType;
+use sqlparser::ast::{BinaryOperator, Expr};
+
+use super::BoundTableRef;
+use crate::binder::{cast_to, common_supertype, BindError, Binder, BoundColumnRef, BoundExpr};
+use crate::catalog::ColumnCatalog;
+use crate::optimizer::ExprVisitor;
+
+/// The join of two bound relations, which is nested for more than two relations, e.g.
+/// `a, b, c` is bound as `(a join b) join c`.
+#[derive(Debug, Clone)]
+pub struct Join {
+    pub left: Box<BoundTableRef>,
+    pub right: Box<BoundTableRef>,
+    pub join_type: JoinType,
+    /// the equi-join keys of `ON`, each pair is the key of left and the key of right. Empty for
+    /// cross join.
+    pub on: Vec<(BoundExpr, BoundExpr)>,
+    /// the right keys of `USING` or `NATURAL`, which are merged into the left keys and projected
+    /// out after the join, so that each shared column appears once.
+    pub using: Vec<ColumnCatalog>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    /// the rows of both sides with equal keys, from `[INNER] JOIN ... ON`
+    Inner,
+    /// the Cartesian product of both sides, from `CROSS JOIN` or comma-separated tables
+    Cross,
+}
+
+impl Binder {
+    /// Bind the conjunction of equalities in `ON` as the join keys, the key of each side only
+    /// references the columns of that side.
+    pub(super) fn bind_join_on(
+        &mut self,
+        expr: &Expr,
+        left: &BoundTableRef,
+        on: &mut Vec<(BoundExpr, BoundExpr)>,
+    ) -> Result<(), BindError> {
+        match expr {
+            Expr::BinaryOp {
+                left: l,
+                op: BinaryOperator::And,
+                right: r,
+            } => {
+                self.bind_join_on(l, left, on)?;
+                self.bind_join_on(r, left, on)
+            }
+            Expr::Nested(expr) => self.bind_join_on(expr, left, on),
+            Expr::BinaryOp {
+                left: l,
+                op: BinaryOperator::Eq,
+                right: r,
+            } => {
+                let (l, r) = (self.bind_expr(l)?, self.bind_expr(r)?);
+                let left_columns = left.columns();
+                let side_of = |expr: &BoundExpr| {
+                    let columns = column_refs(expr);
+                    let in_left = columns.iter().filter(|c| left_columns.contains(c)).count();
+                    match (columns.len(), in_left) {
+                        (0, _) => None,
+                        (n, m) if n == m => Some(true),
+                        (_, 0) => Some(false),
+                        _ => None,
+                    }
+                };
+                let (left_key, right_key) = match (side_of(&l), side_of(&r)) {
+                    (Some(true), Some(false)) => (l, r),
+                    (Some(false), Some(true)) => (r, l),
+                    _ => return Err(BindError::InvalidJoinCondition(expr.to_string())),
+                };
+                self.push_join_key(left_key, right_key, on)
+            }
+            _ => Err(BindError::InvalidJoinCondition(expr.to_string())),
+        }
+    }
+
+    /// Bind the shared columns of `USING` or `NATURAL` as the join keys, each name should be
+    /// found in exactly one column of each side. Return the right keys, which are merged into the
+    /// left keys, so that the right key is resolved as the left key in the select.
+    pub(super) fn bind_join_using(
+        &mut self,
+        names: &[String],
+        left: &BoundTableRef,
+        right: &BoundTableRef,
+        on: &mut Vec<(BoundExpr, BoundExpr)>,
+    ) -> Result<Vec<ColumnCatalog>, BindError> {
+        let find_column =
+            |table_ref: &BoundTableRef, name: &String| -> Result<ColumnCatalog, BindError> {
+                let mut columns = table_ref
+                    .columns()
+                    .into_iter()
+                    .filter(|c| c.desc.name == *name);
+                let column = columns
+                    .next()
+                    .ok_or_else(|| BindError::InvalidColumn(name.clone()))?;
+                match columns.next() {
+                    Some(_) => Err(BindError::AmbiguousColumn(name.clone())),
+                    None => Ok(column),
+                }
+            };
+        let mut using = vec![];
+        for name in names {
+            let (left_column, right_column) = (find_column(left, name)?, find_column(right, name)?);
+            self.push_join_key(
+                BoundExpr::ColumnRef(BoundColumnRef {
+                    column_catalog: left_column.clone(),
+                }),
+                BoundExpr::ColumnRef(BoundColumnRef {
+                    column_catalog: right_column.clone(),
+                }),
+                on,
+            )?;
+            self.context
+                .using_columns
+                .push((right_column.clone(), left_column));
+            using.push(right_column);
+        }
+        Ok(using)
+    }
+
+    /// The left key which the right key of `USING` or `NATURAL` is merged into, the other columns
+    /// are resolved as themselves.
+    pub(crate) fn resolve_using_column(&self, column: ColumnCatalog) -> ColumnCatalog {
+        self.context
+            .using_columns
+            .iter()
+            .find(|(right, _)| *right == column)
+            .map(|(_, left)| left.clone())
+            .unwrap_or(column)
+    }
+
+    pub(crate) fn is_merged_using_column(&self, column: &ColumnCatalog) -> bool {
+        self.context
+            .using_columns
+            .iter()
+            .any(|(right, _)| right == column)
+    }
+
+    fn push_join_key(
+        &self,
+        left_key: BoundExpr,
+        right_key: BoundExpr,
+        on: &mut Vec<(BoundExpr, BoundExpr)>,
+    ) -> Result<(), BindError> {
+        // cast both keys to the common type, so that they are matched by equality
+        let (left_type, right_type) = (
+            left_key.return_type().unwrap_or(DataType::Null),
+            right_key.return_type().unwrap_or(DataType::Null),
+        );
+        let common = common_supertype(&left_type, &right_type).ok_or_else(|| {
+            BindError::BinaryOpTypeMismatch(left_type.to_string(), right_type.to_string())
+        })?;
+        on.push((
+            self.collate(cast_to(left_key, common.clone())),
+            self.collate(cast_to(right_key, common)),
+        ));
+        Ok(())
+    }
+}
+
+impl BoundTableRef {
+    /// all the columns of the relation, which are the left columns followed by the right columns
+    /// for join, except the right keys of `USING` or `NATURAL`.
+    pub fn columns(&self) -> Vec<ColumnCatalog> {
+        match self {
+            BoundTableRef::Table(table_catalog) => table_catalog.get_all_columns(),
+            BoundTableRef::Subquery(subquery) => subquery.catalog.get_all_columns(),
+            BoundTableRef::TableFunction(function) => function.catalog.get_all_columns(),
+            BoundTableRef::Join(join) => {
+                let mut columns = join.left.columns();
+                columns.extend(
+                    join.right
+                        .columns()
+                        .into_iter()
+                        .filter(|c| !join.using.contains(c)),
+                );
+                columns
+            }
+        }
+    }
+
+    /// the right keys of all the `USING` or `NATURAL` joins in the relation
+    pub fn using_columns(&self) -> Vec<ColumnCatalog> {
+        match self {
+            BoundTableRef::Table(_)
+            | BoundTableRef::Subquery(_)
+            | BoundTableRef::TableFunction(_) => vec![],
+            BoundTableRef::Join(join) => {
+                let mut columns = join.left.using_columns();
+                columns.extend(join.right.using_columns());
+                columns.extend(join.using.clone());
+                columns
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ColumnRefCollector {
+    columns: Vec<ColumnCatalog>,
+}
+
+impl ExprVisitor for ColumnRefCollector {
+    fn visit_column_ref(&mut self, expr: &BoundColumnRef) {
+        self.columns.push(expr.column_catalog.clone());
+    }
+}
+
+fn column_refs(expr: &BoundExpr) -> Vec<ColumnCatalog> {
+    let mut collector = ColumnRefCollector::default();
+    collector.visit_expr(expr);
+    collector.columns
+}
@@ -1,4 +1,12 @@
-use sqlparser::ast::{TableFactor, TableWithJoins};
+mod join;
+mod subquery;
+mod table_function;
+
+use itertools::Itertools;
+pub use join::*;
+use sqlparser::ast::{JoinConstraint, JoinOperator, TableFactor, TableWithJoins};
+pub use subquery::*;
+pub use table_function::*;
 
 use super::{BindError, Binder};
 use crate::catalog::TableCatalog;
@@ -6,22 +14,95 @@ use crate::catalog::TableCatalog;
 pub static DEFAULT_DATABASE_NAME: &str = "postgres";
 pub static DEFAULT_SCHEMA_NAME: &str = "postgres";
 
-#[derive(Debug)]
-pub struct BoundTableRef {
-    pub table_catalog: TableCatalog,
+#[derive(Debug, Clone)]
+pub enum BoundTableRef {
+    Table(TableCatalog),
+    Join(Join),
+    Subquery(BoundSubqueryRef),
+    TableFunction(BoundTableFunction),
 }
 
 impl Binder {
+    /// Bind the comma-separated relations of FROM as the cross join of them in order.
+    pub fn bind_from(&mut self, from: &[TableWithJoins]) -> Result<BoundTableRef, BindError> {
+        let mut relations = from.iter();
+        let first = relations.next().expect("FROM should not be empty");
+        let mut table_ref = self.bind_table_with_joins(first)?;
+        for relation in relations {
+            let right = self.bind_table_with_joins(relation)?;
+            table_ref = BoundTableRef::Join(Join {
+                left: Box::new(table_ref),
+                right: Box::new(right),
+                join_type: JoinType::Cross,
+                on: vec![],
+                using: vec![],
+            });
+        }
+        Ok(table_ref)
+    }
+
     pub fn bind_table_with_joins(
         &mut self,
         table_with_joins: &TableWithJoins,
     ) -> Result<BoundTableRef, BindError> {
-        self.bind_table_ref(&table_with_joins.relation)
+        let mut table_ref = self.bind_table_ref(&table_with_joins.relation)?;
+        for join in &table_with_joins.joins {
+            let right = self.bind_table_ref(&join.relation)?;
+            let mut on = vec![];
+            let mut using = vec![];
+            let join_type = match &join.join_operator {
+                JoinOperator::CrossJoin => JoinType::Cross,
+                JoinOperator::Inner(JoinConstraint::On(expr)) => {
+                    self.bind_join_on(expr, &table_ref, &mut on)?;
+                    JoinType::Inner
+                }
+                JoinOperator::Inner(JoinConstraint::Using(idents)) => {
+                    let names = idents
+                        .iter()
+                        .map(|ident| ident.value.to_lowercase())
+                        .collect_vec();
+                    using = self.bind_join_using(&names, &table_ref, &right, &mut on)?;
+                    JoinType::Inner
+                }
+                // the columns of the same name in both sides, which is cross join if none
+                JoinOperator::Inner(JoinConstraint::Natural) => {
+                    let right_names = right
+                        .columns()
+                        .into_iter()
+                        .map(|c| c.desc.name)
+                        .collect_vec();
+                    let names = table_ref
+                        .columns()
+                        .into_iter()
+                        .map(|c| c.desc.name)
+                        .filter(|name| right_names.contains(name))
+                        .unique()
+                        .collect_vec();
+                    using = self.bind_join_using(&names, &table_ref, &right, &mut on)?;
+                    match using.is_empty() {
+                        true => JoinType::Cross,
+                        false => JoinType::Inner,
+                    }
+                }
+                _ => return Err(BindError::UnsupportedStmt(format!("{}", join))),
+            };
+            table_ref = BoundTableRef::Join(Join {
+                left: Box::new(table_ref),
+                right: Box::new(right),
+                join_type,
+                on,
+                using,
+            });
+        }
+        Ok(table_ref)
     }
 
     pub fn bind_table_ref(&mut self, table: &TableFactor) -> Result<BoundTableRef, BindError> {
         match table {
-            TableFactor::Table { name, alias: _, .. } => {
+            TableFactor::Table {
+                name, alias, args, ..
+            } if !args.is_empty() => self.bind_table_function(name, alias, args),
+            TableFactor::Table { name, alias, .. } => {
                 // ObjectName internal items: db.schema.table
                 let (_database, _schema, table) = match name.0.as_slice() {
                     [table] => (
@@ -42,18 +123,51 @@ impl Binder {
                     _ => return Err(BindError::InvalidTable(name.to_string())),
                 };
 
+                // the CTE shadows the table of the same name
+                let cte_name = table.to_lowercase();
+                if let ([_], Some(cte)) = (name.0.as_slice(), self.ctes.get(&cte_name).cloned()) {
+                    let visible_name = match alias {
+                        Some(alias) => alias.name.value.to_lowercase(),
+                        None => cte_name.clone(),
+                    };
+                    let table_ref = self.bind_cte_ref(&cte_name, &cte, &visible_name)?;
+                    if let BoundTableRef::Subquery(subquery) = &table_ref {
+                        self.register_table(visible_name, subquery.catalog.clone());
+                    }
+                    return Ok(table_ref);
+                }
+
                 let table_name = table.to_string();
-                let table_catalog = self
+                let mut table_catalog = self
                     .catalog
-                    .get_table_by_name(table)
+                    .get_table_schema(table)
                     .ok_or_else(|| BindError::InvalidTable(table_name.clone()))?;
-                self.context
-                    .tables
-                    .insert(table_name, table_catalog.clone());
+                // the aliased table is only visible by its alias, same as PostgreSQL
+                let visible_name = match alias {
+                    Some(alias) => {
+                        let alias = alias.name.value.to_lowercase();
+                        table_catalog = table_catalog.with_alias(&alias);
+                        alias
+                    }
+                    None => table_name,
+                };
+                self.register_table(visible_name, table_catalog.clone());
 
-                Ok(BoundTableRef { table_catalog })
+                Ok(BoundTableRef::Table(table_catalog))
             }
             _ => panic!("unsupported table factor"),
         }
     }
+
+    /// make the relation visible by its name in the current select
+    fn register_table(&mut self, visible_name: String, table_catalog: TableCatalog) {
+        if self
+            .context
+            .tables
+            .insert(visible_name.clone(), table_catalog)
+            .is_none()
+        {
+            self.context.table_names.push(visible_name);
+        }
+    }
 }
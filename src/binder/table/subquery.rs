@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::{Cte, SetExpr};
+
+use super::BoundTableRef;
+use crate::binder::{BindError, Binder, BoundExpr, BoundSelect};
+use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog};
+
+/// The subquery as a relation of FROM, e.g. the reference of a CTE. Its output columns are exposed
+/// as the columns of `catalog`, which is named by the visible name of the relation.
+#[derive(Debug, Clone)]
+pub struct BoundSubqueryRef {
+    pub subquery: Box<BoundSelect>,
+    pub catalog: TableCatalog,
+}
+
+impl Binder {
+    /// Bind the CTE definition each time it's referenced, so the CTE is inlined as its subquery.
+    pub(super) fn bind_cte_ref(
+        &mut self,
+        name: &str,
+        cte: &Cte,
+        visible_name: &str,
+    ) -> Result<BoundTableRef, BindError> {
+        if !matches!(cte.query.body, SetExpr::Select(_)) {
+            return Err(BindError::InvalidSubquery(cte.query.to_string()));
+        }
+        // the CTE is not recursive, so it's invisible in its own definition
+        let outer_context = std::mem::take(&mut self.context);
+        let definition = self.ctes.remove(name);
+        let subquery = self.bind_select(&cte.query);
+        self.ctes
+            .extend(definition.map(|cte| (name.to_string(), cte)));
+        self.context = outer_context;
+        let subquery = subquery?;
+
+        let column_aliases = &cte.alias.columns;
+        if !column_aliases.is_empty() && column_aliases.len() != subquery.select_list.len() {
+            return Err(BindError::InvalidSubquery(format!(
+                "{} has {} columns but {} names",
+                name,
+                subquery.select_list.len(),
+                column_aliases.len()
+            )));
+        }
+        let mut columns = BTreeMap::new();
+        let mut column_ids = vec![];
+        for (i, expr) in subquery.select_list.iter().enumerate() {
+            let column_name = match (column_aliases.get(i), &subquery.select_aliases[i], expr) {
+                (Some(alias), _, _) => alias.value.to_lowercase(),
+                (None, Some(alias), _) => alias.clone(),
+                (None, None, BoundExpr::ColumnRef(col)) => col.column_catalog.desc.name.clone(),
+                _ => {
+                    return Err(BindError::InvalidSubquery(format!(
+                        "the column {:?} of {} needs an alias",
+                        expr, name
+                    )))
+                }
+            };
+            let column = ColumnCatalog {
+                table_id: visible_name.to_string(),
+                id: column_name.clone(),
+                desc: ColumnDesc {
+                    name: column_name.clone(),
+                    data_type: expr.return_type().unwrap_or(DataType::Null),
+                },
+            };
+            if columns.insert(column_name.clone(), column).is_some() {
+                return Err(BindError::AmbiguousColumn(column_name));
+            }
+            column_ids.push(column_name);
+        }
+        let catalog = TableCatalog {
+            id: visible_name.to_string(),
+            name: visible_name.to_string(),
+            columns,
+            column_ids,
+        };
+        Ok(BoundTableRef::Subquery(BoundSubqueryRef {
+            subquery: Box::new(subquery),
+            catalog,
+        }))
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use sqlparser::ast::{FunctionArg, FunctionArgExpr, ObjectName, TableAlias};
+
+use super::BoundTableRef;
+use crate::binder::{BindError, Binder, BoundExpr};
+use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog, TableFunctionRef};
+use crate::types::ScalarValue;
+
+/// The call of a table function in FROM, e.g. `json_each('{"a":1}')`. Its output columns are
+/// exposed as the columns of `catalog`, which is named by the visible name of the relation.
+#[derive(Clone)]
+pub struct BoundTableFunction {
+    pub function: TableFunctionRef,
+    pub args: Vec<ScalarValue>,
+    pub catalog: TableCatalog,
+}
+
+impl fmt::Debug for BoundTableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}({:?}) {:?}",
+            self.catalog.name, self.args, self.catalog
+        )
+    }
+}
+
+impl Binder {
+    /// Bind the call of a registered table function, whose arguments must be constants.
+    pub(super) fn bind_table_function(
+        &mut self,
+        name: &ObjectName,
+        alias: &Option<TableAlias>,
+        args: &[FunctionArg],
+    ) -> Result<BoundTableRef, BindError> {
+        let function_name = name.to_string().to_lowercase();
+        let function = self
+            .table_functions
+            .get(&function_name)
+            .ok_or_else(|| BindError::InvalidTable(function_name.clone()))?;
+
+        let mut bound_args = vec![];
+        for arg in args {
+            let expr = match arg {
+                FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => match arg {
+                    FunctionArgExpr::Expr(expr) => expr,
+                    _ => return Err(BindError::InvalidFunctionArgs(function_name)),
+                },
+            };
+            match self.bind_expr(expr)? {
+                BoundExpr::Constant(value) => bound_args.push(value),
+                _ => return Err(BindError::InvalidFunctionArgs(function_name)),
+            }
+        }
+
+        let visible_name = match alias {
+            Some(alias) => alias.name.value.to_lowercase(),
+            None => function_name.clone(),
+        };
+        let mut columns = BTreeMap::new();
+        let mut column_ids = vec![];
+        for field in function.schema().fields() {
+            let column_name = field.name().to_lowercase();
+            let column = ColumnCatalog {
+                table_id: visible_name.clone(),
+                id: column_name.clone(),
+                desc: ColumnDesc {
+                    name: column_name.clone(),
+                    data_type: field.data_type().clone(),
+                },
+            };
+            if columns.insert(column_name.clone(), column).is_some() {
+                return Err(BindError::AmbiguousColumn(column_name));
+            }
+            column_ids.push(column_name);
+        }
+        let catalog = TableCatalog {
+            id: visible_name.clone(),
+            name: function_name,
+            columns,
+            column_ids,
+        };
+        self.register_table(visible_name, catalog.clone());
+
+        Ok(BoundTableRef::TableFunction(BoundTableFunction {
+            function,
+            args: bound_args,
+            catalog,
+        }))
+    }
+}
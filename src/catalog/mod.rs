@@ -1,8 +1,11 @@
+mod table_function;
+
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::sync::Arc;
 
 use arrow::datatypes::DataType;
+pub use table_function::*;
 
 pub type RootCatalogRef = Arc<RootCatalog>;
 
@@ -29,6 +32,20 @@ impl RootCatalog {
     }
 }
 
+/// Look up the table schema by name without touching the table data, which is all the binder
+/// needs. It's implemented by the catalog snapshot and every storage.
+pub trait SchemaProvider: Send + Sync {
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog>;
+}
+
+pub type SchemaProviderRef = Arc<dyn SchemaProvider>;
+
+impl SchemaProvider for RootCatalog {
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog> {
+        self.get_table_by_name(name)
+    }
+}
+
 /// use table name as id for simplicity
 pub type TableId = String;
 
@@ -46,6 +63,14 @@ impl TableCatalog {
         self.columns.get(name).cloned()
     }
 
+    /// Reference the columns by the alias of table instead of the table id.
+    pub fn with_alias(mut self, alias: &str) -> Self {
+        for column in self.columns.values_mut() {
+            column.table_id = alias.to_string();
+        }
+        self
+    }
+
     pub fn get_all_columns(&self) -> Vec<ColumnCatalog> {
         self.column_ids
             .iter()
@@ -59,6 +84,9 @@ pub type ColumnId = String;
 
 #[derive(Clone, PartialEq)]
 pub struct ColumnCatalog {
+    /// the table reference the column comes from, which is the alias if the table is aliased,
+    /// so that the same name columns of tables, or of a self-joined table, are told apart.
+    pub table_id: TableId,
     pub id: ColumnId,
     pub desc: ColumnDesc,
 }
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result;
+use arrow::record_batch::RecordBatch;
+
+use crate::types::ScalarValue;
+
+/// The function in FROM which returns the computed rows rather than the rows of a table, e.g.
+/// `select * from json_each('{"a":1}')`.
+pub trait TableFunction: Send + Sync {
+    /// The columns of the rows returned, which don't depend on the arguments.
+    fn schema(&self) -> SchemaRef;
+
+    /// Compute the rows of the constant arguments, the batches should be of the `schema`.
+    fn call(&self, args: &[ScalarValue]) -> Result<Vec<RecordBatch>>;
+}
+
+pub type TableFunctionRef = Arc<dyn TableFunction>;
+
+/// The table functions by their lowercase names, which the binder looks up the function call in
+/// FROM from.
+#[derive(Clone, Default)]
+pub struct TableFunctionRegistry {
+    functions: HashMap<String, TableFunctionRef>,
+}
+
+impl TableFunctionRegistry {
+    pub fn register(&mut self, name: &str, function: TableFunctionRef) {
+        self.functions.insert(name.to_lowercase(), function);
+    }
+
+    pub fn get(&self, name: &str) -> Option<TableFunctionRef> {
+        self.functions.get(&name.to_lowercase()).cloned()
+    }
+}
+
+impl fmt::Debug for TableFunctionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.functions.keys()).finish()
+    }
+}
@@ -1,10 +1,10 @@
 use std::fs::File;
 
 use anyhow::{Error, Result};
+use arrow::util::pretty::pretty_format_batches;
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 
-use crate::util::pretty_batches;
 use crate::Database;
 
 pub async fn interactive(db: Database) -> Result<()> {
@@ -17,7 +17,7 @@ pub async fn interactive(db: Database) -> Result<()> {
             Ok(sql) => {
                 if !sql.trim().is_empty() {
                     rl.add_history_entry(sql.as_str());
-                    run_sql(&db, sql).await?;
+                    println!("{}", handle_sql(&db, &sql).await);
                 }
             }
             Err(ReadlineError::Interrupted) => {
@@ -92,23 +92,26 @@ fn read_sql(rl: &mut Editor<()>) -> Result<String, ReadlineError> {
     }
 }
 
-async fn run_sql(db: &Database, sql: String) -> Result<()> {
+/// Run the SQL or the internal command starts with "\\", and return the output to print. The
+/// errors are returned as the output, so that the session goes on with the tables loaded.
+pub async fn handle_sql(db: &Database, sql: &str) -> String {
     if let Some(cmds) = sql.trim().strip_prefix('\\') {
-        match run_internal(db, cmds) {
-            Ok(_) => println!("Run Internal {} Success", cmds),
-            Err(err) => println!("Run Internal {} Err: {}", cmds, err),
-        }
-        return Ok(());
+        return match run_internal(db, cmds) {
+            Ok(output) => output,
+            Err(err) => format!("Run Internal {} Err: {}", cmds, err),
+        };
     }
 
-    match db.run(sql.as_str()).await {
-        Ok(res) => pretty_batches(&res),
-        Err(err) => println!("Run Error: {}", err),
+    match db.run(sql).await {
+        Ok(res) => match pretty_format_batches(&res) {
+            Ok(table) => table.to_string(),
+            Err(err) => format!("Run Error: {}", err),
+        },
+        Err(err) => format!("Run Error: {}", err),
     }
-    Ok(())
 }
 
-fn run_internal(db: &Database, cmds: &str) -> Result<()> {
+fn run_internal(db: &Database, cmds: &str) -> Result<String> {
     if cmds.starts_with("load csv") {
         if let Some((table_name, filepath)) = cmds.trim_start_matches("load csv ").split_once(' ') {
             load_csv(db, table_name.trim(), filepath.trim())
@@ -122,14 +125,42 @@ fn run_internal(db: &Database, cmds: &str) -> Result<()> {
     }
 }
 
-fn load_csv(db: &Database, table_name: &str, filepath: &str) -> Result<()> {
-    println!("load csv {} {}", table_name, filepath);
+fn load_csv(db: &Database, table_name: &str, filepath: &str) -> Result<String> {
     db.create_csv_table(table_name.to_string(), filepath.to_string())?;
-    Ok(())
+    Ok(format!(
+        "Run Internal load csv {} {} Success",
+        table_name, filepath
+    ))
 }
 
-fn show_tables(db: &Database) -> Result<()> {
+fn show_tables(db: &Database) -> Result<String> {
     let data = db.show_tables()?;
-    pretty_batches(&vec![data]);
-    Ok(())
+    Ok(pretty_format_batches(&[data])?.to_string())
+}
+
+#[cfg(test)]
+mod cli_test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle_statements_of_session() {
+        let db = Database::new_on_csv();
+
+        let output = handle_sql(&db, "\\load csv employee ./tests/csv/employee.csv").await;
+        assert!(output.ends_with("Success"), "{}", output);
+        let output = handle_sql(&db, "\\dt").await;
+        assert!(output.contains("employee"), "{}", output);
+
+        // the table loaded is kept across the statements
+        let output = handle_sql(&db, "select id, first_name from employee\nwhere id = 1;").await;
+        assert!(output.contains("| 1  | Bill       |"), "{}", output);
+
+        // the errors don't end the session
+        let output = handle_sql(&db, "select no_such_column from employee;").await;
+        assert!(output.starts_with("Run Error: bind error"), "{}", output);
+        let output = handle_sql(&db, "\\unknown").await;
+        assert!(output.contains("Unknown internal command"), "{}", output);
+        let output = handle_sql(&db, "select count(*) from employee;").await;
+        assert!(output.contains("| 4 "), "{}", output);
+    }
 }
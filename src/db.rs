@@ -5,15 +5,21 @@ use arrow::record_batch::RecordBatch;
 use sqlparser::parser::ParserError;
 
 use crate::binder::{BindError, Binder};
+use crate::catalog::{TableFunctionRef, TableFunctionRegistry};
 use crate::executor::{try_collect, ExecutorBuilder, ExecutorError};
-use crate::optimizer::{InputRefRewriter, PhysicalRewriter, PlanRewriter};
+use crate::optimizer::{PhysicalRewriter, PlanRewriter};
 use crate::parser::parse;
-use crate::planner::{LogicalPlanError, Planner};
-use crate::storage::{CsvStorage, Storage, StorageError, StorageImpl};
+use crate::planner::{LogicalPlanError, Planner, PlannerConfig};
+use crate::storage::{CsvConfig, CsvStorage, Storage, StorageError, StorageImpl};
+use crate::types::{Collation, SessionRng};
 use crate::util::pretty_plan_tree;
 
 pub struct Database {
     storage: StorageImpl,
+    rng: SessionRng,
+    collation: Collation,
+    planner_config: PlannerConfig,
+    table_functions: TableFunctionRegistry,
 }
 
 impl Database {
@@ -21,9 +27,37 @@ impl Database {
         let storage = Arc::new(CsvStorage::new());
         Database {
             storage: StorageImpl::CsvStorage(storage),
+            rng: SessionRng::default(),
+            collation: Collation::default(),
+            planner_config: PlannerConfig::default(),
+            table_functions: TableFunctionRegistry::default(),
         }
     }
 
+    /// seed the rng of `random()`, so that the results are reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = SessionRng::with_seed(seed);
+        self
+    }
+
+    /// compare and sort the strings by the collation rather than bytes.
+    pub fn with_collation(mut self, collation: Collation) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    /// toggle the column pruning and predicate pushdown of the planner and optimizer.
+    pub fn with_planner_config(mut self, config: PlannerConfig) -> Self {
+        self.planner_config = config;
+        self
+    }
+
+    /// register the function returning computed rows, which is called in FROM by the name.
+    pub fn with_table_function(mut self, name: &str, function: TableFunctionRef) -> Self {
+        self.table_functions.register(name, function);
+        self
+    }
+
     pub fn create_csv_table(
         &self,
         table_name: String,
@@ -39,10 +73,41 @@ impl Database {
         }
     }
 
+    /// Register the csv file of the header and delimiter as a table.
+    pub fn register_csv(
+        &self,
+        table_name: &str,
+        path: &str,
+        has_header: bool,
+        delimiter: u8,
+    ) -> Result<(), DatabaseError> {
+        let cfg = CsvConfig::default()
+            .with_header(has_header)
+            .with_delimiter(delimiter);
+        self.register_csv_with_config(table_name, path, cfg)
+    }
+
+    pub fn register_csv_with_config(
+        &self,
+        table_name: &str,
+        path: &str,
+        cfg: CsvConfig,
+    ) -> Result<(), DatabaseError> {
+        if let StorageImpl::CsvStorage(ref storage) = self.storage {
+            storage.register_csv_with_config(table_name, path, cfg)?;
+            Ok(())
+        } else {
+            Err(DatabaseError::InternalError(
+                "currently only support csv storage".to_string(),
+            ))
+        }
+    }
+
     pub fn show_tables(&self) -> Result<RecordBatch, DatabaseError> {
         let data = match &self.storage {
             StorageImpl::CsvStorage(s) => s.show_tables()?,
             StorageImpl::InMemoryStorage(s) => s.show_tables()?,
+            StorageImpl::JsonStorage(s) => s.show_tables()?,
         };
         Ok(data)
     }
@@ -60,19 +125,20 @@ impl Database {
         let stats = parse(sql)?;
 
         // 2. bind AST to bound stmts
-        let catalog = storage.get_catalog();
-        let mut binder = Binder::new(Arc::new(catalog));
+        let mut binder = Binder::new(storage.clone())
+            .with_rng(self.rng.clone())
+            .with_collation(self.collation)
+            .with_table_functions(self.table_functions.clone());
         let bound_stmt = binder.bind(&stats[0])?;
         println!("bound_stmt = {:#?}", bound_stmt);
 
         // 3. convert bound stmts to logical plan
-        let planner = Planner {};
+        let planner = Planner::new(self.planner_config.clone());
         let logical_plan = planner.plan(bound_stmt)?;
         println!("logical_plan = {:#?}", logical_plan);
         pretty_plan_tree(&*logical_plan);
 
-        let mut input_ref_rewriter = InputRefRewriter::default();
-        let new_logical_plan = input_ref_rewriter.rewrite(logical_plan);
+        let new_logical_plan = planner.optimize(logical_plan, storage.table_cardinalities());
         println!("new_logical_plan = {:#?}", new_logical_plan);
         pretty_plan_tree(&*new_logical_plan);
 
@@ -135,3 +201,117 @@ pub enum DatabaseError {
     #[error("Internal error: {0}")]
     InternalError(String),
 }
+
+#[cfg(test)]
+mod db_test {
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+
+    use super::*;
+    use crate::catalog::TableFunction;
+    use crate::types::ScalarValue;
+    use crate::util::TempFile;
+
+    /// `repeat_rows(s, n)` returns the rows of `(i, s)` for i in 1..=n.
+    struct RepeatRows {}
+
+    impl TableFunction for RepeatRows {
+        fn schema(&self) -> SchemaRef {
+            Arc::new(Schema::new(vec![
+                Field::new("i", DataType::Int64, false),
+                Field::new("s", DataType::Utf8, false),
+            ]))
+        }
+
+        fn call(&self, args: &[ScalarValue]) -> Result<Vec<RecordBatch>, ArrowError> {
+            let (s, n) = match args {
+                [ScalarValue::String(Some(s)), ScalarValue::Int32(Some(n))] => (s, *n as i64),
+                _ => {
+                    return Err(ArrowError::InvalidArgumentError(format!(
+                        "repeat_rows expects a string and a count, got {:?}",
+                        args
+                    )))
+                }
+            };
+            let batch = RecordBatch::try_new(
+                self.schema(),
+                vec![
+                    Arc::new(Int64Array::from_iter_values(1..=n)),
+                    Arc::new(StringArray::from(vec![s.as_str(); n as usize])),
+                ],
+            )?;
+            Ok(vec![batch])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_csv_without_header() -> Result<(), DatabaseError> {
+        let file = TempFile::with_content(".csv", "1;Bill;100\n2;Gregg;100\n3;John;200\n")
+            .map_err(StorageError::from)?;
+        let db = Database::new_on_csv();
+        db.register_csv("people", file.path().to_str().unwrap(), false, b';')?;
+
+        let batches = db
+            .run("select count(*), sum(column_3) from people where column_1 > 1")
+            .await?;
+
+        let column = |i: usize| {
+            batches[0]
+                .column(i)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .value(0)
+        };
+        assert_eq!((column(0), column(1)), (2, 300));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_filter_inferred_int_column_by_literal() -> Result<(), DatabaseError> {
+        let file = TempFile::with_content(".csv", "id,name\n1,Bill\n2,Gregg\n")
+            .map_err(StorageError::from)?;
+        let db = Database::new_on_csv();
+        let cfg = CsvConfig::default().with_infer_schema_rows(100);
+        db.register_csv_with_config("people", file.path().to_str().unwrap(), cfg)?;
+
+        let batches = db.run("select name from people where id = 1").await?;
+
+        let names = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names.value(0), "Bill");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_select_from_registered_table_function() -> Result<(), DatabaseError> {
+        let db = Database::new_on_csv().with_table_function("repeat_rows", Arc::new(RepeatRows {}));
+
+        let batches = db
+            .run("select r.i, s from repeat_rows('ab', 3) as r where r.i > 1")
+            .await?;
+        let ids = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        let strings = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(*ids, Int64Array::from(vec![2, 3]));
+        assert_eq!(*strings, StringArray::from(vec!["ab", "ab"]));
+
+        let result = db.run("select * from unknown_rows(1)").await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::Bind(BindError::InvalidTable(name))) if name == "unknown_rows"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use arrow::array::{Array, ArrayRef, Float64Array};
+use arrow::compute;
+use arrow::datatypes::DataType;
+
+use super::{invalid_state, Accumulator};
+use crate::executor::decimal_compute::cast_array;
+use crate::executor::ExecutorError;
+use crate::types::ScalarValue;
+
+pub struct AvgAccumulator {
+    sum: f64,
+    count: i64,
+}
+
+impl AvgAccumulator {
+    pub fn new() -> Self {
+        Self { sum: 0.0, count: 0 }
+    }
+}
+
+impl Accumulator for AvgAccumulator {
+    fn update_batch(&mut self, array: &ArrayRef) -> Result<(), ExecutorError> {
+        let values = cast_array(array, &DataType::Float64)?;
+        let values = values.as_any().downcast_ref::<Float64Array>().unwrap();
+        self.sum += compute::sum(values).unwrap_or(0.0);
+        self.count += (values.len() - values.null_count()) as i64;
+        Ok(())
+    }
+
+    /// returns NULL if there is no non-NULL input value.
+    fn evaluate(&self) -> Result<ScalarValue, ExecutorError> {
+        if self.count == 0 {
+            return Ok(ScalarValue::Float64(None));
+        }
+        Ok(ScalarValue::Float64(Some(self.sum / self.count as f64)))
+    }
+
+    /// the state is the running `[sum, count]`.
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError> {
+        Ok(vec![
+            ScalarValue::Float64(Some(self.sum)),
+            ScalarValue::Int64(Some(self.count)),
+        ])
+    }
+
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [ScalarValue::Float64(Some(sum)), ScalarValue::Int64(Some(count))] => {
+                self.sum = *sum;
+                self.count = *count;
+            }
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
+
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [ScalarValue::Float64(Some(sum)), ScalarValue::Int64(Some(count))] => {
+                self.sum += *sum;
+                self.count += *count;
+            }
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
+}
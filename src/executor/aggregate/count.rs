@@ -1,6 +1,6 @@
 use arrow::array::ArrayRef;
 
-use super::Accumulator;
+use super::{invalid_state, Accumulator};
 use crate::executor::ExecutorError;
 use crate::types::ScalarValue;
 
@@ -16,11 +16,31 @@ impl CountAccumulator {
 
 impl Accumulator for CountAccumulator {
     fn update_batch(&mut self, array: &ArrayRef) -> Result<(), ExecutorError> {
-        self.result = (array.len() - array.null_count()) as i64;
+        self.result += (array.len() - array.null_count()) as i64;
         Ok(())
     }
 
     fn evaluate(&self) -> Result<ScalarValue, ExecutorError> {
         Ok(ScalarValue::Int64(Some(self.result)))
     }
+
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError> {
+        Ok(vec![ScalarValue::Int64(Some(self.result))])
+    }
+
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [ScalarValue::Int64(Some(count))] => self.result = *count,
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
+
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [ScalarValue::Int64(Some(count))] => self.result += *count,
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
 }
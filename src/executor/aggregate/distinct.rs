@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use arrow::array::{ArrayRef, UInt32Builder};
+use arrow::compute;
+
+use super::{invalid_state, Accumulator};
+use crate::executor::ExecutorError;
+use crate::types::{append_scalar_value_for_builder, build_scalar_value_builder, ScalarValue};
+
+/// Wrap the accumulator of distinct aggregate, only the first seen non-null values are passed to
+/// the inner accumulator, so that each unique value is folded once.
+pub struct DistinctAccumulator {
+    seen: HashSet<ScalarValue>,
+    inner: Box<dyn Accumulator>,
+}
+
+impl DistinctAccumulator {
+    pub fn new(inner: Box<dyn Accumulator>) -> Self {
+        Self {
+            seen: HashSet::new(),
+            inner,
+        }
+    }
+}
+
+impl Accumulator for DistinctAccumulator {
+    fn update_batch(&mut self, array: &ArrayRef) -> Result<(), ExecutorError> {
+        let mut indices = UInt32Builder::new(array.len());
+        for i in 0..array.len() {
+            let value = ScalarValue::try_from_array(array, i);
+            if !value.is_null() && self.seen.insert(value) {
+                indices.append_value(i as u32)?;
+            }
+        }
+        let unseen = compute::take(array.as_ref(), &indices.finish(), None)?;
+        self.inner.update_batch(&unseen)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue, ExecutorError> {
+        self.inner.evaluate()
+    }
+
+    /// the state is the inner state followed by the seen values.
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError> {
+        let mut state = self.inner.state()?;
+        state.extend(self.seen.iter().cloned());
+        Ok(state)
+    }
+
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        // the inner state has a fixed length, so that it is told apart by the fresh inner one
+        let inner_len = self.inner.state()?.len();
+        if state.len() < inner_len {
+            return Err(invalid_state(state));
+        }
+        let (inner, seen) = state.split_at(inner_len);
+        self.inner.from_state(inner)?;
+        self.seen = seen.iter().cloned().collect();
+        Ok(())
+    }
+
+    /// the inner state is derived from the seen values, so only the values unseen by this one
+    /// are folded into the inner accumulator.
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        let inner_len = self.inner.state()?.len();
+        if state.len() < inner_len {
+            return Err(invalid_state(state));
+        }
+        let unseen = state[inner_len..]
+            .iter()
+            .filter(|value| self.seen.insert((*value).clone()))
+            .collect::<Vec<_>>();
+        let data_type = match unseen.first() {
+            Some(value) => value.data_type(),
+            None => return Ok(()),
+        };
+        let mut builder = build_scalar_value_builder(&data_type);
+        for value in unseen {
+            append_scalar_value_for_builder(value, &mut builder)?;
+        }
+        self.inner.update_batch(&builder.finish())
+    }
+}
@@ -1,20 +1,33 @@
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 
-use arrow::array::UInt32Builder;
+use arrow::array::{Array, ArrayRef, Int64Array, UInt32Builder};
 use arrow::compute;
-use arrow::datatypes::{Field, Schema, SchemaRef};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
 use itertools::Itertools;
 
-use super::create_accumulators;
 use super::hash_utils::create_hashes;
+use super::{create_accumulators, Accumulator};
 use crate::binder::{BoundAggFunc, BoundExpr};
 use crate::executor::*;
+use crate::storage::StorageError;
 use crate::types::{append_scalar_value_for_builder, build_scalar_value_builder, ScalarValue};
+use crate::util::TempFile;
+
+/// The number of files the groups are spilled to by their hashes.
+const SPILL_PARTITIONS: usize = 8;
 
 pub struct HashAggExecutor {
     pub agg_funcs: Vec<BoundExpr>,
     pub group_by: Vec<BoundExpr>,
     pub child: BoxedExecutor,
+    /// the max bytes of the groups in memory, the partial aggregates are spilled to disk once
+    /// exceeding it, and merged after all the input is aggregated. The DISTINCT aggregates are
+    /// not spilled but fail with `ResourcesExhausted` once exceeding it.
+    pub spill_threshold: Option<usize>,
 }
 
 impl HashAggExecutor {
@@ -33,9 +46,8 @@ impl HashAggExecutor {
         let agg_funcs = self.cast_agg_funcs();
 
         let mut group_and_agg_fields: Option<Vec<Field>> = None;
-        let mut group_hashs = Vec::new();
-        let mut group_hash_2_keys = HashMap::new();
-        let mut group_hash_2_accs = HashMap::new();
+        let mut groups = GroupTable::default();
+        let mut spill: Option<Spill> = None;
 
         #[for_await]
         for batch in self.child {
@@ -73,61 +85,148 @@ impl HashAggExecutor {
             create_hashes(&group_keys, &Default::default(), &mut every_rows_hashes)?;
 
             // 3.2
-            // a. build accumulator map(group_hash_2_accs) for aggregation calculation.
-            // b. build group row indices map(group_hash_2_row_indices) to take one group rows
+            // a. find the group of each row by the hash and then the keys, since different keys
+            // may collide on the same hash, and build accumulators for the new groups.
+            // b. build group row indices map(group_2_row_indices) to take one group rows
             // from a column, and use acc.update_batch to calculate the group result.
-            // c. build group values map(group_hash_2_keys) to record the group values.
-            // d. build group hashs vector(group_hashs) to record distinct group hash key order.
-            let mut group_hash_2_row_indices = HashMap::new();
+            let mut group_2_row_indices = HashMap::new();
             for (row, hash) in every_rows_hashes.iter().enumerate() {
-                if !group_hash_2_accs.contains_key(hash) {
-                    // group key hash -> accumulator
-                    group_hash_2_accs.insert(*hash, create_accumulators(&self.agg_funcs));
-                    // group key hash -> row indices
-                    group_hash_2_row_indices.insert(*hash, UInt32Builder::new(0));
-                    // group key hash -> group keys
-                    let group_by_values = group_keys
-                        .iter()
-                        .map(|col| ScalarValue::try_from_array(col, row))
-                        .collect::<Vec<_>>();
-                    group_hash_2_keys.insert(*hash, group_by_values);
-                    // keep group key hash order for later result order
-                    group_hashs.push(*hash);
-                }
+                let group_by_values = group_keys
+                    .iter()
+                    .map(|col| ScalarValue::try_from_array(col, row))
+                    .collect::<Vec<_>>();
+                let group = match groups.find(*hash, &group_by_values) {
+                    Some(group) => group,
+                    None => {
+                        groups.insert(*hash, group_by_values, create_accumulators(&self.agg_funcs))
+                    }
+                };
 
-                group_hash_2_row_indices
-                    .get_mut(hash)
-                    .unwrap()
+                group_2_row_indices
+                    .entry(group)
+                    .or_insert_with(|| UInt32Builder::new(0))
                     .append_value(row as u32)?;
             }
 
             // 4. finish aggregation result for each group.
-            for (hash, mut idx_builder) in group_hash_2_row_indices {
+            for (group, mut idx_builder) in group_2_row_indices {
                 let indices = idx_builder.finish();
-                let accs = group_hash_2_accs.get_mut(&hash).unwrap();
-                for (acc, column) in accs.iter_mut().zip_eq(columns.iter()) {
+                for (acc, column) in groups.accs[group].iter_mut().zip_eq(columns.iter()) {
                     // take one group rows from a column
                     let new_array = compute::take(column.as_ref(), &indices, None)?;
                     acc.update_batch(&new_array)?;
                 }
+                // the states may grow with the input, e.g. the max of strings
+                if self.spill_threshold.is_some() {
+                    groups.update_memory_usage(group);
+                }
+            }
+
+            // 5. spill the groups to disk if they exceed the threshold.
+            if let Some(threshold) = self.spill_threshold {
+                if groups.memory_usage > threshold {
+                    // the state of distinct aggregate is followed by the distinct values seen so
+                    // far, which is of variable length and can't be spilled to the fixed columns,
+                    // so the distinct aggregates fail rather than being spilled.
+                    if agg_funcs.iter().any(|agg| agg.distinct) {
+                        return Err(ExecutorError::ResourcesExhausted(format!(
+                            "hash agg with distinct aggregates uses {} bytes, exceeding the spill \
+                             threshold {}",
+                            groups.memory_usage, threshold
+                        )));
+                    }
+                    if spill.is_none() {
+                        let fields = group_and_agg_fields.as_ref().unwrap();
+                        let key_fields = &fields[..self.group_by.len()];
+                        spill = Some(Spill::try_new(key_fields, &self.agg_funcs)?);
+                    }
+                    spill.as_mut().unwrap().write(std::mem::take(&mut groups))?;
+                }
             }
         }
 
-        // 5.1 build result builders for hash_agg RecordBatch.
         let fields = group_and_agg_fields.unwrap();
+        match spill {
+            Some(mut spill) => {
+                // 6. merge the spilled groups partition by partition, the groups in memory are
+                // spilled as well so that each group is output once.
+                spill.write(groups)?;
+                for partition in spill.finish()? {
+                    let groups = partition.merge(self.group_by.len(), &self.agg_funcs)?;
+                    if !groups.keys.is_empty() {
+                        yield groups.finish(fields.clone())?;
+                    }
+                }
+            }
+            None => yield groups.finish(fields)?,
+        }
+    }
+}
+
+/// The groups aggregated so far in the order of first seen, which is the order of output.
+#[derive(Default)]
+struct GroupTable {
+    /// the indices of groups by the hashes of their keys, the groups of different keys may share
+    /// the same hash, so the keys are compared to find a group
+    buckets: HashMap<u64, Vec<usize>>,
+    hashes: Vec<u64>,
+    keys: Vec<Vec<ScalarValue>>,
+    accs: Vec<Vec<Box<dyn Accumulator>>>,
+    /// the estimated bytes of each group when last measured
+    sizes: Vec<usize>,
+    /// the estimated bytes of the group keys and the partial states
+    memory_usage: usize,
+}
+
+impl GroupTable {
+    fn find(&self, hash: u64, keys: &[ScalarValue]) -> Option<usize> {
+        self.buckets
+            .get(&hash)?
+            .iter()
+            .copied()
+            .find(|group| self.keys[*group] == keys)
+    }
+
+    fn insert(
+        &mut self,
+        hash: u64,
+        keys: Vec<ScalarValue>,
+        accs: Vec<Box<dyn Accumulator>>,
+    ) -> usize {
+        let group = self.keys.len();
+        self.buckets.entry(hash).or_default().push(group);
+        self.hashes.push(hash);
+        self.keys.push(keys);
+        self.accs.push(accs);
+        self.sizes.push(0);
+        self.update_memory_usage(group);
+        group
+    }
+
+    /// Measure the group again after its accumulators are updated.
+    fn update_memory_usage(&mut self, group: usize) {
+        let size = self.keys[group].iter().map(scalar_size).sum::<usize>()
+            + self.accs[group]
+                .iter()
+                .map(|acc| acc.state().map_or(0, |s| s.iter().map(scalar_size).sum()))
+                .sum::<usize>();
+        let old_size = std::mem::replace(&mut self.sizes[group], size);
+        self.memory_usage = self.memory_usage + size - old_size;
+    }
+
+    /// Convert the groups to columnar data, the group keys followed by the aggregate results.
+    fn finish(self, fields: Vec<Field>) -> Result<RecordBatch, ExecutorError> {
         let mut builders = fields
             .iter()
             .map(|f| build_scalar_value_builder(f.data_type()))
             .collect::<Vec<_>>();
 
-        // 5.2 convert row data to columnar data using builders.
-        for hash in group_hashs {
-            let group_values = group_hash_2_keys.get(&hash).unwrap();
+        for (group_values, accs) in self.keys.iter().zip_eq(self.accs.iter()) {
             for (idx, group_key) in group_values.iter().enumerate() {
                 append_scalar_value_for_builder(group_key, &mut builders[idx])?;
             }
 
-            for (idx, acc) in group_hash_2_accs.get(&hash).unwrap().iter().enumerate() {
+            for (idx, acc) in accs.iter().enumerate() {
                 append_scalar_value_for_builder(
                     &acc.evaluate()?,
                     &mut builders[idx + group_values.len()],
@@ -135,9 +234,220 @@ impl HashAggExecutor {
             }
         }
 
-        // 6. finish result builders and build RecordBatch.
         let columns = builders.iter_mut().map(|b| b.finish()).collect::<Vec<_>>();
         let schema = SchemaRef::new(Schema::new(fields));
-        yield RecordBatch::try_new(schema, columns)?;
+        Ok(RecordBatch::try_new(schema, columns)?)
+    }
+}
+
+fn scalar_size(value: &ScalarValue) -> usize {
+    let heap_size = match value {
+        ScalarValue::String(Some(s)) => s.len(),
+        _ => 0,
+    };
+    std::mem::size_of::<ScalarValue>() + heap_size
+}
+
+/// The partial aggregates spilled to the arrow ipc files partitioned by group hash, each row is
+/// the group hash, keys and the states of accumulators. The same group is always spilled to the
+/// same partition, so that the partitions are merged one by one in memory, where the groups of
+/// colliding hashes are told apart by their keys.
+struct Spill {
+    schema: SchemaRef,
+    partitions: Vec<(SpillFile, FileWriter<BufWriter<File>>)>,
+}
+
+impl Spill {
+    fn try_new(key_fields: &[Field], agg_funcs: &[BoundExpr]) -> Result<Self, ExecutorError> {
+        let mut fields = vec![Field::new("hash", DataType::Int64, false)];
+        fields.extend(
+            key_fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Field::new(&format!("key_{}", i), f.data_type().clone(), true)),
+        );
+        for (i, acc) in create_accumulators(agg_funcs).iter().enumerate() {
+            for (j, state) in acc.state()?.iter().enumerate() {
+                fields.push(Field::new(
+                    &format!("state_{}_{}", i, j),
+                    state.data_type(),
+                    true,
+                ));
+            }
+        }
+        let schema = SchemaRef::new(Schema::new(fields));
+        let partitions = (0..SPILL_PARTITIONS)
+            .map(|_| -> Result<_, ExecutorError> {
+                let file = SpillFile::new();
+                let writer =
+                    BufWriter::new(File::create(file.0.path()).map_err(StorageError::from)?);
+                Ok((file, FileWriter::try_new(writer, &schema)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { schema, partitions })
+    }
+
+    fn write(&mut self, groups: GroupTable) -> Result<(), ExecutorError> {
+        let mut builders = (0..SPILL_PARTITIONS)
+            .map(|_| {
+                self.schema
+                    .fields()
+                    .iter()
+                    .map(|f| build_scalar_value_builder(f.data_type()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (group, hash) in groups.hashes.iter().enumerate() {
+            let builders = &mut builders[*hash as usize % SPILL_PARTITIONS];
+            let mut row = vec![ScalarValue::Int64(Some(*hash as i64))];
+            row.extend(groups.keys[group].iter().cloned());
+            for acc in &groups.accs[group] {
+                row.extend(acc.state()?);
+            }
+            if row.len() != builders.len() {
+                return Err(ExecutorError::InternalError(format!(
+                    "spilled row {:?} mismatches schema {:?}",
+                    row, self.schema
+                )));
+            }
+            for (value, builder) in row.iter().zip(builders.iter_mut()) {
+                append_scalar_value_for_builder(value, builder)?;
+            }
+        }
+        for ((_, writer), mut builders) in self.partitions.iter_mut().zip(builders) {
+            let columns = builders.iter_mut().map(|b| b.finish()).collect::<Vec<_>>();
+            if columns[0].is_empty() {
+                continue;
+            }
+            writer.write(&RecordBatch::try_new(self.schema.clone(), columns)?)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Vec<SpillFile>, ExecutorError> {
+        let mut files = vec![];
+        for (file, mut writer) in self.partitions {
+            writer.finish()?;
+            files.push(file);
+        }
+        Ok(files)
+    }
+}
+
+/// The temporary file of one spilled partition, which is removed when dropped.
+struct SpillFile(TempFile);
+
+impl SpillFile {
+    fn new() -> Self {
+        Self(TempFile::new(".arrow"))
+    }
+
+    /// Merge the partial states of the same group into one group.
+    fn merge(&self, num_keys: usize, agg_funcs: &[BoundExpr]) -> Result<GroupTable, ExecutorError> {
+        let file = File::open(self.0.path()).map_err(StorageError::from)?;
+        let reader = FileReader::try_new(BufReader::new(file), None)?;
+        let mut groups = GroupTable::default();
+        for batch in reader {
+            let batch = batch?;
+            let hashes = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap();
+            let columns: &[ArrayRef] = batch.columns();
+            for row in 0..batch.num_rows() {
+                let hash = hashes.value(row) as u64;
+                let mut values = columns[1..]
+                    .iter()
+                    .map(|col| ScalarValue::try_from_array(col, row));
+                let keys = values.by_ref().take(num_keys).collect::<Vec<_>>();
+                let states = values.collect::<Vec<_>>();
+
+                let (group, merged) = match groups.find(hash, &keys) {
+                    Some(group) => (group, true),
+                    None => (
+                        groups.insert(hash, keys, create_accumulators(agg_funcs)),
+                        false,
+                    ),
+                };
+                let mut states = states.as_slice();
+                for acc in groups.accs[group].iter_mut() {
+                    let len = acc.state()?.len();
+                    let (state, rest) = states.split_at(len);
+                    if merged {
+                        acc.merge_state(state)?;
+                    } else {
+                        acc.from_state(state)?;
+                    }
+                    states = rest;
+                }
+            }
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod hash_agg_test {
+    use std::sync::Arc;
+
+    use arrow::array::StringArray;
+
+    use super::*;
+    use crate::executor::aggregate::min_max::MaxAccumulator;
+
+    #[test]
+    fn test_group_table_tracks_growing_states() -> Result<(), ExecutorError> {
+        let mut groups = GroupTable::default();
+        let group = groups.insert(
+            0,
+            vec![ScalarValue::Int64(Some(1))],
+            vec![Box::new(MaxAccumulator::new(DataType::Utf8))],
+        );
+        let inserted = groups.memory_usage;
+
+        let array: ArrayRef = Arc::new(StringArray::from(vec!["a".repeat(1000)]));
+        groups.accs[group][0].update_batch(&array)?;
+        groups.update_memory_usage(group);
+        assert_eq!(groups.memory_usage, inserted + 1000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_table_separates_colliding_hashes() {
+        let mut groups = GroupTable::default();
+        let one = vec![ScalarValue::Int64(Some(1))];
+        let two = vec![ScalarValue::Int64(Some(2))];
+        let first = groups.insert(7, one.clone(), vec![]);
+        assert_eq!(groups.find(7, &two), None);
+        let second = groups.insert(7, two.clone(), vec![]);
+
+        assert_ne!(first, second);
+        assert_eq!(groups.find(7, &one), Some(first));
+        assert_eq!(groups.find(7, &two), Some(second));
+        assert_eq!(groups.find(8, &one), None);
+    }
+
+    #[test]
+    fn test_spill_merges_colliding_hashes_by_keys() -> Result<(), ExecutorError> {
+        let mut spill = Spill::try_new(&[Field::new("k", DataType::Int64, true)], &[])?;
+        for key in [1, 2, 1] {
+            let mut groups = GroupTable::default();
+            groups.insert(7, vec![ScalarValue::Int64(Some(key))], vec![]);
+            spill.write(groups)?;
+        }
+
+        let mut keys = vec![];
+        for partition in spill.finish()? {
+            keys.extend(partition.merge(1, &[])?.keys);
+        }
+        assert_eq!(
+            keys,
+            vec![
+                vec![ScalarValue::Int64(Some(1))],
+                vec![ScalarValue::Int64(Some(2))]
+            ]
+        );
+        Ok(())
     }
 }
@@ -2,9 +2,10 @@
 
 use ahash::{CallHasher, RandomState};
 use arrow::array::{
-    Array, ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, StringArray,
+    Array, ArrayRef, BooleanArray, Date32Array, DecimalArray, Float64Array, Int32Array, Int64Array,
+    StringArray, TimestampMicrosecondArray,
 };
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, TimeUnit};
 
 use crate::executor::ExecutorError;
 
@@ -179,6 +180,40 @@ pub fn create_hashes<'a>(
             DataType::Int64 => {
                 hash_array_primitive!(Int64Array, col, i64, hashes_buffer, random_state, multi_col);
             }
+            DataType::Date32 => {
+                hash_array_primitive!(
+                    Date32Array,
+                    col,
+                    i32,
+                    hashes_buffer,
+                    random_state,
+                    multi_col
+                );
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                hash_array_primitive!(
+                    TimestampMicrosecondArray,
+                    col,
+                    i64,
+                    hashes_buffer,
+                    random_state,
+                    multi_col
+                );
+            }
+            DataType::Decimal(_, _) => {
+                let array = col.as_any().downcast_ref::<DecimalArray>().unwrap();
+                for (i, hash) in hashes_buffer.iter_mut().enumerate() {
+                    if array.is_null(i) {
+                        continue;
+                    }
+                    let value_hash = i128::get_hash(&array.value(i).as_i128(), random_state);
+                    *hash = if multi_col {
+                        combine_hashes(value_hash, *hash)
+                    } else {
+                        value_hash
+                    };
+                }
+            }
             DataType::Float64 => {
                 hash_array_float!(
                     Float64Array,
@@ -1,10 +1,11 @@
 // arrow compute logic inspired by datafusion
 
-use arrow::array::{ArrayRef, Float64Array, Int32Array, Int64Array, StringArray};
+use arrow::array::{Array, ArrayRef, Float64Array, Int32Array, Int64Array, StringArray};
 use arrow::compute;
 use arrow::datatypes::DataType;
 
-use super::Accumulator;
+use super::{invalid_state, Accumulator};
+use crate::executor::scalar_cmp::{max_scalar, min_scalar};
 use crate::executor::ExecutorError;
 use crate::types::ScalarValue;
 
@@ -43,9 +44,20 @@ macro_rules! min_max_batch {
     }};
 }
 
+// min/max(array) -> ScalarValue for the types without arrow min/max kernel, e.g. decimal.
+fn fold_min_max_batch<F>(values: &ArrayRef, f: F) -> Result<ScalarValue, ExecutorError>
+where
+    F: Fn(&ScalarValue, &ScalarValue) -> Result<ScalarValue, ExecutorError>,
+{
+    (0..values.len())
+        .map(|i| ScalarValue::try_from_array(values, i))
+        .try_fold(ScalarValue::from(values.data_type()), |acc, v| f(&acc, &v))
+}
+
 /// dynamically-typed min(array) -> ScalarValue
 fn min_batch(values: &ArrayRef) -> Result<ScalarValue, ExecutorError> {
     Ok(match values.data_type() {
+        DataType::Decimal(..) => fold_min_max_batch(values, min_scalar)?,
         DataType::Utf8 => {
             typed_min_max_batch_string!(values, StringArray, String, min_string)
         }
@@ -56,6 +68,7 @@ fn min_batch(values: &ArrayRef) -> Result<ScalarValue, ExecutorError> {
 /// dynamically-typed max(array) -> ScalarValue
 fn max_batch(values: &ArrayRef) -> Result<ScalarValue, ExecutorError> {
     Ok(match values.data_type() {
+        DataType::Decimal(..) => fold_min_max_batch(values, max_scalar)?,
         DataType::Utf8 => {
             typed_min_max_batch_string!(values, StringArray, String, max_string)
         }
@@ -63,51 +76,6 @@ fn max_batch(values: &ArrayRef) -> Result<ScalarValue, ExecutorError> {
     })
 }
 
-// min/max of two scalar string values.
-macro_rules! typed_min_max_string {
-    ($VALUE:expr, $DELTA:expr, $SCALAR:ident, $OP:ident) => {{
-        ScalarValue::$SCALAR(match ($VALUE, $DELTA) {
-            (None, None) => None,
-            (Some(a), None) => Some(a.clone()),
-            (None, Some(b)) => Some(b.clone()),
-            (Some(a), Some(b)) => Some((a).$OP(b).clone()),
-        })
-    }};
-}
-
-// min/max of two non-string scalar values.
-macro_rules! typed_min_max {
-    ($VALUE:expr, $DELTA:expr, $SCALAR:ident, $OP:ident) => {{
-        ScalarValue::$SCALAR(match ($VALUE, $DELTA) {
-            (None, None) => None,
-            (Some(a), None) => Some(a.clone()),
-            (None, Some(b)) => Some(b.clone()),
-            (Some(a), Some(b)) => Some((*a).$OP(*b)),
-        })
-    }};
-}
-
-// min/max of two scalar values of the same type
-macro_rules! min_max {
-    ($VALUE:expr, $DELTA:expr, $OP:ident) => {{
-        match ($VALUE, $DELTA) {
-            (ScalarValue::Float64(lhs), ScalarValue::Float64(rhs)) => {
-                typed_min_max!(lhs, rhs, Float64, $OP)
-            }
-            (ScalarValue::Int64(lhs), ScalarValue::Int64(rhs)) => {
-                typed_min_max!(lhs, rhs, Int64, $OP)
-            }
-            (ScalarValue::Int32(lhs), ScalarValue::Int32(rhs)) => {
-                typed_min_max!(lhs, rhs, Int32, $OP)
-            }
-            (ScalarValue::String(lhs), ScalarValue::String(rhs)) => {
-                typed_min_max_string!(lhs, rhs, String, $OP)
-            }
-            _ => unimplemented!("unsupported min_max scalar type: {}", $VALUE.data_type()),
-        }
-    }};
-}
-
 pub struct MinAccumulator {
     min: ScalarValue,
 }
@@ -123,13 +91,35 @@ impl MinAccumulator {
 impl Accumulator for MinAccumulator {
     fn update_batch(&mut self, array: &ArrayRef) -> Result<(), ExecutorError> {
         let delta = &min_batch(array)?;
-        self.min = min_max!(&self.min, delta, min);
+        self.min = min_scalar(&self.min, delta)?;
         Ok(())
     }
 
     fn evaluate(&self) -> Result<ScalarValue, ExecutorError> {
         Ok(self.min.clone())
     }
+
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError> {
+        Ok(vec![self.min.clone()])
+    }
+
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [min] if min.data_type() == self.min.data_type() => self.min = min.clone(),
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
+
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [min] if min.data_type() == self.min.data_type() => {
+                self.min = min_scalar(&self.min, min)?
+            }
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
 }
 
 pub struct MaxAccumulator {
@@ -147,11 +137,33 @@ impl MaxAccumulator {
 impl Accumulator for MaxAccumulator {
     fn update_batch(&mut self, array: &ArrayRef) -> Result<(), ExecutorError> {
         let delta = &max_batch(array)?;
-        self.max = min_max!(&self.max, delta, max);
+        self.max = max_scalar(&self.max, delta)?;
         Ok(())
     }
 
     fn evaluate(&self) -> Result<ScalarValue, ExecutorError> {
         Ok(self.max.clone())
     }
+
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError> {
+        Ok(vec![self.max.clone()])
+    }
+
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [max] if max.data_type() == self.max.data_type() => self.max = max.clone(),
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
+
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [max] if max.data_type() == self.max.data_type() => {
+                self.max = max_scalar(&self.max, max)?
+            }
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
 }
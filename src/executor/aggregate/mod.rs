@@ -1,13 +1,17 @@
 use arrow::array::ArrayRef;
 
+use self::avg::AvgAccumulator;
 use self::count::CountAccumulator;
+use self::distinct::DistinctAccumulator;
 use self::min_max::{MaxAccumulator, MinAccumulator};
 use self::sum::SumAccumulator;
 use super::ExecutorError;
 use crate::binder::{AggFunc, BoundExpr};
 use crate::types::ScalarValue;
 
+mod avg;
 mod count;
+mod distinct;
 pub mod hash_agg;
 mod hash_utils;
 mod min_max;
@@ -22,15 +26,35 @@ pub trait Accumulator: Send + Sync {
 
     /// returns its value based on its current state.
     fn evaluate(&self) -> Result<ScalarValue, ExecutorError>;
+
+    /// returns the partial state, which can be checkpointed or sent to another accumulator.
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError>;
+
+    /// restores the partial state returned by [`Accumulator::state`] into a fresh accumulator.
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError>;
+
+    /// merges the partial state of another accumulator of the same aggregate, as if the rows
+    /// accumulated by it were also accumulated by this one.
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError>;
+}
+
+fn invalid_state(state: &[ScalarValue]) -> ExecutorError {
+    ExecutorError::InternalError(format!("invalid accumulator state: {:?}", state))
 }
 
-fn create_accumulator(expr: &BoundExpr) -> Box<dyn Accumulator> {
+pub(super) fn create_accumulator(expr: &BoundExpr) -> Box<dyn Accumulator> {
     if let BoundExpr::AggFunc(agg_expr) = expr {
-        match agg_expr.func {
+        let acc: Box<dyn Accumulator> = match agg_expr.func {
             AggFunc::Count => Box::new(CountAccumulator::new()),
             AggFunc::Sum => Box::new(SumAccumulator::new(agg_expr.return_type.clone())),
             AggFunc::Min => Box::new(MinAccumulator::new(agg_expr.return_type.clone())),
             AggFunc::Max => Box::new(MaxAccumulator::new(agg_expr.return_type.clone())),
+            AggFunc::Avg => Box::new(AvgAccumulator::new()),
+        };
+        if agg_expr.distinct {
+            Box::new(DistinctAccumulator::new(acc))
+        } else {
+            acc
         }
     } else {
         unreachable!(
@@ -43,3 +67,83 @@ fn create_accumulator(expr: &BoundExpr) -> Box<dyn Accumulator> {
 fn create_accumulators(exprs: &[BoundExpr]) -> Vec<Box<dyn Accumulator>> {
     exprs.iter().map(create_accumulator).collect()
 }
+
+#[cfg(test)]
+mod accumulator_test {
+    use std::sync::Arc;
+
+    use arrow::array::Int64Array;
+    use arrow::datatypes::DataType;
+
+    use super::*;
+
+    #[test]
+    fn test_accumulator_state_round_trip() -> Result<(), ExecutorError> {
+        let new_accumulators = || -> Vec<Box<dyn Accumulator>> {
+            vec![
+                Box::new(SumAccumulator::new(DataType::Int64)),
+                Box::new(CountAccumulator::new()),
+                Box::new(MinAccumulator::new(DataType::Int64)),
+                Box::new(MaxAccumulator::new(DataType::Int64)),
+                Box::new(AvgAccumulator::new()),
+                Box::new(DistinctAccumulator::new(Box::new(CountAccumulator::new()))),
+            ]
+        };
+        let first: ArrayRef = Arc::new(Int64Array::from(vec![Some(3), None, Some(1), Some(3)]));
+        let second: ArrayRef = Arc::new(Int64Array::from(vec![Some(7), Some(1)]));
+
+        for (mut acc, mut restored) in new_accumulators().into_iter().zip(new_accumulators()) {
+            acc.update_batch(&first)?;
+            restored.from_state(&acc.state()?)?;
+            assert_eq!(restored.evaluate()?, acc.evaluate()?);
+
+            // the restored accumulator keeps accumulating from the checkpoint
+            acc.update_batch(&second)?;
+            restored.update_batch(&second)?;
+            assert_eq!(restored.evaluate()?, acc.evaluate()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_accumulator_merges_partial_states() -> Result<(), ExecutorError> {
+        let new_accumulators = || -> Vec<Box<dyn Accumulator>> {
+            vec![
+                Box::new(SumAccumulator::new(DataType::Int64)),
+                Box::new(CountAccumulator::new()),
+                Box::new(MinAccumulator::new(DataType::Int64)),
+                Box::new(MaxAccumulator::new(DataType::Int64)),
+                Box::new(AvgAccumulator::new()),
+                Box::new(DistinctAccumulator::new(Box::new(SumAccumulator::new(
+                    DataType::Int64,
+                )))),
+            ]
+        };
+        let first: ArrayRef = Arc::new(Int64Array::from(vec![Some(3), None, Some(1), Some(3)]));
+        let second: ArrayRef = Arc::new(Int64Array::from(vec![Some(7), Some(1), Some(9)]));
+        let accumulators = new_accumulators()
+            .into_iter()
+            .zip(new_accumulators())
+            .zip(new_accumulators());
+
+        for ((mut all, mut left), mut right) in accumulators {
+            all.update_batch(&first)?;
+            all.update_batch(&second)?;
+            left.update_batch(&first)?;
+            right.update_batch(&second)?;
+            left.merge_state(&right.state()?)?;
+            assert_eq!(left.evaluate()?, all.evaluate()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_accumulator_rejects_invalid_state() {
+        let mut acc = AvgAccumulator::new();
+        let state = vec![ScalarValue::Int64(Some(1))];
+        assert!(matches!(
+            acc.from_state(&state),
+            Err(ExecutorError::InternalError(_))
+        ));
+    }
+}
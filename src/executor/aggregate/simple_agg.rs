@@ -33,7 +33,7 @@ impl SimpleAggExecutor {
         #[for_await]
         for batch in self.child {
             let batch = batch?;
-            // only support one epxrssion in aggregation, not supported example: `sum(distinct a)`
+            // the argument of aggregation is evaluated into an array first, e.g. `sum(a + b)`
             let columns: Result<Vec<_>, ExecutorError> = agg_funcs
                 .iter()
                 .map(|agg| agg.exprs[0].eval_column(&batch))
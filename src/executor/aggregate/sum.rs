@@ -1,11 +1,11 @@
 // most of ideas inspired by datafusion
 
-use arrow::array::{ArrayRef, Float64Array, Int32Array, Int64Array};
+use arrow::array::{Array, ArrayRef, DecimalArray, Float64Array, Int32Array, Int64Array};
 use arrow::compute;
-use arrow::compute::kernels::cast::cast;
 use arrow::datatypes::DataType;
 
-use super::Accumulator;
+use super::{invalid_state, Accumulator};
+use crate::executor::decimal_compute::cast_array;
 use crate::executor::ExecutorError;
 use crate::types::ScalarValue;
 
@@ -48,8 +48,16 @@ impl SumAccumulator {
         values: &ArrayRef,
         sum_type: &DataType,
     ) -> Result<ScalarValue, ExecutorError> {
-        let values = cast(values, sum_type)?;
+        let values = cast_array(values, sum_type)?;
         Ok(match values.data_type() {
+            DataType::Decimal(p, s) => {
+                let array = values.as_any().downcast_ref::<DecimalArray>().unwrap();
+                let delta = (0..array.len())
+                    .filter(|i| array.is_valid(*i))
+                    .map(|i| array.value(i).as_i128())
+                    .reduce(|a, b| a + b);
+                ScalarValue::Decimal128(delta, *p as u8, *s as u8)
+            }
             DataType::Int32 => typed_sum_delta_batch!(values, Int32Array, Int32),
             DataType::Int64 => typed_sum_delta_batch!(values, Int64Array, Int64),
             DataType::Float64 => typed_sum_delta_batch!(values, Float64Array, Float64),
@@ -77,6 +85,14 @@ fn sum_result(l: &ScalarValue, r: &ScalarValue) -> ScalarValue {
         (ScalarValue::Int64(lhs), ScalarValue::Int32(rhs)) => {
             typed_sum!(lhs, rhs, Int64, i64)
         }
+        // decimals are summed in the same scale
+        (ScalarValue::Decimal128(lhs, p, s), ScalarValue::Decimal128(rhs, _, _)) => {
+            let sum = match (lhs, rhs) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(*b),
+            };
+            ScalarValue::Decimal128(sum, *p, *s)
+        }
         _ => unimplemented!("not expected {:?} and {:?} for sum", l, r),
     }
 }
@@ -91,4 +107,26 @@ impl Accumulator for SumAccumulator {
     fn evaluate(&self) -> Result<ScalarValue, ExecutorError> {
         Ok(self.result.clone())
     }
+
+    fn state(&self) -> Result<Vec<ScalarValue>, ExecutorError> {
+        Ok(vec![self.result.clone()])
+    }
+
+    fn from_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [result] if result.data_type() == self.data_type => self.result = result.clone(),
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
+
+    fn merge_state(&mut self, state: &[ScalarValue]) -> Result<(), ExecutorError> {
+        match state {
+            [result] if result.data_type() == self.data_type => {
+                self.result = sum_result(&self.result, result)
+            }
+            _ => return Err(invalid_state(state)),
+        }
+        Ok(())
+    }
 }
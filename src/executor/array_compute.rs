@@ -1,14 +1,23 @@
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, Float64Array, Int32Array, Int64Array};
+use arrow::array::{Array, ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array};
 use arrow::compute::{
-    add, divide, eq_dyn, gt_dyn, gt_eq_dyn, lt_dyn, lt_eq_dyn, multiply, subtract,
+    add, and_kleene, cast, divide, eq_dyn, gt_dyn, gt_eq_dyn, lt_dyn, lt_eq_dyn, modulus, multiply,
+    neq_dyn, or_kleene, subtract,
 };
 use arrow::datatypes::DataType;
+use arrow::error::ArrowError;
 use sqlparser::ast::BinaryOperator;
 
+use super::decimal_compute::decimal_binary_op;
+use super::interval_compute::interval_binary_op;
+use super::scalar_func::string_concat;
 use super::ExecutorError;
 
+fn unsupported_data_type(data_type: &DataType) -> ExecutorError {
+    ExecutorError::Evaluator(format!("unsupported data type {}", data_type))
+}
+
 /// Copied from datafusion binary.rs
 macro_rules! compute_op {
     // invoke binary operator
@@ -39,7 +48,67 @@ macro_rules! arithmetic_op {
             DataType::Int32 => compute_op!($LEFT, $RIGHT, $OP, Int32Array),
             DataType::Int64 => compute_op!($LEFT, $RIGHT, $OP, Int64Array),
             DataType::Float64 => compute_op!($LEFT, $RIGHT, $OP, Float64Array),
-            _ => todo!("unsupported data type"),
+            t => Err(unsupported_data_type(t)),
+        }
+    }};
+}
+
+/// Divide or modulo element-wise and propagate NULL, the divisor at NULL slots is not checked for
+/// zero, e.g. `a / NULLIF(b, 0)` whose NULL slots may still hold zero in the values buffer.
+macro_rules! null_aware_divide_op {
+    ($LEFT:expr, $RIGHT:expr, $DT:ident, $ZERO:expr, $OP:tt) => {{
+        let ll = $LEFT
+            .as_any()
+            .downcast_ref::<$DT>()
+            .expect("null_aware_divide_op failed to downcast array");
+        let rr = $RIGHT
+            .as_any()
+            .downcast_ref::<$DT>()
+            .expect("null_aware_divide_op failed to downcast array");
+        let array = ll
+            .iter()
+            .zip(rr.iter())
+            .map(|(l, r)| match (l, r) {
+                (Some(_), Some(r)) if r == $ZERO => Err(ArrowError::DivideByZero),
+                (Some(l), Some(r)) => Ok(Some(l $OP r)),
+                _ => Ok(None),
+            })
+            .collect::<Result<$DT, ArrowError>>()?;
+        Ok(Arc::new(array))
+    }};
+}
+
+fn divide_op(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    if right.null_count() == 0 {
+        return arithmetic_op!(left, right, divide);
+    }
+    match left.data_type() {
+        DataType::Int32 => null_aware_divide_op!(left, right, Int32Array, 0, /),
+        DataType::Int64 => null_aware_divide_op!(left, right, Int64Array, 0, /),
+        DataType::Float64 => null_aware_divide_op!(left, right, Float64Array, 0.0, /),
+        t => Err(unsupported_data_type(t)),
+    }
+}
+
+fn modulo_op(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    if right.null_count() == 0 {
+        return arithmetic_op!(left, right, modulus);
+    }
+    match left.data_type() {
+        DataType::Int32 => null_aware_divide_op!(left, right, Int32Array, 0, %),
+        DataType::Int64 => null_aware_divide_op!(left, right, Int64Array, 0, %),
+        DataType::Float64 => null_aware_divide_op!(left, right, Float64Array, 0.0, %),
+        t => Err(unsupported_data_type(t)),
+    }
+}
+
+/// The kleene boolean kernels follow the SQL three-valued logic, e.g. `TRUE OR NULL` is TRUE and
+/// `FALSE AND NULL` is FALSE.
+macro_rules! boolean_op {
+    ($LEFT:expr, $RIGHT:expr, $OP:ident) => {{
+        match $LEFT.data_type() {
+            DataType::Boolean => compute_op!($LEFT, $RIGHT, $OP, BooleanArray),
+            t => Err(unsupported_data_type(t)),
         }
     }};
 }
@@ -49,19 +118,80 @@ pub fn binary_op(
     right: &ArrayRef,
     op: &BinaryOperator,
 ) -> Result<ArrayRef, ExecutorError> {
+    // the binder coerces both operands to the same type, only the decimals may differ in
+    // precision and scale and a timestamp may be added with an interval, so a mismatch here
+    // comes from a malformed plan.
+    match (left.data_type(), right.data_type()) {
+        (DataType::Decimal(..), DataType::Decimal(..)) => {
+            return decimal_binary_op(left, right, op)
+        }
+        (DataType::Timestamp(..) | DataType::Interval(_), DataType::Interval(_)) => {
+            return interval_binary_op(left, right, op)
+        }
+        (l, r) if l != r => {
+            return Err(ExecutorError::Evaluator(format!(
+                "binary operator {} types mismatch: {} and {}",
+                op, l, r
+            )))
+        }
+        _ => {}
+    }
+    if let DataType::Date32 | DataType::Timestamp(..) = left.data_type() {
+        return temporal_compare_op(left, right, op);
+    }
     match op {
         BinaryOperator::Plus => arithmetic_op!(left, right, add),
         BinaryOperator::Minus => arithmetic_op!(left, right, subtract),
         BinaryOperator::Multiply => arithmetic_op!(left, right, multiply),
-        BinaryOperator::Divide => arithmetic_op!(left, right, divide),
+        BinaryOperator::Divide => divide_op(left, right),
+        BinaryOperator::Modulo => modulo_op(left, right),
         BinaryOperator::Gt => Ok(Arc::new(gt_dyn(left, right)?)),
         BinaryOperator::Lt => Ok(Arc::new(lt_dyn(left, right)?)),
         BinaryOperator::GtEq => Ok(Arc::new(gt_eq_dyn(left, right)?)),
         BinaryOperator::LtEq => Ok(Arc::new(lt_eq_dyn(left, right)?)),
         BinaryOperator::Eq => Ok(Arc::new(eq_dyn(left, right)?)),
-        BinaryOperator::NotEq => todo!(),
-        BinaryOperator::And => todo!(),
-        BinaryOperator::Or => todo!(),
-        _ => todo!(),
+        BinaryOperator::NotEq => Ok(Arc::new(neq_dyn(left, right)?)),
+        BinaryOperator::And => boolean_op!(left, right, and_kleene),
+        BinaryOperator::Or => boolean_op!(left, right, or_kleene),
+        BinaryOperator::StringConcat => string_concat(left, right),
+        _ => Err(ExecutorError::Evaluator(format!(
+            "unsupported binary operator {}",
+            op
+        ))),
+    }
+}
+
+/// The date and timestamp are compared as their underlying integers.
+fn temporal_compare_op(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    op: &BinaryOperator,
+) -> Result<ArrayRef, ExecutorError> {
+    use BinaryOperator as Op;
+    if !matches!(
+        op,
+        Op::Gt | Op::Lt | Op::GtEq | Op::LtEq | Op::Eq | Op::NotEq
+    ) {
+        return Err(ExecutorError::Evaluator(format!(
+            "unsupported temporal operator {}",
+            op
+        )));
+    }
+    let integer_type = match left.data_type() {
+        DataType::Date32 => DataType::Int32,
+        _ => DataType::Int64,
+    };
+    binary_op(
+        &cast(left, &integer_type)?,
+        &cast(right, &integer_type)?,
+        op,
+    )
+}
+
+/// Convert the NULL values of a boolean mask to false, so that NULL is treated as not matched.
+pub fn null_as_false(mask: &BooleanArray) -> BooleanArray {
+    if mask.null_count() == 0 {
+        return mask.clone();
     }
+    mask.iter().map(|v| Some(v.unwrap_or(false))).collect()
 }
@@ -0,0 +1,48 @@
+use arrow::record_batch::RecordBatch;
+use futures::TryStreamExt;
+
+use super::{BoxedExecutor, ExecutorError};
+
+/// The cursor over the output of executor, which fetches the rows incrementally and keeps the
+/// position across fetches, so that the result is never materialized as a whole.
+pub struct Cursor {
+    executor: BoxedExecutor,
+    /// the rest of the batch partially returned by the last fetch
+    pending: Option<RecordBatch>,
+}
+
+impl Cursor {
+    pub fn new(executor: BoxedExecutor) -> Self {
+        Self {
+            executor,
+            pending: None,
+        }
+    }
+
+    /// Fetch up to n rows, the batches are sliced at the boundary. Returns empty once the
+    /// executor is exhausted.
+    pub async fn fetch(&mut self, n: usize) -> Result<Vec<RecordBatch>, ExecutorError> {
+        let mut output = vec![];
+        let mut remaining = n;
+        while remaining > 0 {
+            let batch = match self.pending.take() {
+                Some(batch) => batch,
+                None => match self.executor.try_next().await? {
+                    Some(batch) => batch,
+                    None => break,
+                },
+            };
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            if batch.num_rows() > remaining {
+                self.pending = Some(batch.slice(remaining, batch.num_rows() - remaining));
+                output.push(batch.slice(0, remaining));
+                break;
+            }
+            remaining -= batch.num_rows();
+            output.push(batch);
+        }
+        Ok(output)
+    }
+}
@@ -0,0 +1,285 @@
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, DecimalArray, Float64Array, Int32Array, Int64Array, StringArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use arrow::error::ArrowError;
+use sqlparser::ast::BinaryOperator;
+
+use super::ExecutorError;
+use crate::types::{
+    decimal_arithmetic_type, div_round, format_decimal, parse_decimal, rescale_decimal,
+};
+
+fn decimal_overflow() -> ArrowError {
+    ArrowError::ComputeError("decimal overflow".to_string())
+}
+
+/// The unscaled values of decimal array and its scale.
+fn decimal_values(array: &ArrayRef) -> (Vec<Option<i128>>, u8) {
+    let array = array
+        .as_any()
+        .downcast_ref::<DecimalArray>()
+        .expect("decimal_compute failed to downcast array");
+    let values = (0..array.len())
+        .map(|i| array.is_valid(i).then(|| array.value(i).as_i128()))
+        .collect();
+    (values, array.scale() as u8)
+}
+
+fn build_decimal_array(
+    values: Vec<Option<i128>>,
+    precision: u8,
+    scale: u8,
+) -> Result<ArrayRef, ExecutorError> {
+    let array = values
+        .into_iter()
+        .collect::<DecimalArray>()
+        .with_precision_and_scale(precision as usize, scale as usize)?;
+    Ok(Arc::new(array))
+}
+
+fn decimal_type(data_type: &DataType) -> (u8, u8) {
+    match data_type {
+        DataType::Decimal(p, s) => (*p as u8, *s as u8),
+        _ => unreachable!("expected decimal type, got {}", data_type),
+    }
+}
+
+/// Evaluate the binary operator whose operands are both decimals, the binder has resolved the
+/// operand types, so only the precision and scale may differ.
+pub fn decimal_binary_op(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    op: &BinaryOperator,
+) -> Result<ArrayRef, ExecutorError> {
+    let (l_type, r_type) = (
+        decimal_type(left.data_type()),
+        decimal_type(right.data_type()),
+    );
+    let (l_values, l_scale) = decimal_values(left);
+    let (r_values, r_scale) = decimal_values(right);
+    let pairs = l_values.into_iter().zip(r_values.into_iter());
+
+    use BinaryOperator as Op;
+    if let Op::Gt | Op::Lt | Op::GtEq | Op::LtEq | Op::Eq | Op::NotEq = op {
+        // compare in the same scale
+        let scale = l_scale.max(r_scale);
+        let result = pairs
+            .map(|pair| match pair {
+                (Some(l), Some(r)) => {
+                    let l = rescale_decimal(l, l_scale, scale).ok_or_else(decimal_overflow)?;
+                    let r = rescale_decimal(r, r_scale, scale).ok_or_else(decimal_overflow)?;
+                    Ok(Some(match op {
+                        Op::Gt => l > r,
+                        Op::Lt => l < r,
+                        Op::GtEq => l >= r,
+                        Op::LtEq => l <= r,
+                        Op::Eq => l == r,
+                        _ => l != r,
+                    }))
+                }
+                _ => Ok(None),
+            })
+            .collect::<Result<BooleanArray, ArrowError>>()?;
+        return Ok(Arc::new(result));
+    }
+    if !matches!(
+        op,
+        Op::Plus | Op::Minus | Op::Multiply | Op::Divide | Op::Modulo
+    ) {
+        return Err(ExecutorError::Evaluator(format!(
+            "unsupported decimal operator: {}",
+            op
+        )));
+    }
+
+    let (precision, scale) = decimal_arithmetic_type(op, l_type, r_type);
+    let values = pairs
+        .map(|pair| {
+            let (l, r) = match pair {
+                (Some(l), Some(r)) => (l, r),
+                _ => return Ok(None),
+            };
+            let value = match op {
+                Op::Plus | Op::Minus | Op::Modulo => {
+                    let l = rescale_decimal(l, l_scale, scale);
+                    let r = rescale_decimal(r, r_scale, scale);
+                    match (l, r, op) {
+                        (Some(l), Some(r), Op::Plus) => l.checked_add(r),
+                        (Some(l), Some(r), Op::Minus) => l.checked_sub(r),
+                        (Some(_), Some(0), _) => return Err(ArrowError::DivideByZero),
+                        (Some(l), Some(r), _) => l.checked_rem(r),
+                        _ => None,
+                    }
+                }
+                Op::Multiply => l
+                    .checked_mul(r)
+                    .and_then(|v| rescale_decimal(v, l_scale + r_scale, scale)),
+                Op::Divide => {
+                    if r == 0 {
+                        return Err(ArrowError::DivideByZero);
+                    }
+                    // l / r in scale s is `l * 10^(s - s1 + s2) / r`
+                    rescale_decimal(l, l_scale, scale + r_scale).map(|l| div_round(l, r))
+                }
+                _ => unreachable!(),
+            };
+            value.map(Some).ok_or_else(decimal_overflow)
+        })
+        .collect::<Result<Vec<_>, ArrowError>>()?;
+    build_decimal_array(values, precision, scale)
+}
+
+/// Cast the array to the target type, the casts from or to decimal are handled here since they
+/// are not fully supported by arrow cast kernel.
+pub fn cast_array(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef, ExecutorError> {
+    match (array.data_type(), to_type) {
+        (from, to) if from == to => Ok(array.clone()),
+        (DataType::Decimal(..), _) => cast_from_decimal(array, to_type),
+        (_, DataType::Decimal(p, s)) => cast_to_decimal(array, *p as u8, *s as u8),
+        _ => Ok(cast(array, to_type)?),
+    }
+}
+
+fn cast_from_decimal(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef, ExecutorError> {
+    let (values, scale) = decimal_values(array);
+    let factor = 10i128.pow(scale as u32);
+    let array: ArrayRef = match to_type {
+        DataType::Decimal(p, s) => {
+            let values = values
+                .into_iter()
+                .map(|v| match v {
+                    Some(v) => rescale_decimal(v, scale, *s as u8)
+                        .map(Some)
+                        .ok_or_else(decimal_overflow),
+                    None => Ok(None),
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?;
+            return build_decimal_array(values, *p as u8, *s as u8);
+        }
+        DataType::Float64 => Arc::new(
+            values
+                .into_iter()
+                .map(|v| v.map(|v| v as f64 / factor as f64))
+                .collect::<Float64Array>(),
+        ),
+        // the fraction part is truncated
+        DataType::Int64 => Arc::new(
+            values
+                .into_iter()
+                .map(|v| v.map(|v| (v / factor) as i64))
+                .collect::<Int64Array>(),
+        ),
+        DataType::Int32 => Arc::new(
+            values
+                .into_iter()
+                .map(|v| v.map(|v| (v / factor) as i32))
+                .collect::<Int32Array>(),
+        ),
+        DataType::Utf8 => Arc::new(
+            values
+                .into_iter()
+                .map(|v| v.map(|v| format_decimal(v, scale)))
+                .collect::<StringArray>(),
+        ),
+        _ => return Ok(cast(array, to_type)?),
+    };
+    Ok(array)
+}
+
+fn cast_to_decimal(array: &ArrayRef, precision: u8, scale: u8) -> Result<ArrayRef, ExecutorError> {
+    let factor = 10i128.pow(scale as u32);
+    let values = match array.data_type() {
+        DataType::Int32 | DataType::Int64 => {
+            let array = cast(array, &DataType::Int64)?;
+            let array = array.as_any().downcast_ref::<Int64Array>().unwrap();
+            array
+                .iter()
+                .map(|v| match v {
+                    Some(v) => (v as i128)
+                        .checked_mul(factor)
+                        .map(Some)
+                        .ok_or_else(decimal_overflow),
+                    None => Ok(None),
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?
+        }
+        DataType::Float64 => {
+            let array = array.as_any().downcast_ref::<Float64Array>().unwrap();
+            array
+                .iter()
+                .map(|v| v.map(|v| (v * factor as f64).round() as i128))
+                .collect()
+        }
+        DataType::Utf8 => {
+            let array = array.as_any().downcast_ref::<StringArray>().unwrap();
+            array
+                .iter()
+                .map(|v| match v {
+                    Some(v) => {
+                        let (value, _, from_scale) = parse_decimal(v).ok_or_else(|| {
+                            ArrowError::CastError(format!("invalid decimal string: {}", v))
+                        })?;
+                        rescale_decimal(value, from_scale, scale)
+                            .map(Some)
+                            .ok_or_else(decimal_overflow)
+                    }
+                    None => Ok(None),
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?
+        }
+        DataType::Null => vec![None; array.len()],
+        from => {
+            return Err(
+                ArrowError::CastError(format!("unsupported cast from {} to decimal", from)).into(),
+            )
+        }
+    };
+    build_decimal_array(values, precision, scale)
+}
+
+#[cfg(test)]
+mod decimal_compute_test {
+    use super::*;
+
+    #[test]
+    fn test_decimal_binary_op() -> Result<(), ExecutorError> {
+        let left = build_decimal_array(vec![Some(1250), None], 4, 2)?;
+        let right = build_decimal_array(vec![Some(5), Some(10)], 2, 1)?;
+
+        let sum = decimal_binary_op(&left, &right, &BinaryOperator::Plus)?;
+        assert_eq!(sum.data_type(), &DataType::Decimal(5, 2));
+        assert_eq!(decimal_values(&sum).0, vec![Some(1300), None]);
+
+        let product = decimal_binary_op(&left, &right, &BinaryOperator::Multiply)?;
+        assert_eq!(product.data_type(), &DataType::Decimal(7, 3));
+        assert_eq!(decimal_values(&product).0, vec![Some(6250), None]);
+
+        let gt = decimal_binary_op(&left, &right, &BinaryOperator::Gt)?;
+        assert_eq!(
+            gt.as_any().downcast_ref::<BooleanArray>().unwrap(),
+            &BooleanArray::from(vec![Some(true), None])
+        );
+
+        let result = decimal_binary_op(&left, &right, &BinaryOperator::And);
+        assert!(matches!(result, Err(ExecutorError::Evaluator(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cast_decimal() -> Result<(), ExecutorError> {
+        let array: ArrayRef = Arc::new(StringArray::from(vec![Some("1.25"), None]));
+        let decimal = cast_array(&array, &DataType::Decimal(5, 1))?;
+        assert_eq!(decimal_values(&decimal).0, vec![Some(13), None]);
+
+        let float = cast_array(&decimal, &DataType::Float64)?;
+        assert_eq!(
+            float.as_any().downcast_ref::<Float64Array>().unwrap(),
+            &Float64Array::from(vec![Some(1.3), None])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+
+use super::*;
+
+/// Output a single row of no columns, which the select without FROM clause evaluates its exprs
+/// over, e.g. `select 1`.
+pub struct DummyExecutor {}
+
+impl DummyExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        let options = RecordBatchOptions {
+            row_count: Some(1),
+            ..Default::default()
+        };
+        yield RecordBatch::try_new_with_options(Arc::new(Schema::empty()), vec![], &options)?;
+    }
+}
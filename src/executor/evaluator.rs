@@ -1,10 +1,18 @@
-use arrow::array::ArrayRef;
-use arrow::compute::cast;
-use arrow::datatypes::Field;
+use std::sync::Arc;
+
+use arrow::array::{new_null_array, ArrayRef, BooleanArray, StringArray};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{
+    and_kleene, eq_dyn, gt_eq_dyn, is_not_null, is_null, like_utf8, lt_eq_dyn, nlike_utf8, not,
+    or_kleene,
+};
+use arrow::datatypes::{DataType, Field};
 use arrow::record_batch::RecordBatch;
 
 use super::*;
-use crate::binder::BoundExpr;
+use crate::binder::{
+    BoundBetween, BoundCase, BoundExpr, BoundInList, BoundLike, BoundScalarFunc, ScalarFunc,
+};
 use crate::types::build_scalar_value_array;
 
 /// Evaluate the bound expr on the given record batch.
@@ -20,8 +28,46 @@ impl BoundExpr {
             }
             BoundExpr::Constant(val) => Ok(build_scalar_value_array(val, batch.num_rows())),
             BoundExpr::ColumnRef(_) => panic!("column ref should be resolved"),
-            BoundExpr::TypeCast(tc) => Ok(cast(&tc.expr.eval_column(batch)?, &tc.cast_type)?),
+            BoundExpr::TypeCast(tc) => cast_array(&tc.expr.eval_column(batch)?, &tc.cast_type),
             BoundExpr::AggFunc(_) => todo!(),
+            BoundExpr::Case(case) => eval_case(case, batch),
+            BoundExpr::InList(in_list) => eval_in_list(in_list, batch),
+            BoundExpr::Between(between) => eval_between(between, batch),
+            BoundExpr::Like(like) => eval_like(like, batch),
+            BoundExpr::IsNull(e) => {
+                let array = e.expr.eval_column(batch)?;
+                let result = if e.negated {
+                    is_not_null(array.as_ref())?
+                } else {
+                    is_null(array.as_ref())?
+                };
+                Ok(Arc::new(result))
+            }
+            BoundExpr::Not(e) => {
+                let array = e.expr.eval_column(batch)?;
+                Ok(Arc::new(not(downcast_array::<BooleanArray>(
+                    &array, "NOT",
+                )?)?))
+            }
+            // random() has no argument to infer the row count from
+            BoundExpr::ScalarFunc(BoundScalarFunc {
+                func: ScalarFunc::Random(rng),
+                ..
+            }) => Ok(rng.next_array(batch.num_rows())),
+            BoundExpr::ScalarFunc(func) => {
+                let args: Vec<ArrayRef> = func
+                    .args
+                    .iter()
+                    .map(|arg| arg.eval_column(batch))
+                    .try_collect()?;
+                eval_scalar_func(&func.func, &args)
+            }
+            BoundExpr::Subquery(_) => Err(ExecutorError::InternalError(
+                "subquery should be resolved".to_string(),
+            )),
+            BoundExpr::WindowFunc(_) => Err(ExecutorError::InternalError(
+                "window function should be computed by window executor".to_string(),
+            )),
         }
     }
 
@@ -49,23 +95,193 @@ impl BoundExpr {
             }
             BoundExpr::AggFunc(agg) => {
                 let inner_name = agg.exprs[0].eval_field(batch).name().clone();
-                let new_name = format!("{}({})", agg.func, inner_name);
+                let distinct = if agg.distinct { "DISTINCT " } else { "" };
+                let new_name = format!("{}({}{})", agg.func, distinct, inner_name);
                 Field::new(new_name.as_str(), agg.return_type.clone(), true)
             }
+            BoundExpr::Case(case) => Field::new("case", case.return_type.clone(), true),
+            BoundExpr::InList(in_list) => {
+                let inner_name = in_list.expr.eval_field(batch).name().clone();
+                let op = if in_list.negated { "not_in" } else { "in" };
+                let new_name = format!("{}_{}_list", inner_name, op);
+                Field::new(new_name.as_str(), DataType::Boolean, true)
+            }
+            BoundExpr::Between(between) => {
+                let inner_name = between.expr.eval_field(batch).name().clone();
+                let op = if between.negated {
+                    "not_between"
+                } else {
+                    "between"
+                };
+                let new_name = format!("{}_{}", inner_name, op);
+                Field::new(new_name.as_str(), DataType::Boolean, true)
+            }
+            BoundExpr::Like(like) => {
+                let inner_name = like.expr.eval_field(batch).name().clone();
+                let op = if like.negated { "not_like" } else { "like" };
+                let new_name = format!("{}_{}", inner_name, op);
+                Field::new(new_name.as_str(), DataType::Boolean, true)
+            }
+            BoundExpr::IsNull(e) => {
+                let inner_name = e.expr.eval_field(batch).name().clone();
+                let op = if e.negated { "is_not_null" } else { "is_null" };
+                let new_name = format!("{}_{}", inner_name, op);
+                Field::new(new_name.as_str(), DataType::Boolean, false)
+            }
+            BoundExpr::Not(e) => {
+                let inner_name = e.expr.eval_field(batch).name().clone();
+                let new_name = format!("not({})", inner_name);
+                Field::new(new_name.as_str(), DataType::Boolean, true)
+            }
+            BoundExpr::ScalarFunc(func) => {
+                let arg_names = func
+                    .args
+                    .iter()
+                    .map(|arg| arg.eval_field(batch).name().clone())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let new_name = format!("{}({})", func.func, arg_names);
+                Field::new(new_name.as_str(), func.return_type.clone(), true)
+            }
+            BoundExpr::Subquery(subquery) => {
+                Field::new("subquery", subquery.return_type.clone(), true)
+            }
+            BoundExpr::WindowFunc(func) => Field::new(
+                func.func.to_string().as_str(),
+                func.return_type.clone(),
+                true,
+            ),
+        }
+    }
+}
+
+/// Downcast the evaluated array of the operand, returns an error rather than panic if the plan
+/// produces an unexpected type.
+fn downcast_array<'a, T: 'static>(
+    array: &'a ArrayRef,
+    operator: &str,
+) -> Result<&'a T, ExecutorError> {
+    array.as_any().downcast_ref::<T>().ok_or_else(|| {
+        ExecutorError::Evaluator(format!(
+            "unexpected operand type {} for {}",
+            array.data_type(),
+            operator
+        ))
+    })
+}
+
+/// Evaluate the branches from last to first, each branch overrides the rows matched by its when
+/// mask, so the first matched branch wins. The unmatched rows are filled by else or NULL.
+fn eval_case(case: &BoundCase, batch: &RecordBatch) -> Result<ArrayRef, ExecutorError> {
+    let num_rows = batch.num_rows();
+    // NULL constant is evaluated as DataType::Null array, which should be aligned to return type.
+    let align_type = |array: ArrayRef| {
+        if array.data_type() == &DataType::Null {
+            new_null_array(&case.return_type, num_rows)
+        } else {
+            array
         }
+    };
+
+    let mut result = match &case.else_expr {
+        Some(expr) => align_type(expr.eval_column(batch)?),
+        None => new_null_array(&case.return_type, num_rows),
+    };
+    for (when, then) in case.when_then.iter().rev() {
+        let mask = when.eval_column(batch)?;
+        let mask = downcast_array::<BooleanArray>(&mask, "CASE WHEN")?;
+        let then = align_type(then.eval_column(batch)?);
+        result = zip(&null_as_false(mask), then.as_ref(), result.as_ref())?;
     }
+    Ok(result)
+}
+
+/// Compare the target with each list item and combine the results by kleene OR, so that NULL
+/// target or non-matched NULL item produces NULL, following the SQL three-valued logic.
+fn eval_in_list(in_list: &BoundInList, batch: &RecordBatch) -> Result<ArrayRef, ExecutorError> {
+    let target = in_list.expr.eval_column(batch)?;
+    let mut result: Option<BooleanArray> = None;
+    for item in &in_list.list {
+        let item = item.eval_column(batch)?;
+        let item = if item.data_type() == &DataType::Null {
+            new_null_array(target.data_type(), batch.num_rows())
+        } else {
+            item
+        };
+        let matched = eq_dyn(target.as_ref(), item.as_ref())?;
+        result = Some(match result {
+            Some(prev) => or_kleene(&prev, &matched)?,
+            None => matched,
+        });
+    }
+    let result = result.expect("in list should not be empty");
+    if in_list.negated {
+        Ok(Arc::new(not(&result)?))
+    } else {
+        Ok(Arc::new(result))
+    }
+}
+
+/// The target expr is evaluated once and compared with both bounds, the results are combined by
+/// kleene AND, so that NULL operands follow the SQL three-valued logic.
+fn eval_between(between: &BoundBetween, batch: &RecordBatch) -> Result<ArrayRef, ExecutorError> {
+    let num_rows = batch.num_rows();
+    let target = between.expr.eval_column(batch)?;
+    let align_type = |array: ArrayRef| {
+        if array.data_type() == &DataType::Null {
+            new_null_array(target.data_type(), num_rows)
+        } else {
+            array
+        }
+    };
+    let low = align_type(between.low.eval_column(batch)?);
+    let high = align_type(between.high.eval_column(batch)?);
+    let ge_low = gt_eq_dyn(target.as_ref(), low.as_ref())?;
+    let le_high = lt_eq_dyn(target.as_ref(), high.as_ref())?;
+    let result = and_kleene(&ge_low, &le_high)?;
+    if between.negated {
+        Ok(Arc::new(not(&result)?))
+    } else {
+        Ok(Arc::new(result))
+    }
+}
+
+/// The pattern is matched by arrow like kernels, which escape the regex special characters and
+/// translate `%` and `_` to regex, NULL input or pattern produces NULL.
+fn eval_like(like: &BoundLike, batch: &RecordBatch) -> Result<ArrayRef, ExecutorError> {
+    let num_rows = batch.num_rows();
+    let as_string = |array: ArrayRef| {
+        if array.data_type() == &DataType::Null {
+            new_null_array(&DataType::Utf8, num_rows)
+        } else {
+            array
+        }
+    };
+    let left = as_string(like.expr.eval_column(batch)?);
+    let right = as_string(like.pattern.eval_column(batch)?);
+    let left = downcast_array::<StringArray>(&left, "LIKE")?;
+    let right = downcast_array::<StringArray>(&right, "LIKE")?;
+    let result = if like.negated {
+        nlike_utf8(left, right)?
+    } else {
+        like_utf8(left, right)?
+    };
+    Ok(Arc::new(result))
 }
 
 #[cfg(test)]
 mod evaluator_test {
+    use std::assert_matches::assert_matches;
     use std::sync::Arc;
 
-    use arrow::array::{Int32Array, Int64Array};
+    use arrow::array::{BooleanArray, Int32Array, Int64Array, StringArray};
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::record_batch::RecordBatch;
+    use sqlparser::ast::BinaryOperator;
 
-    use crate::binder::{BoundExpr, BoundInputRef, BoundTypeCast};
+    use crate::binder::{BoundBinaryOp, BoundExpr, BoundInputRef, BoundNot, BoundTypeCast};
     use crate::executor::ExecutorError;
+    use crate::types::ScalarValue;
 
     fn build_record_batch() -> RecordBatch {
         let schema = Schema::new(vec![
@@ -110,4 +326,98 @@ mod evaluator_test {
         assert_eq!(*result, Int64Array::from(vec![3, 4]));
         Ok(())
     }
+
+    #[test]
+    fn test_eval_column_for_mismatched_types_returns_error() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Utf8, false),
+            Field::new("b", DataType::Int64, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec!["1", "2"])),
+                Arc::new(Int64Array::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+        let input_ref = |index, return_type| {
+            Box::new(BoundExpr::InputRef(BoundInputRef { index, return_type }))
+        };
+        let expr = BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Eq,
+            left: input_ref(0, DataType::Utf8),
+            right: input_ref(1, DataType::Int64),
+            return_type: Some(DataType::Boolean),
+        });
+        assert_matches!(expr.eval_column(&batch), Err(ExecutorError::Evaluator(_)));
+
+        let expr = BoundExpr::Not(BoundNot {
+            expr: input_ref(1, DataType::Int64),
+        });
+        assert_matches!(expr.eval_column(&batch), Err(ExecutorError::Evaluator(_)));
+    }
+
+    fn build_nullable_boolean_batch() -> RecordBatch {
+        let schema = Schema::new(vec![Field::new("a", DataType::Boolean, true)]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(BooleanArray::from(vec![
+                Some(true),
+                Some(false),
+                None,
+            ]))],
+        )
+        .unwrap()
+    }
+
+    fn build_boolean_input_ref() -> BoundExpr {
+        BoundExpr::InputRef(BoundInputRef {
+            index: 0,
+            return_type: DataType::Boolean,
+        })
+    }
+
+    fn build_boolean_op_with_null(op: BinaryOperator) -> BoundExpr {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op,
+            left: Box::new(build_boolean_input_ref()),
+            right: Box::new(BoundExpr::Constant(ScalarValue::Boolean(None))),
+            return_type: Some(DataType::Boolean),
+        })
+    }
+
+    #[test]
+    fn test_eval_column_for_kleene_and() -> Result<(), ExecutorError> {
+        let batch = build_nullable_boolean_batch();
+        let expr = build_boolean_op_with_null(BinaryOperator::And);
+        let result = expr.eval_column(&batch)?;
+        // FALSE AND NULL is FALSE
+        assert_eq!(*result, BooleanArray::from(vec![None, Some(false), None]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_eval_column_for_kleene_or() -> Result<(), ExecutorError> {
+        let batch = build_nullable_boolean_batch();
+        let expr = build_boolean_op_with_null(BinaryOperator::Or);
+        let result = expr.eval_column(&batch)?;
+        // TRUE OR NULL is TRUE
+        assert_eq!(*result, BooleanArray::from(vec![Some(true), None, None]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_eval_column_for_not() -> Result<(), ExecutorError> {
+        let batch = build_nullable_boolean_batch();
+        let expr = BoundExpr::Not(BoundNot {
+            expr: Box::new(build_boolean_input_ref()),
+        });
+        let result = expr.eval_column(&batch)?;
+        assert_eq!(
+            *result,
+            BooleanArray::from(vec![Some(false), Some(true), None])
+        );
+        Ok(())
+    }
 }
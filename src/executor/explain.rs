@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+
+use super::*;
+
+/// Render the physical plan as an indented tree, one line per row. For EXPLAIN ANALYZE, the
+/// instrumented executor of the plan is drained first, and each node is annotated with its
+/// metrics. For EXPLAIN VERBOSE, the optimizer rules fired on the plan follow the plan tree.
+pub struct ExplainExecutor {
+    pub plan: PlanRef,
+    pub executor: Option<BoxedExecutor>,
+    pub metrics: ExecutionMetrics,
+    pub fired_rules: Vec<(String, usize)>,
+}
+
+impl ExplainExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        if let Some(executor) = self.executor {
+            #[for_await]
+            for batch in executor {
+                batch?;
+            }
+        }
+
+        let metrics = self.metrics;
+        let mut explain_result = String::new();
+        self.plan
+            .explain_with_annotation(0, &mut explain_result, &|node| {
+                metrics.get(node).map(|m| {
+                    format!(
+                        "(rows={}, time={:.3}ms)",
+                        m.rows,
+                        m.elapsed.as_secs_f64() * 1000.0
+                    )
+                })
+            });
+        for (rule, count) in self.fired_rules.iter() {
+            explain_result.push_str(&format!("Rule {} fired {} times\n", rule, count));
+        }
+        let lines = StringArray::from(explain_result.lines().collect::<Vec<_>>());
+        let schema = Arc::new(Schema::new(vec![Field::new("plan", DataType::Utf8, false)]));
+        yield RecordBatch::try_new(schema, vec![Arc::new(lines)])?;
+    }
+}
@@ -1,27 +1,96 @@
-use arrow::array::BooleanArray;
+use std::collections::HashSet;
+
+use arrow::array::{ArrayRef, BooleanArray};
 use arrow::compute::filter_record_batch;
 
+use super::subquery::resolve_subqueries;
 use super::*;
 use crate::binder::BoundExpr;
+use crate::types::ScalarValue;
 
 pub struct FilterExecutor {
     pub expr: BoundExpr,
     pub child: BoxedExecutor,
+    /// the uncorrelated subquery of `expr [NOT] IN (subquery)`, which is evaluated once as the
+    /// membership set of `expr`.
+    pub subquery: Option<BoxedExecutor>,
+    pub negated: bool,
+    /// the subqueries referenced by expr, which are executed before the child.
+    pub subqueries: Vec<BoxedExecutor>,
 }
 
 impl FilterExecutor {
     #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
     pub async fn execute(self) {
+        let membership = match self.subquery {
+            Some(subquery) => Some(build_membership(subquery).await?),
+            None => None,
+        };
+
+        let mut expr = self.expr;
+        resolve_subqueries(std::slice::from_mut(&mut expr), self.subqueries).await?;
+
         #[for_await]
         for batch in self.child {
             let batch = batch?;
-            let eval_mask = self.expr.eval_column(&batch)?;
-            let predicate = eval_mask
-                .as_any()
-                .downcast_ref::<BooleanArray>()
-                .expect("filter executor expected evaluate boolean array");
-            let batch = filter_record_batch(&batch, predicate)?;
+            let eval_mask = expr.eval_column(&batch)?;
+            let predicate = match &membership {
+                Some(membership) => membership.contains(&eval_mask, self.negated),
+                None => eval_mask
+                    .as_any()
+                    .downcast_ref::<BooleanArray>()
+                    .expect("filter executor expected evaluate boolean array")
+                    .clone(),
+            };
+            // NULL predicate result is treated as not passing
+            let batch = filter_record_batch(&batch, &null_as_false(&predicate))?;
             yield batch;
         }
     }
 }
+
+/// The distinct values of the single column subquery result.
+struct Membership {
+    values: HashSet<ScalarValue>,
+    has_null: bool,
+}
+
+impl Membership {
+    /// Follow the SQL semantic: NULL probe, or a missed probe when the set contains NULL, is
+    /// unknown rather than false.
+    fn contains(&self, array: &ArrayRef, negated: bool) -> BooleanArray {
+        (0..array.len())
+            .map(|i| {
+                let value = ScalarValue::try_from_array(array, i);
+                if value.is_null() {
+                    None
+                } else if self.values.contains(&value) {
+                    Some(!negated)
+                } else if self.has_null {
+                    None
+                } else {
+                    Some(negated)
+                }
+            })
+            .collect()
+    }
+}
+
+async fn build_membership(subquery: BoxedExecutor) -> Result<Membership, ExecutorError> {
+    let mut membership = Membership {
+        values: HashSet::new(),
+        has_null: false,
+    };
+    for batch in try_collect(subquery).await? {
+        let column = batch.column(0);
+        for i in 0..batch.num_rows() {
+            let value = ScalarValue::try_from_array(column, i);
+            if value.is_null() {
+                membership.has_null = true;
+            } else {
+                membership.values.insert(value);
+            }
+        }
+    }
+    Ok(membership)
+}
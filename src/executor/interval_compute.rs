@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, IntervalMonthDayNanoArray, TimestampMicrosecondArray,
+};
+use arrow::datatypes::DataType;
+use arrow::error::ArrowError;
+use sqlparser::ast::BinaryOperator;
+
+use super::ExecutorError;
+use crate::types::{add_interval_to_timestamp, IntervalMonthDayNano};
+
+fn interval_overflow() -> ArrowError {
+    ArrowError::ComputeError("interval overflow".to_string())
+}
+
+fn unsupported_operator(op: &BinaryOperator) -> ExecutorError {
+    ExecutorError::Evaluator(format!("unsupported interval operator {}", op))
+}
+
+fn interval_values(array: &ArrayRef) -> Vec<Option<IntervalMonthDayNano>> {
+    let array = array
+        .as_any()
+        .downcast_ref::<IntervalMonthDayNanoArray>()
+        .expect("interval_compute failed to downcast array");
+    array
+        .iter()
+        .map(|v| v.map(IntervalMonthDayNano::from_i128))
+        .collect()
+}
+
+/// Evaluate the binary operator whose right operand is an interval, which is `timestamp ±
+/// interval`, `interval ± interval` or the comparison between intervals.
+pub fn interval_binary_op(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    op: &BinaryOperator,
+) -> Result<ArrayRef, ExecutorError> {
+    use BinaryOperator as Op;
+    let r_values = interval_values(right);
+    // subtraction is the addition of the negated interval
+    let rhs = |r: IntervalMonthDayNano| match op {
+        Op::Minus => r.checked_neg().ok_or_else(interval_overflow),
+        _ => Ok(r),
+    };
+
+    match (left.data_type(), op) {
+        (DataType::Timestamp(..), Op::Plus | Op::Minus) => {
+            let l_values = left
+                .as_any()
+                .downcast_ref::<TimestampMicrosecondArray>()
+                .expect("interval_compute failed to downcast array");
+            let result = l_values
+                .iter()
+                .zip(r_values.into_iter())
+                .map(|pair| match pair {
+                    (Some(l), Some(r)) => add_interval_to_timestamp(l, rhs(r)?)
+                        .map(Some)
+                        .ok_or_else(interval_overflow),
+                    _ => Ok(None),
+                })
+                .collect::<Result<TimestampMicrosecondArray, ArrowError>>()?;
+            Ok(Arc::new(result))
+        }
+        (DataType::Interval(_), Op::Plus | Op::Minus) => {
+            let result = interval_values(left)
+                .into_iter()
+                .zip(r_values.into_iter())
+                .map(|pair| match pair {
+                    (Some(l), Some(r)) => l
+                        .checked_add(rhs(r)?)
+                        .map(|v| Some(v.to_i128()))
+                        .ok_or_else(interval_overflow),
+                    _ => Ok(None),
+                })
+                .collect::<Result<IntervalMonthDayNanoArray, ArrowError>>()?;
+            Ok(Arc::new(result))
+        }
+        (DataType::Interval(_), Op::Gt | Op::Lt | Op::GtEq | Op::LtEq | Op::Eq | Op::NotEq) => {
+            let result = interval_values(left)
+                .into_iter()
+                .zip(r_values.into_iter())
+                .map(|pair| match pair {
+                    (Some(l), Some(r)) => Some(match op {
+                        Op::Gt => l > r,
+                        Op::Lt => l < r,
+                        Op::GtEq => l >= r,
+                        Op::LtEq => l <= r,
+                        Op::Eq => l == r,
+                        _ => l != r,
+                    }),
+                    _ => None,
+                })
+                .collect::<BooleanArray>();
+            Ok(Arc::new(result))
+        }
+        _ => Err(unsupported_operator(op)),
+    }
+}
@@ -0,0 +1,61 @@
+use arrow::array::UInt32Array;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+
+use super::{join_batch, join_schema};
+use crate::executor::*;
+
+/// Output the Cartesian product of left and right rows. The right input is collected in memory,
+/// then each left batch is joined with every right batch.
+pub struct CrossJoinExecutor {
+    pub left: BoxedExecutor,
+    pub right: BoxedExecutor,
+}
+
+impl CrossJoinExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        let right_batches = try_collect(self.right).await?;
+        if right_batches.is_empty() {
+            return Ok(());
+        }
+        let mut schema: Option<SchemaRef> = None;
+
+        #[for_await]
+        for left_batch in self.left {
+            let left_batch = left_batch?;
+            let schema = schema
+                .get_or_insert_with(|| join_schema(&left_batch, &right_batches[0]))
+                .clone();
+            for right_batch in &right_batches {
+                if let Some(batch) = cross_join_batch(schema.clone(), &left_batch, right_batch)? {
+                    yield batch;
+                }
+            }
+        }
+    }
+}
+
+/// Repeat each left row for every right row, and the whole right batch for every left row.
+fn cross_join_batch(
+    schema: SchemaRef,
+    left: &RecordBatch,
+    right: &RecordBatch,
+) -> Result<Option<RecordBatch>, ExecutorError> {
+    let (left_rows, right_rows) = (left.num_rows(), right.num_rows());
+    if left_rows == 0 || right_rows == 0 {
+        return Ok(None);
+    }
+    let left_indices = UInt32Array::from_iter_values(
+        (0..left_rows as u32).flat_map(|i| std::iter::repeat(i).take(right_rows)),
+    );
+    let right_indices =
+        UInt32Array::from_iter_values((0..left_rows).flat_map(|_| 0..right_rows as u32));
+    Ok(Some(join_batch(
+        schema,
+        left,
+        &left_indices,
+        right,
+        &right_indices,
+    )?))
+}
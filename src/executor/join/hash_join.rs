@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use arrow::array::{ArrayRef, UInt32Array};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+
+use super::{join_batch, join_schema};
+use crate::binder::BoundExpr;
+use crate::executor::*;
+use crate::types::ScalarValue;
+
+/// Inner join the rows with equal keys. The hash table is built on the right rows, then probed by
+/// each left batch. The rows with any NULL key never match.
+///
+/// The build side is consumed only once the output is first polled, and the left side is not read
+/// until the build completes.
+pub struct HashJoinExecutor {
+    pub left: BoxedExecutor,
+    pub right: BoxedExecutor,
+    /// the equi-join keys, which are evaluated on the left and right batch respectively
+    pub on: Vec<(BoundExpr, BoundExpr)>,
+    /// the max bytes of the buffered build side, exceeding it fails the query
+    pub memory_limit: Option<usize>,
+}
+
+impl HashJoinExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        let (left_keys, right_keys): (Vec<_>, Vec<_>) = self.on.into_iter().unzip();
+
+        // build, the keys of each batch are hashed as it arrives
+        let mut build = BuildSide::default();
+        #[for_await]
+        for batch in self.right {
+            let batch = batch?;
+            build.push(&right_keys, batch)?;
+            if let Some(limit) = self.memory_limit {
+                if build.memory_usage > limit {
+                    return Err(ExecutorError::ResourcesExhausted(format!(
+                        "hash join build side uses {} bytes, exceeding the limit {}",
+                        build.memory_usage, limit
+                    )));
+                }
+            }
+        }
+        let (right, hash_table) = match build.finish()? {
+            Some(built) => built,
+            None => return Ok(()),
+        };
+        let mut schema: Option<SchemaRef> = None;
+
+        // probe
+        #[for_await]
+        for left_batch in self.left {
+            let left_batch = left_batch?;
+            let schema = schema
+                .get_or_insert_with(|| join_schema(&left_batch, &right))
+                .clone();
+            let keys = eval_keys(&left_keys, &left_batch)?;
+            let mut left_indices = vec![];
+            let mut right_indices = vec![];
+            for row in 0..left_batch.num_rows() {
+                let matched = row_key(&keys, row).and_then(|key| hash_table.get(&key));
+                for right_row in matched.into_iter().flatten() {
+                    left_indices.push(row as u32);
+                    right_indices.push(*right_row);
+                }
+            }
+            if left_indices.is_empty() {
+                continue;
+            }
+            yield join_batch(
+                schema,
+                &left_batch,
+                &UInt32Array::from(left_indices),
+                &right,
+                &UInt32Array::from(right_indices),
+            )?;
+        }
+    }
+}
+
+/// The buffered batches of build side and the hash table of their rows, the rows are numbered
+/// across batches in the order of arrival.
+#[derive(Default)]
+struct BuildSide {
+    batches: Vec<RecordBatch>,
+    hash_table: HashMap<Vec<ScalarValue>, Vec<u32>>,
+    num_rows: usize,
+    /// the bytes of the buffered batches
+    memory_usage: usize,
+}
+
+impl BuildSide {
+    fn push(&mut self, keys: &[BoundExpr], batch: RecordBatch) -> Result<(), ExecutorError> {
+        let key_arrays = eval_keys(keys, &batch)?;
+        for row in 0..batch.num_rows() {
+            if let Some(key) = row_key(&key_arrays, row) {
+                let row = (self.num_rows + row) as u32;
+                self.hash_table.entry(key).or_default().push(row);
+            }
+        }
+        self.num_rows += batch.num_rows();
+        self.memory_usage += batch
+            .columns()
+            .iter()
+            .map(|array| array.get_array_memory_size())
+            .sum::<usize>();
+        self.batches.push(batch);
+        Ok(())
+    }
+
+    /// The build side as one batch along with the hash table, None if there is no batch.
+    fn finish(
+        self,
+    ) -> Result<Option<(RecordBatch, HashMap<Vec<ScalarValue>, Vec<u32>>)>, ExecutorError> {
+        if self.batches.is_empty() {
+            return Ok(None);
+        }
+        let batch = RecordBatch::concat(&self.batches[0].schema(), &self.batches)?;
+        Ok(Some((batch, self.hash_table)))
+    }
+}
+
+fn eval_keys(keys: &[BoundExpr], batch: &RecordBatch) -> Result<Vec<ArrayRef>, ExecutorError> {
+    keys.iter().map(|key| key.eval_column(batch)).try_collect()
+}
+
+/// the key of row, or None if any of the key is NULL
+fn row_key(keys: &[ArrayRef], row: usize) -> Option<Vec<ScalarValue>> {
+    let key = keys
+        .iter()
+        .map(|array| ScalarValue::try_from_array(array, row))
+        .collect::<Vec<_>>();
+    (!key.iter().any(|v| v.is_null())).then(|| key)
+}
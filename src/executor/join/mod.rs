@@ -0,0 +1,42 @@
+pub mod cross_join;
+pub mod hash_join;
+
+use std::sync::Arc;
+
+use arrow::array::UInt32Array;
+use arrow::compute;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+
+use super::ExecutorError;
+
+/// the left fields followed by the right fields
+fn join_schema(left: &RecordBatch, right: &RecordBatch) -> SchemaRef {
+    let fields = left
+        .schema()
+        .fields()
+        .iter()
+        .chain(right.schema().fields().iter())
+        .cloned()
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+/// Output the joined rows, the i-th row is the `left_indices[i]` left row followed by the
+/// `right_indices[i]` right row.
+fn join_batch(
+    schema: SchemaRef,
+    left: &RecordBatch,
+    left_indices: &UInt32Array,
+    right: &RecordBatch,
+    right_indices: &UInt32Array,
+) -> Result<RecordBatch, ExecutorError> {
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for column in left.columns() {
+        columns.push(compute::take(column.as_ref(), left_indices, None)?);
+    }
+    for column in right.columns() {
+        columns.push(compute::take(column.as_ref(), right_indices, None)?);
+    }
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
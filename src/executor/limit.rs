@@ -0,0 +1,87 @@
+use arrow::array::ArrayRef;
+use arrow::record_batch::RecordBatch;
+
+use super::*;
+use crate::binder::BoundExpr;
+use crate::types::ScalarValue;
+
+pub struct LimitExecutor {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    /// the ORDER BY keys of `WITH TIES`, the rows after limit are output while they tie with the
+    /// last row on the keys. Empty if without ties.
+    pub tie_keys: Vec<BoundExpr>,
+    pub child: BoxedExecutor,
+}
+
+impl LimitExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        let mut skip = self.offset.unwrap_or(0);
+        let mut remaining = self.limit.unwrap_or(usize::MAX);
+        if remaining == 0 {
+            return Ok(());
+        }
+        // the key of the last row once the limit is reached with ties
+        let mut last_key: Option<Vec<ScalarValue>> = None;
+
+        #[for_await]
+        for batch in self.child {
+            let batch = batch?;
+            let num_rows = batch.num_rows();
+            if let Some(key) = &last_key {
+                let keys = self.eval_tie_keys(&batch)?;
+                let ties = (0..num_rows)
+                    .take_while(|&i| row_key(&keys, i) == *key)
+                    .count();
+                if ties > 0 {
+                    yield batch.slice(0, ties);
+                }
+                if ties < num_rows {
+                    break;
+                }
+                continue;
+            }
+            // skip the whole batch if it is before offset
+            if skip >= num_rows {
+                skip -= num_rows;
+                continue;
+            }
+            let start = skip;
+            let mut len = (num_rows - skip).min(remaining);
+            skip = 0;
+            remaining -= len;
+            if remaining == 0 && !self.tie_keys.is_empty() {
+                let keys = self.eval_tie_keys(&batch)?;
+                let key = row_key(&keys, start + len - 1);
+                len += (start + len..num_rows)
+                    .take_while(|&i| row_key(&keys, i) == key)
+                    .count();
+                yield batch.slice(start, len);
+                // the ties may continue in the next batch
+                if start + len < num_rows {
+                    break;
+                }
+                last_key = Some(key);
+                continue;
+            }
+            yield batch.slice(start, len);
+            if remaining == 0 {
+                break;
+            }
+        }
+    }
+
+    fn eval_tie_keys(&self, batch: &RecordBatch) -> Result<Vec<ArrayRef>, ExecutorError> {
+        self.tie_keys
+            .iter()
+            .map(|expr| expr.eval_column(batch))
+            .try_collect()
+    }
+}
+
+pub(super) fn row_key(keys: &[ArrayRef], row: usize) -> Vec<ScalarValue> {
+    keys.iter()
+        .map(|array| ScalarValue::try_from_array(array, row))
+        .collect()
+}
@@ -0,0 +1,20 @@
+use arrow::record_batch::RecordBatch;
+use futures::stream::select_all;
+
+use super::*;
+
+/// Interleave the batches of inputs in the order they become ready, e.g. the partitioned streams
+/// of parallel scan. The first error stops the whole stream.
+pub struct MergeExecutor {
+    pub inputs: Vec<BoxedExecutor>,
+}
+
+impl MergeExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        #[for_await]
+        for batch in select_all(self.inputs) {
+            yield batch?;
+        }
+    }
+}
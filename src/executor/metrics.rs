@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::*;
+use crate::optimizer::PlanNode;
+
+/// The metrics of one executor, the elapsed time includes the time spent in its children.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OperatorMetrics {
+    pub rows: usize,
+    pub elapsed: Duration,
+}
+
+/// The metrics of all instrumented executors, keyed by the address of their plan nodes.
+#[derive(Default, Clone)]
+pub struct ExecutionMetrics {
+    operators: Arc<Mutex<HashMap<usize, OperatorMetrics>>>,
+}
+
+fn node_id(plan: &dyn PlanNode) -> usize {
+    plan as *const dyn PlanNode as *const () as usize
+}
+
+impl ExecutionMetrics {
+    pub fn get(&self, plan: &dyn PlanNode) -> Option<OperatorMetrics> {
+        self.operators.lock().unwrap().get(&node_id(plan)).copied()
+    }
+
+    /// Wrap the executor of plan node to record its output rows and elapsed time.
+    pub fn instrument(&self, plan: &PlanRef, executor: BoxedExecutor) -> BoxedExecutor {
+        let id = node_id(plan.as_ref());
+        self.operators
+            .lock()
+            .unwrap()
+            .insert(id, Default::default());
+        MetricsExecutor {
+            id,
+            metrics: self.clone(),
+            child: executor,
+        }
+        .execute()
+    }
+}
+
+struct MetricsExecutor {
+    id: usize,
+    metrics: ExecutionMetrics,
+    child: BoxedExecutor,
+}
+
+impl MetricsExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    async fn execute(self) {
+        let mut child = self.child;
+        loop {
+            let start = Instant::now();
+            let batch = child.try_next().await?;
+            let elapsed = start.elapsed();
+
+            {
+                let mut operators = self.metrics.operators.lock().unwrap();
+                let metrics = operators.get_mut(&self.id).unwrap();
+                metrics.elapsed += elapsed;
+                metrics.rows += batch.as_ref().map_or(0, |batch| batch.num_rows());
+            }
+            match batch {
+                Some(batch) => yield batch,
+                None => break,
+            }
+        }
+    }
+}
@@ -1,25 +1,59 @@
 mod aggregate;
 mod array_compute;
+mod cursor;
+mod decimal_compute;
+mod dummy;
 mod evaluator;
+mod explain;
 mod filter;
+mod interval_compute;
+mod join;
+mod limit;
+mod merge;
+mod metrics;
+mod order;
 mod project;
+mod rebatch;
+mod scalar_cmp;
+mod scalar_func;
+mod subquery;
+mod table_function;
 mod table_scan;
+mod union;
+mod window;
 
 use array_compute::*;
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
+use decimal_compute::*;
 use futures::stream::BoxStream;
 use futures::TryStreamExt;
 use futures_async_stream::try_stream;
+use scalar_func::*;
 
 use self::aggregate::hash_agg::HashAggExecutor;
 use self::aggregate::simple_agg::SimpleAggExecutor;
+pub use self::cursor::Cursor;
+use self::dummy::DummyExecutor;
+use self::explain::ExplainExecutor;
 use self::filter::FilterExecutor;
+use self::join::cross_join::CrossJoinExecutor;
+use self::join::hash_join::HashJoinExecutor;
+use self::limit::LimitExecutor;
+pub use self::merge::MergeExecutor;
+pub use self::metrics::*;
+use self::order::OrderExecutor;
 use self::project::ProjectExecutor;
+use self::rebatch::RebatchExecutor;
+pub use self::rebatch::DEFAULT_BATCH_SIZE;
+use self::table_function::TableFunctionExecutor;
 use self::table_scan::TableScanExecutor;
+use self::union::UnionExecutor;
+use self::window::WindowExecutor;
 use crate::optimizer::{
-    PhysicalFilter, PhysicalProject, PhysicalSimpleAgg, PhysicalTableScan, PlanRef, PlanTreeNode,
-    PlanVisitor,
+    Dummy, PhysicalCrossJoin, PhysicalExplain, PhysicalFilter, PhysicalHashJoin, PhysicalLimit,
+    PhysicalOrder, PhysicalProject, PhysicalSimpleAgg, PhysicalTableScan, PhysicalUnion,
+    PhysicalWindow, PlanRef, PlanTreeNode, PlanVisitor, TableFunctionScan,
 };
 use crate::storage::{StorageError, StorageImpl};
 
@@ -27,15 +61,71 @@ pub type BoxedExecutor = BoxStream<'static, Result<RecordBatch, ExecutorError>>;
 
 pub struct ExecutorBuilder {
     storage: StorageImpl,
+    /// collect the metrics of every executor if set, used by EXPLAIN ANALYZE
+    metrics: Option<ExecutionMetrics>,
+    /// the target number of rows in the output batches of scan and filter
+    batch_size: usize,
+    /// the number of tasks to read the partitions of a table concurrently
+    scan_parallelism: usize,
+    /// the max bytes buffered by the build side of hash join, which fails when exceeding it
+    memory_limit: Option<usize>,
+    /// the max bytes of the groups of hash agg in memory, which are spilled to disk when
+    /// exceeding it
+    agg_spill_threshold: Option<usize>,
 }
 
 impl ExecutorBuilder {
     pub fn new(storage: StorageImpl) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            metrics: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            scan_parallelism: 1,
+            memory_limit: None,
+            agg_spill_threshold: None,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch size should be positive");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Scan the partitions of table in parallel, the order of rows is not preserved then.
+    pub fn with_scan_parallelism(mut self, parallelism: usize) -> Self {
+        assert!(parallelism > 0, "scan parallelism should be positive");
+        self.scan_parallelism = parallelism;
+        self
+    }
+
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Spill the hash aggregates to disk once their groups exceed the threshold bytes. The
+    /// DISTINCT aggregates can't be spilled, they fail with `ResourcesExhausted` instead.
+    pub fn with_agg_spill_threshold(mut self, threshold: usize) -> Self {
+        self.agg_spill_threshold = Some(threshold);
+        self
+    }
+
+    /// Coalesce or split the output of executor to batches of the target size.
+    fn rebatch(&self, executor: BoxedExecutor) -> BoxedExecutor {
+        RebatchExecutor {
+            batch_size: self.batch_size,
+            child: executor,
+        }
+        .execute()
     }
 
     pub fn build(&mut self, plan: PlanRef) -> BoxedExecutor {
-        self.visit(plan).unwrap()
+        let executor = self.visit(plan.clone()).unwrap();
+        match &self.metrics {
+            Some(metrics) => metrics.instrument(&plan, executor),
+            None => executor,
+        }
     }
 
     #[allow(dead_code)]
@@ -74,55 +164,87 @@ pub enum ExecutorError {
     ),
     #[error("Internal error: {0}")]
     InternalError(String),
+    #[error("evaluator error: {0}")]
+    Evaluator(String),
+    #[error("resources exhausted: {0}")]
+    ResourcesExhausted(String),
 }
 
 impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
+    fn visit_dummy(&mut self, _plan: &Dummy) -> Option<BoxedExecutor> {
+        Some(DummyExecutor {}.execute())
+    }
+
+    fn visit_table_function_scan(&mut self, plan: &TableFunctionScan) -> Option<BoxedExecutor> {
+        Some(TableFunctionExecutor { plan: plan.clone() }.execute())
+    }
+
     fn visit_physical_table_scan(&mut self, plan: &PhysicalTableScan) -> Option<BoxedExecutor> {
-        Some(match &self.storage {
+        let executor = match &self.storage {
             StorageImpl::CsvStorage(storage) => TableScanExecutor {
                 plan: plan.clone(),
                 storage: storage.clone(),
+                parallelism: self.scan_parallelism,
             }
             .execute(),
             StorageImpl::InMemoryStorage(storage) => TableScanExecutor {
                 plan: plan.clone(),
                 storage: storage.clone(),
+                parallelism: self.scan_parallelism,
             }
             .execute(),
-        })
+            StorageImpl::JsonStorage(storage) => TableScanExecutor {
+                plan: plan.clone(),
+                storage: storage.clone(),
+                parallelism: self.scan_parallelism,
+            }
+            .execute(),
+        };
+        Some(self.rebatch(executor))
     }
 
     fn visit_physical_project(&mut self, plan: &PhysicalProject) -> Option<BoxedExecutor> {
         Some(
             ProjectExecutor {
                 exprs: plan.logical().exprs(),
-                child: self
-                    .visit(plan.children().first().unwrap().clone())
-                    .unwrap(),
+                aliases: plan.logical().aliases(),
+                child: self.build(plan.logical().input()),
+                subqueries: plan
+                    .logical()
+                    .subqueries()
+                    .into_iter()
+                    .map(|subquery| self.build(subquery))
+                    .collect(),
             }
             .execute(),
         )
     }
 
     fn visit_physical_filter(&mut self, plan: &PhysicalFilter) -> Option<BoxedExecutor> {
-        Some(
-            FilterExecutor {
-                expr: plan.logical().expr(),
-                child: self
-                    .visit(plan.children().first().unwrap().clone())
-                    .unwrap(),
-            }
-            .execute(),
-        )
+        let executor = FilterExecutor {
+            expr: plan.logical().expr(),
+            child: self.build(plan.logical().input()),
+            subquery: plan
+                .logical()
+                .subquery()
+                .map(|subquery| self.build(subquery)),
+            negated: plan.logical().negated(),
+            subqueries: plan
+                .logical()
+                .subqueries()
+                .into_iter()
+                .map(|subquery| self.build(subquery))
+                .collect(),
+        }
+        .execute();
+        Some(self.rebatch(executor))
     }
 
     fn visit_physical_simple_agg(&mut self, plan: &PhysicalSimpleAgg) -> Option<BoxedExecutor> {
         Some(
             SimpleAggExecutor {
                 agg_funcs: plan.logical().agg_funcs(),
-                child: self
-                    .visit(plan.children().first().unwrap().clone())
-                    .unwrap(),
+                child: self.build(plan.children().first().unwrap().clone()),
             }
             .execute(),
         )
@@ -136,9 +258,100 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
             HashAggExecutor {
                 agg_funcs: plan.logical().agg_funcs(),
                 group_by: plan.logical().group_by(),
-                child: self
-                    .visit(plan.children().first().unwrap().clone())
-                    .unwrap(),
+                child: self.build(plan.children().first().unwrap().clone()),
+                spill_threshold: self.agg_spill_threshold,
+            }
+            .execute(),
+        )
+    }
+
+    fn visit_physical_order(&mut self, plan: &PhysicalOrder) -> Option<BoxedExecutor> {
+        Some(
+            OrderExecutor {
+                order_by: plan.logical().order_by(),
+                child: self.build(plan.children().first().unwrap().clone()),
+            }
+            .execute(),
+        )
+    }
+
+    fn visit_physical_window(&mut self, plan: &PhysicalWindow) -> Option<BoxedExecutor> {
+        Some(
+            WindowExecutor {
+                window_funcs: plan.logical().window_funcs(),
+                child: self.build(plan.logical().input()),
+            }
+            .execute(),
+        )
+    }
+
+    fn visit_physical_limit(&mut self, plan: &PhysicalLimit) -> Option<BoxedExecutor> {
+        Some(
+            LimitExecutor {
+                limit: plan.logical().limit(),
+                offset: plan.logical().offset(),
+                tie_keys: plan.logical().tie_keys(),
+                child: self.build(plan.children().first().unwrap().clone()),
+            }
+            .execute(),
+        )
+    }
+
+    fn visit_physical_cross_join(&mut self, plan: &PhysicalCrossJoin) -> Option<BoxedExecutor> {
+        let executor = CrossJoinExecutor {
+            left: self.build(plan.logical().left()),
+            right: self.build(plan.logical().right()),
+        }
+        .execute();
+        Some(self.rebatch(executor))
+    }
+
+    fn visit_physical_hash_join(&mut self, plan: &PhysicalHashJoin) -> Option<BoxedExecutor> {
+        let executor = HashJoinExecutor {
+            left: self.build(plan.logical().left()),
+            right: self.build(plan.logical().right()),
+            on: plan.logical().on(),
+            memory_limit: self.memory_limit,
+        }
+        .execute();
+        Some(self.rebatch(executor))
+    }
+
+    fn visit_physical_explain(&mut self, plan: &PhysicalExplain) -> Option<BoxedExecutor> {
+        let child = plan.children().first().unwrap().clone();
+        let metrics = ExecutionMetrics::default();
+        // EXPLAIN ANALYZE executes the child plan with the executors instrumented
+        let executor = plan.logical().analyze().then(|| {
+            ExecutorBuilder {
+                storage: self.storage.clone(),
+                metrics: Some(metrics.clone()),
+                batch_size: self.batch_size,
+                scan_parallelism: self.scan_parallelism,
+                memory_limit: self.memory_limit,
+                agg_spill_threshold: self.agg_spill_threshold,
+            }
+            .build(child.clone())
+        });
+        Some(
+            ExplainExecutor {
+                plan: child,
+                executor,
+                metrics,
+                fired_rules: plan.logical().fired_rules().to_vec(),
+            }
+            .execute(),
+        )
+    }
+
+    fn visit_physical_union(&mut self, plan: &PhysicalUnion) -> Option<BoxedExecutor> {
+        Some(
+            UnionExecutor {
+                types: plan.logical().types(),
+                inputs: plan
+                    .children()
+                    .into_iter()
+                    .map(|child| self.build(child))
+                    .collect(),
             }
             .execute(),
         )
@@ -147,22 +360,42 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
 
 #[cfg(test)]
 mod executor_test {
-    use std::sync::Arc;
+    use std::assert_matches::assert_matches;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, UNIX_EPOCH};
 
     use anyhow::Result;
-    use arrow::array::{Int64Array, StringArray};
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::array::{
+        Date32Array, DecimalArray, Float64Array, Int64Array, ListArray, StringArray,
+        TimestampMicrosecondArray,
+    };
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
     use arrow::record_batch::RecordBatch;
     use arrow::util::pretty::pretty_format_batches;
+    use futures::{stream, StreamExt};
 
-    use super::BoxedExecutor;
-    use crate::binder::Binder;
+    use super::{
+        BoxedExecutor, Cursor, ExecutorError, HashAggExecutor, HashJoinExecutor, LimitExecutor,
+        MergeExecutor, TableScanExecutor,
+    };
+    use crate::binder::{AggFunc, Binder, BoundAggFunc, BoundColumnRef, BoundExpr, BoundInputRef};
+    use crate::catalog::RootCatalog;
     use crate::executor::{try_collect, ExecutorBuilder};
-    use crate::optimizer::{InputRefRewriter, PhysicalRewriter, PlanRewriter};
+    use crate::optimizer::{
+        count_operators, Batch, BatchStrategy, InputRefRewriter, LogicalAgg, LogicalProject,
+        LogicalTableScan, LogicalUnion, Optimizer, PhysicalRewriter, PhysicalTableScan,
+        PlanNodeType, PlanRef, PlanRewriter, PlanTreeNode, PushAggThroughUnionRewriter,
+        PushLimitThroughProjectRewriter, RemoveRedundantProjectRewriter,
+    };
     use crate::parser::parse;
     use crate::planner::Planner;
-    use crate::storage::{InMemoryStorage, Storage, StorageError, StorageImpl};
-    use crate::util::pretty_batches;
+    use crate::storage::{
+        CsvStorage, CsvTable, CsvTransaction, InMemoryStorage, JsonStorage, Storage, StorageError,
+        StorageImpl, Table, TableStats, Transaction,
+    };
+    use crate::types::{parse_date, parse_timestamp, Collation, ScalarValue, SessionRng};
+    use crate::util::{pretty_batches, TempFile};
 
     fn build_record_batch() -> Result<Vec<RecordBatch>, StorageError> {
         let schema = Arc::new(Schema::new(vec![
@@ -187,21 +420,37 @@ mod executor_test {
     }
 
     fn build_executor(storage: InMemoryStorage, sql: &str) -> Result<BoxedExecutor> {
+        build_executor_with_rng(storage, sql, SessionRng::default())
+    }
+
+    fn build_executor_with_rng(
+        storage: InMemoryStorage,
+        sql: &str,
+        rng: SessionRng,
+    ) -> Result<BoxedExecutor> {
+        build_executor_with_binder(storage, sql, |binder| binder.with_rng(rng))
+    }
+
+    fn build_executor_with_binder(
+        storage: InMemoryStorage,
+        sql: &str,
+        config_binder: impl FnOnce(Binder) -> Binder,
+    ) -> Result<BoxedExecutor> {
         // parse sql to AST
         let stmts = parse(sql).unwrap();
 
         // bind AST to bound stmts
         let catalog = storage.get_catalog();
-        let mut binder = Binder::new(Arc::new(catalog));
+        let mut binder = config_binder(Binder::new(Arc::new(catalog)));
         let bound_stmt = binder.bind(&stmts[0]).unwrap();
         println!("bound_stmt = {:#?}", bound_stmt);
 
         // convert bound stmts to logical plan
-        let planner = Planner {};
+        let planner = Planner::default();
         let logical_plan = planner.plan(bound_stmt)?;
         println!("logical_plan = {:#?}", logical_plan);
-        let mut input_ref_rewriter = InputRefRewriter::default();
-        let new_logical_plan = input_ref_rewriter.rewrite(logical_plan);
+        // optimize by the same rules as `Database::run`
+        let new_logical_plan = planner.optimize(logical_plan, storage.table_cardinalities());
         println!("new_logical_plan = {:#?}", new_logical_plan);
 
         // rewrite logical plan to physical plan
@@ -294,4 +543,2125 @@ mod executor_test {
         assert_eq!(expected, actual, "Actual result:\n{}", table);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_executor_case_when_works() -> Result<()> {
+        // create in-memory storage
+        let id = "employee".to_string();
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+
+        // build executor
+        let executor = build_executor(
+            storage,
+            "select case when salary < 150 then 'low' when salary < 300 then 'mid' else 'high' end from employee",
+        )?;
+
+        // collect result
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(*a, StringArray::from(vec!["low", "low", "mid", "high"]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_case_with_null_else_is_typed() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage,
+            "select case when salary > 150 then salary else null end from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let field = output[0].schema().field(0).clone();
+        assert_eq!(field.data_type(), &DataType::Int64);
+        assert!(field.is_nullable());
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*a, Int64Array::from(vec![None, None, Some(200), Some(400)]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_divide_by_nullif_works() -> Result<()> {
+        // create in-memory storage
+        let id = "employee".to_string();
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+
+        // build executor
+        let executor = build_executor(storage, "select salary / nullif(id - 1, 0) from employee")?;
+
+        // collect result
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(
+            *a,
+            Int64Array::from(vec![None, Some(100), Some(100), Some(133)])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_between_works() -> Result<()> {
+        // create in-memory storage
+        let id = "employee".to_string();
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+
+        // build executor
+        let executor = build_executor(
+            storage,
+            "select id from employee where salary between 100 and 200",
+        )?;
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*a, Int64Array::from(vec![1, 2, 3]));
+
+        // build executor for negated form
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+        let executor = build_executor(
+            storage,
+            "select id from employee where salary not between 100 and 200",
+        )?;
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*a, Int64Array::from(vec![4]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_order_limit_offset_pagination_works() -> Result<()> {
+        // ids 0..35 are stored out of order across two batches
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let ids = (0..35).map(|i| (i * 17) % 35).collect::<Vec<i64>>();
+        let batches = vec![
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int64Array::from(ids[..20].to_vec()))],
+            )?,
+            RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(ids[20..].to_vec()))])?,
+        ];
+
+        let mut paged_ids = vec![];
+        for page in 0..4 {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("t".to_string(), batches.clone())?;
+            let sql = format!("select id from t order by id limit 10 offset {}", page * 10);
+            let executor = build_executor(storage, sql.as_str())?;
+            let output = try_collect(executor).await?;
+            let mut page_ids = vec![];
+            for batch in output {
+                let a = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                page_ids.extend(a.values().iter().copied());
+            }
+            let expected_len = if page < 3 { 10 } else { 5 };
+            assert_eq!(page_ids.len(), expected_len);
+            paged_ids.extend(page_ids);
+        }
+
+        // pages have no overlaps or gaps
+        assert_eq!(paged_ids, (0..35).collect::<Vec<i64>>());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_current_date_works() -> Result<()> {
+        let id = "employee".to_string();
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+
+        // 2022-05-09 12:00:00 UTC
+        let query_start = UNIX_EPOCH + Duration::from_secs(1652097600);
+        let executor = build_executor_with_binder(
+            storage,
+            "select current_date, now() from employee",
+            |binder| binder.with_query_start(query_start),
+        )?;
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(*a, Date32Array::from(vec![19121; 4]));
+        assert_eq!(
+            output[0].column(1).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        let b = output[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(
+            *b,
+            TimestampMicrosecondArray::from(vec![1_652_097_600_000_000; 4])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_decimal_sum_keeps_scale() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("price", DataType::Decimal(10, 2), true),
+        ]));
+        let prices = vec![Some(1250), Some(325), None, Some(5)]
+            .into_iter()
+            .collect::<DecimalArray>()
+            .with_precision_and_scale(10, 2)?;
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                Arc::new(prices),
+            ],
+        )?;
+
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("orders".to_string(), vec![batch.clone()])?;
+        let executor = build_executor(storage, "select sum(price) from orders")?;
+        let output = try_collect(executor).await?;
+        let sum = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<DecimalArray>()
+            .unwrap();
+        assert_eq!(sum.data_type(), &DataType::Decimal(38, 2));
+        assert_eq!(sum.value(0).as_i128(), 1580);
+
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("orders".to_string(), vec![batch])?;
+        let executor = build_executor(
+            storage,
+            "select id, price * 2 from orders where price > decimal '3.25'",
+        )?;
+        let output = try_collect(executor).await?;
+        let ids = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*ids, Int64Array::from(vec![1]));
+        let doubled = output[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<DecimalArray>()
+            .unwrap();
+        assert_eq!(doubled.data_type(), &DataType::Decimal(21, 2));
+        assert_eq!(doubled.value(0).as_i128(), 2500);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_group_by_decimal() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("price", DataType::Decimal(10, 2), true),
+        ]));
+        let prices = vec![Some(1250), Some(325), Some(1250), None]
+            .into_iter()
+            .collect::<DecimalArray>()
+            .with_precision_and_scale(10, 2)?;
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                Arc::new(prices),
+            ],
+        )?;
+
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("orders".to_string(), vec![batch])?;
+        let executor = build_executor(
+            storage,
+            "select price, count(id) from orders group by price",
+        )?;
+        let output = try_collect(executor).await?;
+        let prices = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<DecimalArray>()
+            .unwrap();
+        let counts = output[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        let mut groups = (0..prices.len())
+            .map(|i| {
+                let price = (!prices.is_null(i)).then(|| prices.value(i).as_i128());
+                (price, counts.value(i))
+            })
+            .collect::<Vec<_>>();
+        groups.sort();
+        assert_eq!(groups, vec![(None, 1), (Some(325), 1), (Some(1250), 2)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_scan_drops_unprojected_columns() -> Result<()> {
+        let id = "employee".to_string();
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+        let table = storage.get_catalog().get_table_by_name(&id).unwrap();
+        let columns = ["salary", "first_name"]
+            .iter()
+            .map(|name| table.get_column_by_name(name).unwrap())
+            .collect();
+        let plan = PhysicalTableScan::new(LogicalTableScan::new(id, columns));
+
+        let executor = TableScanExecutor {
+            plan,
+            storage: Arc::new(storage),
+            parallelism: 1,
+        }
+        .execute();
+        let output = try_collect(executor).await?;
+        let schema = output[0].schema();
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["salary", "first_name"]);
+        Ok(())
+    }
+
+    /// The csv storage which counts the batches read from its tables.
+    struct CountingStorage {
+        inner: CsvStorage,
+        reads: Arc<AtomicUsize>,
+    }
+
+    #[derive(Clone)]
+    struct CountingTable {
+        inner: CsvTable,
+        reads: Arc<AtomicUsize>,
+    }
+
+    struct CountingTransaction {
+        inner: CsvTransaction,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl Storage for CountingStorage {
+        type TableType = CountingTable;
+
+        fn create_csv_table(&self, id: String, filepath: String) -> Result<(), StorageError> {
+            self.inner.create_csv_table(id, filepath)
+        }
+
+        fn create_mem_table(&self, id: String, data: Vec<RecordBatch>) -> Result<(), StorageError> {
+            self.inner.create_mem_table(id, data)
+        }
+
+        fn get_table(&self, id: String) -> Result<Self::TableType, StorageError> {
+            Ok(CountingTable {
+                inner: self.inner.get_table(id)?,
+                reads: self.reads.clone(),
+            })
+        }
+
+        fn get_catalog(&self) -> RootCatalog {
+            self.inner.get_catalog()
+        }
+
+        fn show_tables(&self) -> Result<RecordBatch, StorageError> {
+            self.inner.show_tables()
+        }
+
+        fn table_stats(&self, table: &str) -> TableStats {
+            self.inner.table_stats(table)
+        }
+    }
+
+    impl Table for CountingTable {
+        type TransactionType = CountingTransaction;
+
+        fn read(&self) -> Result<Self::TransactionType, StorageError> {
+            Ok(CountingTransaction {
+                inner: self.inner.read()?,
+                reads: self.reads.clone(),
+            })
+        }
+    }
+
+    impl Transaction for CountingTransaction {
+        fn next_batch(&mut self) -> Result<Option<RecordBatch>, StorageError> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.next_batch()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_table_scan_stops_reading_after_limit() -> Result<()> {
+        // 10 batches of the csv reader
+        let mut content = "id,name\n".to_string();
+        for i in 0..10 * 1024 {
+            content.push_str(&format!("{},name_{}\n", i, i));
+        }
+        let file = TempFile::with_content(".csv", &content)?;
+        let storage = CountingStorage {
+            inner: CsvStorage::new(),
+            reads: Arc::new(AtomicUsize::new(0)),
+        };
+        storage.create_csv_table("t".to_string(), file.path().to_str().unwrap().to_string())?;
+        let table = storage.get_catalog().get_table_by_name("t").unwrap();
+        let plan = PhysicalTableScan::new(LogicalTableScan::new(
+            "t".to_string(),
+            table.get_all_columns(),
+        ));
+        let reads = storage.reads.clone();
+
+        let scan = TableScanExecutor {
+            plan,
+            storage: Arc::new(storage),
+            parallelism: 1,
+        }
+        .execute();
+        let executor = LimitExecutor {
+            limit: Some(5),
+            offset: Some(10),
+            tie_keys: vec![],
+            child: scan,
+        }
+        .execute();
+        let output = try_collect(executor).await?;
+
+        assert_eq!(output.iter().map(|b| b.num_rows()).sum::<usize>(), 5);
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_float_sum_and_compare_works() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("v", DataType::Float64, true),
+            Field::new("w", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                Arc::new(Float64Array::from(vec![
+                    Some(1.5),
+                    Some(2.5),
+                    Some(f64::NAN),
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(1.0),
+                    Some(f64::INFINITY),
+                    Some(-1.0),
+                    None,
+                ])),
+            ],
+        )?;
+        let run = |sql: &'static str| -> Result<BoxedExecutor> {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("t".to_string(), vec![batch.clone()])?;
+            build_executor(storage, sql)
+        };
+        let float_column = |batch: &RecordBatch, i: usize| {
+            batch
+                .column(i)
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap()
+                .clone()
+        };
+
+        // NaN propagates in sum and is the greatest in max, infinity is kept
+        let output = try_collect(run("select sum(v), max(v), sum(w), min(w) from t")?).await?;
+        assert!(float_column(&output[0], 0).value(0).is_nan());
+        assert!(float_column(&output[0], 1).value(0).is_nan());
+        assert_eq!(float_column(&output[0], 2).value(0), f64::INFINITY);
+        assert_eq!(float_column(&output[0], 3).value(0), -1.0);
+
+        // int and float are coerced to float
+        let output = try_collect(run("select id + v, v % 2 from t where v < 2.0 + id")?).await?;
+        assert_eq!(
+            float_column(&output[0], 0),
+            Float64Array::from(vec![2.5, 4.5])
+        );
+        assert_eq!(
+            float_column(&output[0], 1),
+            Float64Array::from(vec![1.5, 0.5])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_union_all_is_flattened() -> Result<()> {
+        let sql = "select id from employee where id = 1 \
+            union all select id from employee where id = 2 \
+            union all select id from employee where id = 3 \
+            union all select id from employee where id = 4";
+
+        // four branches are planned as one union rather than nested unions
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        let stmts = parse(sql).unwrap();
+        let mut binder = Binder::new(Arc::new(storage.get_catalog()));
+        let logical_plan = Planner::default().plan(binder.bind(&stmts[0]).unwrap())?;
+        assert_eq!(logical_plan.children().len(), 4);
+        let counts = count_operators(&logical_plan);
+        assert_eq!(counts.get("LogicalUnion"), Some(&1));
+        assert_eq!(counts.get("LogicalTableScan"), Some(&4));
+
+        let output = try_collect(build_executor(storage, sql)?).await?;
+        let ids = output
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert!(output
+            .iter()
+            .all(|batch| batch.schema().field(0).name() == "id"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_select_without_from() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        let executor = build_executor(
+            storage,
+            "select 1 + 1, 3 union all select 10000000000, 4000000000",
+        )?;
+        let output = try_collect(executor).await?;
+        let column = |i: usize| {
+            output
+                .iter()
+                .flat_map(|batch| {
+                    batch
+                        .column(i)
+                        .as_any()
+                        .downcast_ref::<Int64Array>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>()
+        };
+        // each branch outputs a single row, and the int literals are unified into Int64
+        assert_eq!(column(0), vec![2, 10000000000]);
+        assert_eq!(column(1), vec![3, 4000000000]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_count_star_counts_null_rows() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("v", DataType::Int64, true),
+        ]));
+        let build_batch = |ids: Vec<i64>| {
+            let nulls = vec![None; ids.len()];
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int64Array::from(ids)),
+                    Arc::new(Int64Array::from(nulls)),
+                ],
+            )
+        };
+        // the rows are split into two batches, the counts should be accumulated
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(
+            "t".to_string(),
+            vec![build_batch(vec![1, 2])?, build_batch(vec![3, 4])?],
+        )?;
+
+        let executor = build_executor(storage, "select count(*), count(v), count(id) from t")?;
+        let output = try_collect(executor).await?;
+        let counts = (0..3)
+            .map(|i| {
+                output[0]
+                    .column(i)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .value(0)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![4, 0, 4]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_distinct_agg_works() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // salary: 100, 100, 200, 400
+        let executor = build_executor(
+            storage,
+            "select count(distinct salary), sum(distinct salary), count(salary) from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let values = (0..3)
+            .map(|i| {
+                output[0]
+                    .column(i)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .value(0)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![3, 700, 4]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_agg_over_expression_works() -> Result<()> {
+        let run = |sql: &'static str| -> Result<BoxedExecutor> {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+            build_executor(storage, sql)
+        };
+        let int_column = |batch: &RecordBatch, i: usize| {
+            batch
+                .column(i)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .clone()
+        };
+
+        // salary: 100, 100, 200, 400
+        let output =
+            try_collect(run("select sum(salary * 2), count(id + 1) from employee")?).await?;
+        assert_eq!(int_column(&output[0], 0), Int64Array::from(vec![1600]));
+        assert_eq!(int_column(&output[0], 1), Int64Array::from(vec![4]));
+
+        let output = try_collect(run(
+            "select salary, sum(id * 10) from employee group by salary",
+        )?)
+        .await?;
+        assert_eq!(
+            int_column(&output[0], 0),
+            Int64Array::from(vec![100, 200, 400])
+        );
+        assert_eq!(
+            int_column(&output[0], 1),
+            Int64Array::from(vec![30, 30, 40])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_explain_works() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage,
+            "explain select first_name from employee where id = 1",
+        )?;
+        let output = try_collect(executor).await?;
+        let lines = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .map(|line| line.unwrap().to_string())
+            .collect::<Vec<_>>();
+        // the plan tree is rendered from root to leaf, children are indented
+        let position = |node: &str| {
+            lines
+                .iter()
+                .position(|line| line.trim_start().starts_with(node))
+                .unwrap_or_else(|| panic!("{} not found in {:?}", node, lines))
+        };
+        let (project, filter, scan) = (
+            position("PhysicalProject"),
+            position("PhysicalFilter"),
+            position("PhysicalTableScan"),
+        );
+        assert!(project < filter && filter < scan, "{:?}", lines);
+        assert!(lines[scan].starts_with("    "), "{:?}", lines);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_explain_analyze_works() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage,
+            "explain analyze select first_name from employee where id > 2",
+        )?;
+        let output = try_collect(executor).await?;
+        let lines = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .map(|line| line.unwrap().to_string())
+            .collect::<Vec<_>>();
+        let find = |node: &str| {
+            lines
+                .iter()
+                .find(|line| line.trim_start().starts_with(node))
+                .unwrap_or_else(|| panic!("{} not found in {:?}", node, lines))
+                .clone()
+        };
+        // the scan outputs all 4 rows, and 2 of them pass the filter
+        assert!(find("PhysicalTableScan").contains("(rows=4, time="));
+        assert!(find("PhysicalFilter").contains("(rows=2, time="));
+        assert!(find("PhysicalProject").contains("(rows=2, time="));
+        assert!(
+            lines.iter().all(|line| line.ends_with("ms)")),
+            "{:?}",
+            lines
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_explain_verbose_lists_fired_rules() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let stmts =
+            parse("explain verbose select id, salary from (select id, salary from employee) t")
+                .unwrap();
+        let mut binder = Binder::new(Arc::new(storage.get_catalog()));
+        let logical_plan = Planner::default().plan(binder.bind(&stmts[0]).unwrap())?;
+        let mut optimizer = Optimizer::new(vec![Batch::new(
+            "Remove Redundant Project",
+            BatchStrategy::FixedPoint(10),
+            vec![
+                Box::new(InputRefRewriter::default()),
+                Box::new(RemoveRedundantProjectRewriter::default()),
+            ],
+        )]);
+        let new_logical_plan = optimizer.optimize(logical_plan);
+        let explain = new_logical_plan
+            .as_logical_explain()
+            .unwrap()
+            .clone()
+            .with_fired_rules(optimizer.fired_rules().to_vec());
+        let physical_plan = PhysicalRewriter {}.rewrite(Arc::new(explain));
+        let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(Arc::new(storage)));
+        let output = try_collect(builder.build(physical_plan)).await?;
+
+        let lines = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .map(|line| line.unwrap().to_string())
+            .collect::<Vec<_>>();
+        // the rules follow the plan tree
+        let rule = lines
+            .iter()
+            .position(|line| line.starts_with("Rule RemoveRedundantProjectRewriter fired"))
+            .unwrap_or_else(|| panic!("rule not found in {:?}", lines));
+        let scan = lines
+            .iter()
+            .position(|line| line.trim_start().starts_with("PhysicalTableScan"))
+            .unwrap();
+        assert!(scan < rule, "{:?}", lines);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_respects_batch_size() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from((0..2500).collect::<Vec<i64>>()))],
+        )?;
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("t".to_string(), vec![batch])?;
+        let catalog = storage.get_catalog();
+        let columns = catalog.get_table_by_name("t").unwrap().get_all_columns();
+        let plan = PhysicalTableScan::new(LogicalTableScan::new("t".to_string(), columns));
+
+        let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(Arc::new(storage)))
+            .with_batch_size(1000);
+        let output = try_collect(builder.build(Arc::new(plan))).await?;
+        let sizes = output.iter().map(|b| b.num_rows()).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![1000, 1000, 500]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_parallel_scan_is_same_as_serial() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batches = (0..10)
+            .map(|i| {
+                let ids = (i * 100..(i + 1) * 100).collect::<Vec<i64>>();
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(Int64Array::from(ids))])
+            })
+            .try_collect::<Vec<_>>()?;
+        let storage = Arc::new(InMemoryStorage::new());
+        storage.create_mem_table("t".to_string(), batches)?;
+        let catalog = storage.get_catalog();
+        let columns = catalog.get_table_by_name("t").unwrap().get_all_columns();
+        let plan: PlanRef = Arc::new(PhysicalTableScan::new(LogicalTableScan::new(
+            "t".to_string(),
+            columns,
+        )));
+
+        let scan = |parallelism: usize| {
+            let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(storage.clone()))
+                .with_scan_parallelism(parallelism);
+            let executor = builder.build(plan.clone());
+            async move {
+                let mut ids = try_collect(executor)
+                    .await?
+                    .iter()
+                    .flat_map(|batch| {
+                        batch
+                            .column(0)
+                            .as_any()
+                            .downcast_ref::<Int64Array>()
+                            .unwrap()
+                            .values()
+                            .to_vec()
+                    })
+                    .collect::<Vec<_>>();
+                ids.sort_unstable();
+                Result::<_>::Ok(ids)
+            }
+        };
+        let serial = scan(1).await?;
+        let parallel = scan(4).await?;
+        assert_eq!(serial.len(), 1000);
+        assert_eq!(serial, parallel);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_merge_outputs_all_rows_once() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let child = |ranges: Vec<std::ops::Range<i64>>| -> BoxedExecutor {
+            let batches = ranges
+                .into_iter()
+                .map(|range| -> Result<RecordBatch, ExecutorError> {
+                    let ids = Int64Array::from(range.collect::<Vec<_>>());
+                    Ok(RecordBatch::try_new(schema.clone(), vec![Arc::new(ids)])?)
+                })
+                .collect::<Vec<_>>();
+            stream::iter(batches).boxed()
+        };
+        let inputs = vec![
+            child(vec![0..10, 10..20]),
+            child(vec![20..25]),
+            child(vec![25..30, 30..40, 40..50]),
+        ];
+        let output = try_collect(MergeExecutor { inputs }.execute()).await?;
+        let mut ids = output
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, (0..50).collect::<Vec<_>>());
+
+        let failed: BoxedExecutor =
+            stream::iter(vec![Err(ExecutorError::Evaluator("oops".to_string()))]).boxed();
+        let inputs = vec![child(vec![0..10]), failed];
+        let result = try_collect(MergeExecutor { inputs }.execute()).await;
+        assert_matches!(result, Err(ExecutorError::Evaluator(_)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_filter_by_in_subquery() -> Result<()> {
+        let run = |sql: &'static str| async move {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+            let output = try_collect(build_executor(storage, sql)?).await?;
+            let ids = output
+                .iter()
+                .flat_map(|batch| {
+                    batch
+                        .column(0)
+                        .as_any()
+                        .downcast_ref::<Int64Array>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>();
+            Result::<_>::Ok(ids)
+        };
+
+        let ids = run("select id from employee \
+            where id in (select id from employee where salary > 100) and id < 4")
+        .await?;
+        assert_eq!(ids, vec![3]);
+
+        let ids = run(
+            "select id from employee where id not in (select id from employee where salary > 100)",
+        )
+        .await?;
+        assert_eq!(ids, vec![1, 2]);
+
+        // no value is IN the empty subquery, and every value is NOT IN it
+        let ids = run(
+            "select id from employee where id in (select id from employee where salary > 1000)",
+        )
+        .await?;
+        assert!(ids.is_empty());
+
+        let ids = run(
+            "select id from employee where id not in (select id from employee where salary > 1000)",
+        )
+        .await?;
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_random_with_seed_is_reproducible() -> Result<()> {
+        let run = || async {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+            let sql = "select id, random() from employee";
+            let executor = build_executor_with_rng(storage, sql, SessionRng::with_seed(42))?;
+            let output = try_collect(executor).await?;
+            let column = output[0]
+                .column(1)
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap()
+                .values()
+                .to_vec();
+            Result::<_>::Ok(column)
+        };
+        let first = run().await?;
+        let second = run().await?;
+        assert_eq!(first.len(), 4);
+        assert!(first.iter().all(|v| (0.0..1.0).contains(v)));
+        // each row draws a new value
+        assert_ne!(first[0], first[1]);
+        assert_eq!(first, second);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_array_literal_length_and_index() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        let sql = "select array[id, 10, 20], array_length(array[id, 10, 20]), \
+            array[id, 10, 20][2], array[id][5] from employee";
+        let output = try_collect(build_executor(storage, sql)?).await?;
+        let batch = &output[0];
+
+        let arrays = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        let first = arrays.value(0);
+        let first = first.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(first.values(), &[1, 10, 20]);
+        let lengths = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(lengths.values(), &[3, 3, 3, 3]);
+        let seconds = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(seconds.values(), &[10, 10, 10, 10]);
+        // out of range index is NULL
+        assert_eq!(batch.column(3).null_count(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_select_alias_names_output() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        let sql = "select id, salary as pay from employee order by pay desc, id";
+        let output = try_collect(build_executor(storage, sql)?).await?;
+        let schema = output[0].schema();
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "pay"]);
+        let ids = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[4, 3, 1, 2]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_select_wildcard_keeps_declared_order() -> Result<()> {
+        for sql in ["select * from employee", "select e.* from employee e"] {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+            let output = try_collect(build_executor(storage, sql)?).await?;
+            let schema = output[0].schema();
+            let names = schema
+                .fields()
+                .iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, vec!["id", "first_name", "last_name", "salary"]);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_fetch_with_ties() -> Result<()> {
+        let run = |sql: &'static str| async move {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Int64, false),
+                Field::new("salary", DataType::Int64, false),
+            ]));
+            let batch = RecordBatch::try_new(
+                schema,
+                vec![
+                    Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                    Arc::new(Int64Array::from(vec![300, 200, 100, 200])),
+                ],
+            )?;
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("employee".to_string(), vec![batch])?;
+            let output = try_collect(build_executor(storage, sql)?).await?;
+            let mut ids = output
+                .iter()
+                .flat_map(|batch| {
+                    batch
+                        .column(0)
+                        .as_any()
+                        .downcast_ref::<Int64Array>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>();
+            ids.sort_unstable();
+            Result::<_>::Ok(ids)
+        };
+
+        // the 2nd and 3rd rows tie on salary 200
+        let ids =
+            run("select id from employee order by salary fetch first 2 rows with ties").await?;
+        assert_eq!(ids, vec![2, 3, 4]);
+        let ids = run("select id from employee order by salary fetch first 2 rows only").await?;
+        assert_eq!(ids.len(), 2);
+        Ok(())
+    }
+
+    fn build_dept_record_batch() -> Result<Vec<RecordBatch>, StorageError> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("dept_id", DataType::Int64, false),
+            Field::new("dept_name", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec!["Sales", "R&D", "HR"])),
+            ],
+        )?;
+        Ok(vec![batch])
+    }
+
+    #[tokio::test]
+    async fn test_executor_cross_join_comma_separated_tables() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        storage.create_mem_table("dept".to_string(), build_dept_record_batch()?)?;
+
+        let stmts = parse("select * from employee, dept").unwrap();
+        let mut binder = Binder::new(Arc::new(storage.get_catalog()));
+        let logical_plan = Planner::default().plan(binder.bind(&stmts[0]).unwrap())?;
+        let columns = logical_plan
+            .schema()
+            .into_iter()
+            .map(|c| format!("{}.{}", c.table_id, c.id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec![
+                "employee.id",
+                "employee.first_name",
+                "employee.last_name",
+                "employee.salary",
+                "dept.dept_id",
+                "dept.dept_name",
+            ]
+        );
+
+        // execute the join under the projection
+        let join = logical_plan.children()[0].clone();
+        assert_eq!(join.node_type(), PlanNodeType::LogicalJoin);
+        let physical_plan = PhysicalRewriter {}.rewrite(join);
+        let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(Arc::new(storage)));
+        let output = try_collect(builder.build(physical_plan)).await?;
+        assert_eq!(output.iter().map(|b| b.num_rows()).sum::<usize>(), 4 * 3);
+        assert!(output.iter().all(|b| b.num_columns() == 6));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_hash_join_on_equal_keys() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        storage.create_mem_table("dept".to_string(), build_dept_record_batch()?)?;
+
+        // the employee 4 has no matched dept
+        let executor = build_executor(
+            storage,
+            "select d.dept_name, e.first_name from employee e join dept d on e.id = d.dept_id",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+-----------+------------+",
+            "| dept_name | first_name |",
+            "+-----------+------------+",
+            "| Sales     | Bill       |",
+            "| R&D       | Gregg      |",
+            "| HR        | John       |",
+            "+-----------+------------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_join_using_merges_shared_columns() -> Result<()> {
+        let run = |sql: &'static str| async move {
+            let storage = InMemoryStorage::new();
+            storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Int64, false),
+                Field::new("bonus", DataType::Int64, false),
+            ]));
+            let batch = RecordBatch::try_new(
+                schema,
+                vec![
+                    Arc::new(Int64Array::from(vec![1, 2, 3])),
+                    Arc::new(Int64Array::from(vec![10, 20, 30])),
+                ],
+            )?;
+            storage.create_mem_table("bonus".to_string(), vec![batch])?;
+            Result::<_>::Ok(try_collect(build_executor(storage, sql)?).await?)
+        };
+
+        // the shared key appears once, followed by the other columns of both sides
+        for sql in [
+            "select * from employee join bonus using (id)",
+            "select * from employee natural join bonus",
+        ] {
+            let output = run(sql).await?;
+            let schema = output[0].schema();
+            let names = schema.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+            assert_eq!(
+                names,
+                vec!["id", "first_name", "last_name", "salary", "bonus"],
+                "{}",
+                sql
+            );
+            let rows = output.iter().map(|batch| batch.num_rows()).sum::<usize>();
+            assert_eq!(rows, 3, "{}", sql);
+        }
+
+        // the right key is resolved as the merged key, and the bare key is not ambiguous
+        let output = run("select bonus.id, id, bonus from employee join bonus using (id)").await?;
+        assert_eq!(output[0].column(0), output[0].column(1));
+        assert_eq!(
+            output[0]
+                .column(2)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap(),
+            &Int64Array::from(vec![10, 20, 30])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_project_same_column_twice() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(storage, "select id, id, id from employee")?;
+        let output = try_collect(executor).await?;
+        let schema = output[0].schema();
+        let names = schema.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "id:1", "id:2"]);
+        assert_eq!(output[0].column(0), output[0].column(1));
+        assert_eq!(output[0].column(0), output[0].column(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_scalar_subquery_in_select_list() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage.clone(),
+            "select id, (select max(salary) from employee) from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*a, Int64Array::from(vec![400, 400, 400, 400]));
+
+        // the subquery of no row is NULL
+        let executor = build_executor(
+            storage.clone(),
+            "select id, (select salary from employee where id > 4) from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        assert_eq!(output[0].column(1).null_count(), 4);
+
+        let executor = build_executor(
+            storage,
+            "select id, (select salary from employee) from employee",
+        )?;
+        let result = try_collect(executor).await;
+        assert_matches!(result, Err(ExecutorError::Evaluator(_)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_abs_overflow_is_error() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from(vec![
+                Some(-1),
+                None,
+                Some(i64::MIN),
+            ]))],
+        )?;
+        storage.create_mem_table("t".to_string(), batch)?;
+
+        let executor = build_executor(storage.clone(), "select abs(a) from t where a > -2")?;
+        let output = try_collect(executor).await?;
+        let a = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*a, Int64Array::from(vec![1]));
+
+        let executor = build_executor(storage, "select abs(a) from t")?;
+        let result = try_collect(executor).await;
+        assert_matches!(result, Err(ExecutorError::Evaluator(_)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_filter_by_exists_subquery() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        storage.create_mem_table("dept".to_string(), build_dept_record_batch()?)?;
+
+        for (sql, expected_rows) in [
+            (
+                "select * from employee where exists (select 1 from dept where dept_id = 2)",
+                4,
+            ),
+            (
+                "select * from employee where exists (select 1 from dept where dept_id = 5)",
+                0,
+            ),
+            (
+                "select * from employee where not exists (select 1 from dept where dept_id = 5)",
+                4,
+            ),
+            (
+                "select id from employee where id > 2 and exists (select dept_name from dept)",
+                2,
+            ),
+        ] {
+            let executor = build_executor(storage.clone(), sql)?;
+            let output = try_collect(executor).await?;
+            let num_rows = output.iter().map(|b| b.num_rows()).sum::<usize>();
+            assert_eq!(num_rows, expected_rows, "{}", sql);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_select_from_cte() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        storage.create_mem_table("dept".to_string(), build_dept_record_batch()?)?;
+
+        let executor = build_executor(
+            storage.clone(),
+            "with rich as (select id, first_name from employee where salary > 100) \
+            select * from rich",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+----+------------+",
+            "| id | first_name |",
+            "+----+------------+",
+            "| 3  | John       |",
+            "| 4  | Von        |",
+            "+----+------------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+
+        // join the CTE with a table, and the CTE referenced twice is inlined twice
+        let executor = build_executor(
+            storage,
+            "with rich(rich_id) as (select id from employee where salary > 100) \
+            select a.rich_id, dept_name from rich a join dept on a.rich_id = dept.dept_id \
+            join rich b on a.rich_id = b.rich_id",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+---------+-----------+",
+            "| rich_id | dept_name |",
+            "+---------+-----------+",
+            "| 3       | HR        |",
+            "+---------+-----------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_row_number_over_partitions() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage,
+            "select id, salary, row_number() over (partition by salary order by id), \
+            row_number() over (order by id desc) as rn from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+----+--------+------------+----+",
+            "| id | salary | row_number | rn |",
+            "+----+--------+------------+----+",
+            "| 1  | 100    | 1          | 4  |",
+            "| 2  | 100    | 2          | 3  |",
+            "| 3  | 200    | 1          | 2  |",
+            "| 4  | 400    | 1          | 1  |",
+            "+----+--------+------------+----+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_rank_and_dense_rank_over_ties() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // the peers on salary share the rank, and rank leaves a gap after them
+        let executor = build_executor(
+            storage,
+            "select id, salary, rank() over (order by salary), \
+            dense_rank() over (order by salary) as dr, \
+            row_number() over (order by salary) as rn from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+----+--------+------+----+----+",
+            "| id | salary | rank | dr | rn |",
+            "+----+--------+------+----+----+",
+            "| 1  | 100    | 1    | 1  | 1  |",
+            "| 2  | 100    | 1    | 1  | 2  |",
+            "| 3  | 200    | 3    | 2  | 3  |",
+            "| 4  | 400    | 4    | 3  | 4  |",
+            "+----+--------+------+----+----+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_aggregate_over_partitions() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // each row carries the aggregate of its whole partition
+        let executor = build_executor(
+            storage,
+            "select id, salary, sum(salary) over (partition by salary) as total, \
+            count(*) over (partition by salary) as cnt, avg(salary) over () as average \
+            from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+----+--------+-------+-----+---------+",
+            "| id | salary | total | cnt | average |",
+            "+----+--------+-------+-----+---------+",
+            "| 1  | 100    | 200   | 2   | 200     |",
+            "| 2  | 100    | 200   | 2   | 200     |",
+            "| 3  | 200    | 200   | 1   | 200     |",
+            "| 4  | 400    | 400   | 1   | 200     |",
+            "+----+--------+-------+-----+---------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_aggregate_over_window_frames() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // the moving average of the neighbours, and the running sum up to the peers
+        let executor = build_executor(
+            storage,
+            "select avg(salary) over (order by id rows between 1 preceding and 1 following), \
+            sum(salary) over (order by salary) from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let moving_avg = output[0].column(0);
+        let moving_avg = moving_avg.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(
+            moving_avg.values(),
+            &[100.0, 400.0 / 3.0, 700.0 / 3.0, 300.0]
+        );
+        let running_sum = output[0].column(1);
+        let running_sum = running_sum.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(running_sum.values(), &[200, 200, 400, 800]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_aggregate_over_growing_frames() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // the frames grow from the partition start, which are accumulated row by row
+        let executor = build_executor(
+            storage,
+            "select sum(salary) over (order by id rows between unbounded preceding and current row), \
+            min(salary) over (order by id desc rows between unbounded preceding and current row), \
+            count(id) over (order by id rows between 1 preceding and current row) from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let column = |i: usize| {
+            output[0]
+                .column(i)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(column(0), Int64Array::from(vec![100, 200, 400, 800]));
+        assert_eq!(column(1), Int64Array::from(vec![100, 100, 200, 400]));
+        // the sliding frame is accumulated again once its start moves
+        assert_eq!(column(2), Int64Array::from(vec![1, 2, 2, 2]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_lag_and_lead() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // the rows out of the partition are NULL, or the default if given
+        let executor = build_executor(
+            storage,
+            "select lag(salary) over (order by id) as prev, \
+            lead(salary, 2, 0) over (order by id) as next, \
+            lag(id) over (partition by salary order by id) as prev_id from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let column = |i: usize| {
+            output[0]
+                .column(i)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(
+            column(0),
+            Int64Array::from(vec![None, Some(100), Some(100), Some(200)])
+        );
+        assert_eq!(column(1), Int64Array::from(vec![200, 400, 0, 0]));
+        assert_eq!(column(2), Int64Array::from(vec![None, Some(1), None, None]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_ntile() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // the first buckets take the remainder rows
+        let executor = build_executor(
+            storage,
+            "select ntile(2) over (order by salary) as half, \
+            ntile(3) over (order by salary) as third from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let column = |i: usize| {
+            output[0]
+                .column(i)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(column(0), Int64Array::from(vec![1, 1, 2, 2]));
+        assert_eq!(column(1), Int64Array::from(vec![1, 1, 2, 3]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_window_output_in_arithmetic() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage,
+            "select id, salary - avg(salary) over () as diff from employee",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+----+------+",
+            "| id | diff |",
+            "+----+------+",
+            "| 1  | -100 |",
+            "| 2  | -100 |",
+            "| 3  | 0    |",
+            "| 4  | 200  |",
+            "+----+------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_compare_strings_by_collation() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("name", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec![
+                "Zoe", "émile", "Adam", "eve", "Éric",
+            ]))],
+        )?;
+        let sql = "select name from names where name >= 'E' order by name";
+        let collect_names = |output: Vec<RecordBatch>| {
+            output
+                .iter()
+                .flat_map(|batch| {
+                    let column = batch.column(0);
+                    let names = column.as_any().downcast_ref::<StringArray>().unwrap();
+                    names.iter().flatten().map(String::from).collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // the accented letters are after all ASCII letters by bytes
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("names".to_string(), vec![batch.clone()])?;
+        let output = try_collect(build_executor(storage, sql)?).await?;
+        assert_eq!(collect_names(output), vec!["Zoe", "eve", "Éric", "émile"]);
+
+        // the unicode collation compares the accented letters as their base letters
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("names".to_string(), vec![batch.clone()])?;
+        let executor = build_executor_with_binder(storage, sql, |binder| {
+            binder.with_collation(Collation::Unicode)
+        })?;
+        let output = try_collect(executor).await?;
+        assert_eq!(collect_names(output), vec!["émile", "Éric", "eve", "Zoe"]);
+
+        // IN list and BETWEEN compare by the collation as well
+        let sql =
+            "select name from names where name in ('EVE', 'zoe') or name between 'e' and 'er'";
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("names".to_string(), vec![batch])?;
+        let executor = build_executor_with_binder(storage, sql, |binder| {
+            binder.with_collation(Collation::Unicode)
+        })?;
+        let output = try_collect(executor).await?;
+        assert_eq!(collect_names(output), vec!["Zoe", "émile", "eve"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_limit_pushed_below_project() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        let stmts = parse("select salary from employee limit 2").unwrap();
+        let catalog = Arc::new(storage.get_catalog());
+        let bound_stmt = Binder::new(catalog).bind(&stmts[0]).unwrap();
+        let plan = Planner::default().plan(bound_stmt)?;
+        assert_eq!(plan.node_type(), PlanNodeType::LogicalLimit);
+
+        let plan = InputRefRewriter::default().rewrite(plan);
+        let plan = PushLimitThroughProjectRewriter::default().rewrite(plan);
+        assert_eq!(plan.node_type(), PlanNodeType::LogicalProject);
+        assert_eq!(plan.children()[0].node_type(), PlanNodeType::LogicalLimit);
+
+        let plan = PhysicalRewriter {}.rewrite(plan);
+        let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(Arc::new(storage)));
+        let output = try_collect(builder.build(plan)).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+--------+",
+            "| salary |",
+            "+--------+",
+            "| 100    |",
+            "| 100    |",
+            "+--------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_project_zero_columns() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        let table = storage.get_catalog().get_table_by_name("employee").unwrap();
+        let scan = LogicalTableScan::new("employee".to_string(), table.get_all_columns());
+        let plan: PlanRef = Arc::new(LogicalProject::new(vec![], Arc::new(scan)));
+
+        let plan = PhysicalRewriter {}.rewrite(InputRefRewriter::default().rewrite(plan));
+        let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(Arc::new(storage)));
+        let output = try_collect(builder.build(plan)).await?;
+        assert_eq!(output.iter().map(|b| b.num_rows()).sum::<usize>(), 4);
+        assert!(output.iter().all(|b| b.num_columns() == 0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_having_filters_groups() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        // the aggregate only in HAVING is computed but not output
+        let executor = build_executor(
+            storage,
+            "select salary, count(id) from employee group by salary \
+            having sum(id) > 3 or count(id) > 1",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+--------+-----------+",
+            "| salary | Count(id) |",
+            "+--------+-----------+",
+            "| 100    | 2         |",
+            "| 400    | 1         |",
+            "+--------+-----------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_group_by_expr() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+
+        let executor = build_executor(
+            storage,
+            "select salary / 100 as bucket, sum(id) from employee group by salary / 100",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+--------+---------+",
+            "| bucket | Sum(id) |",
+            "+--------+---------+",
+            "| 1      | 3       |",
+            "| 2      | 3       |",
+            "| 4      | 4       |",
+            "+--------+---------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_scans_json_table() -> Result<()> {
+        let storage = Arc::new(JsonStorage::new());
+        storage.create_json_table("people".to_string(), "./tests/json/people.json".to_string())?;
+
+        let stmts = parse("select name, id from people where active").unwrap();
+        let mut binder = Binder::new(storage.clone());
+        let bound_stmt = binder.bind(&stmts[0]).unwrap();
+        let logical_plan = Planner::default().plan(bound_stmt)?;
+        let logical_plan = InputRefRewriter::default().rewrite(logical_plan);
+        let physical_plan = PhysicalRewriter {}.rewrite(logical_plan);
+        let executor = ExecutorBuilder::new(StorageImpl::JsonStorage(storage)).build(physical_plan);
+
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+------+----+",
+            "| name | id |",
+            "+------+----+",
+            "| Bill | 1  |",
+            "| John | 3  |",
+            "+------+----+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_hash_agg_spills_groups_over_threshold() -> Result<()> {
+        // 10 batches of 100 rows in 50 groups, every group appears in every batch
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Int64, false),
+            Field::new("v", DataType::Int64, true),
+        ]));
+        let batches = (0..10)
+            .map(|b| {
+                let rows = (b * 100..(b + 1) * 100).collect::<Vec<i64>>();
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int64Array::from_iter_values(rows.iter().map(|i| i % 50))),
+                        Arc::new(Int64Array::from_iter(
+                            rows.iter().map(|i| (i % 7 != 0).then(|| *i)),
+                        )),
+                    ],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let input_ref = |index| {
+            BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: DataType::Int64,
+            })
+        };
+        let agg = |func, return_type| {
+            BoundExpr::AggFunc(BoundAggFunc {
+                func,
+                exprs: vec![input_ref(1)],
+                return_type,
+                distinct: false,
+            })
+        };
+        let new_executor = |spill_threshold| {
+            HashAggExecutor {
+                agg_funcs: vec![
+                    agg(AggFunc::Sum, DataType::Int64),
+                    agg(AggFunc::Count, DataType::Int64),
+                    agg(AggFunc::Min, DataType::Int64),
+                    agg(AggFunc::Avg, DataType::Float64),
+                ],
+                group_by: vec![input_ref(0)],
+                child: stream::iter(batches.clone().into_iter().map(Ok)).boxed(),
+                spill_threshold,
+            }
+            .execute()
+        };
+        let sorted_rows = |output: Vec<RecordBatch>| {
+            let mut rows = output
+                .iter()
+                .flat_map(|batch| {
+                    (0..batch.num_rows()).map(|row| {
+                        batch
+                            .columns()
+                            .iter()
+                            .map(|column| ScalarValue::try_from_array(column, row))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            rows.sort_by_key(|row| match row[0] {
+                ScalarValue::Int64(Some(k)) => k,
+                _ => unreachable!(),
+            });
+            rows
+        };
+
+        let in_memory = try_collect(new_executor(None)).await?;
+        assert_eq!(in_memory.len(), 1);
+        // the groups are spilled after every batch, and merged by partitions
+        let spilled = try_collect(new_executor(Some(1))).await?;
+        assert!(spilled.len() > 1);
+
+        let expected = sorted_rows(in_memory);
+        assert_eq!(expected.len(), 50);
+        assert_eq!(sorted_rows(spilled), expected);
+
+        // the distinct aggregates are not spilled
+        let distinct = HashAggExecutor {
+            agg_funcs: vec![BoundExpr::AggFunc(BoundAggFunc {
+                func: AggFunc::Count,
+                exprs: vec![input_ref(1)],
+                return_type: DataType::Int64,
+                distinct: true,
+            })],
+            group_by: vec![input_ref(0)],
+            child: stream::iter(batches.clone().into_iter().map(Ok)).boxed(),
+            spill_threshold: Some(1),
+        }
+        .execute();
+        let result = try_collect(distinct).await;
+        assert_matches!(
+            result,
+            Err(ExecutorError::ResourcesExhausted(msg))
+                if msg.starts_with("hash agg with distinct aggregates uses ")
+                    && msg.ends_with(" bytes, exceeding the spill threshold 1")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_cursor_fetches_in_chunks() -> Result<()> {
+        // the rows of batches in 3, 1 and 4, so the chunks cross the batch boundaries
+        let batch = build_record_batch()?.remove(0);
+        let batches = vec![batch.slice(0, 3), batch.slice(3, 1), batch];
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), batches)?;
+
+        let executor = build_executor(storage, "select id from employee")?;
+        let mut cursor = Cursor::new(executor);
+        let mut ids = vec![];
+        loop {
+            let chunk = cursor.fetch(2).await?;
+            let num_rows = chunk.iter().map(|b| b.num_rows()).sum::<usize>();
+            if num_rows == 0 {
+                break;
+            }
+            assert_eq!(num_rows, 2);
+            for b in &chunk {
+                let column = b.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+                ids.extend(column.iter().flatten());
+            }
+        }
+        assert_eq!(ids, vec![1, 2, 3, 4, 1, 2, 3, 4]);
+        // the exhausted cursor keeps returning empty
+        assert!(cursor.fetch(2).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_hash_join_probes_after_build_completes() -> Result<()> {
+        let batch = build_record_batch()?.remove(0);
+        let events = Arc::new(Mutex::new(vec![]));
+        // the stream of batches which records the event when each batch is read
+        let recorded = |name: &'static str, batches: Vec<RecordBatch>| -> BoxedExecutor {
+            let events = events.clone();
+            stream::iter(batches)
+                .map(move |batch| {
+                    events.lock().unwrap().push(name);
+                    Ok(batch)
+                })
+                .boxed()
+        };
+        let id = || {
+            BoundExpr::InputRef(BoundInputRef {
+                index: 0,
+                return_type: DataType::Int64,
+            })
+        };
+        let new_executor = |memory_limit| {
+            HashJoinExecutor {
+                left: recorded("probe", vec![batch.slice(0, 2), batch.slice(2, 2)]),
+                right: recorded("build", vec![batch.slice(0, 1), batch.slice(1, 3)]),
+                on: vec![(id(), id())],
+                memory_limit,
+            }
+            .execute()
+        };
+
+        let executor = new_executor(None);
+        // nothing is read until the output is polled
+        assert!(events.lock().unwrap().is_empty());
+        let output = try_collect(executor).await?;
+        assert_eq!(output.iter().map(|b| b.num_rows()).sum::<usize>(), 4);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["build", "build", "probe", "probe"]
+        );
+
+        // the build side is failed once it exceeds the memory limit, before reading the probe side
+        events.lock().unwrap().clear();
+        let result = try_collect(new_executor(Some(1))).await;
+        assert_matches!(result, Err(ExecutorError::ResourcesExhausted(_)));
+        assert_eq!(*events.lock().unwrap(), vec!["build"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_count_over_union_all_sums_branch_counts() -> Result<()> {
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("employee".to_string(), build_record_batch()?)?;
+        storage.create_mem_table("dept".to_string(), build_dept_record_batch()?)?;
+        let scan = |name: &str| -> PlanRef {
+            let table = storage.get_catalog().get_table_by_name(name).unwrap();
+            let columns = vec![table.get_all_columns()[0].clone()];
+            Arc::new(LogicalTableScan::new(name.to_string(), columns))
+        };
+        let employee_id = storage
+            .get_catalog()
+            .get_table_by_name("employee")
+            .unwrap()
+            .get_column_by_name("id")
+            .unwrap();
+        // count(id) over (employee union all dept)
+        let count = BoundExpr::AggFunc(BoundAggFunc {
+            func: AggFunc::Count,
+            exprs: vec![BoundExpr::ColumnRef(BoundColumnRef {
+                column_catalog: employee_id,
+            })],
+            return_type: DataType::Int64,
+            distinct: false,
+        });
+        let plan: PlanRef = Arc::new(LogicalAgg::new(
+            vec![count],
+            vec![],
+            Arc::new(LogicalUnion::new(
+                vec![scan("employee"), scan("dept")],
+                vec![DataType::Int64],
+            )),
+        ));
+        let plan = InputRefRewriter::default().rewrite(plan);
+        let plan = PushAggThroughUnionRewriter::default().rewrite(plan);
+
+        // sum over the union of the counts of each branch
+        let agg = plan.as_logical_agg().unwrap();
+        assert_matches!(&agg.agg_funcs()[0], BoundExpr::AggFunc(f) if f.func == AggFunc::Sum);
+        let union = agg.input();
+        let branches = union.as_logical_union().unwrap().inputs();
+        assert_eq!(branches.len(), 2);
+        for branch in branches {
+            let partial = branch.as_logical_agg().unwrap().agg_funcs();
+            assert_matches!(&partial[0], BoundExpr::AggFunc(f) if f.func == AggFunc::Count);
+        }
+
+        let plan = PhysicalRewriter {}.rewrite(plan);
+        let mut builder = ExecutorBuilder::new(StorageImpl::InMemoryStorage(Arc::new(storage)));
+        let output = try_collect(builder.build(plan)).await?;
+        let sum = output[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*sum, Int64Array::from(vec![7]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_group_by_date_and_timestamp() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("day", DataType::Date32, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Date32Array::from(vec![
+                    parse_date("2020-01-01"),
+                    parse_date("2020-01-02"),
+                    parse_date("2020-01-01"),
+                    None,
+                ])),
+                Arc::new(TimestampMicrosecondArray::from(vec![
+                    parse_timestamp("2020-01-01 10:00:00"),
+                    parse_timestamp("2020-01-02 10:00:00"),
+                    parse_timestamp("2020-01-01 10:00:00"),
+                    None,
+                ])),
+            ],
+        )?;
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("events".to_string(), vec![batch])?;
+
+        let executor = build_executor(
+            storage,
+            "select day, ts, count(day) from events group by day, ts",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+------------+---------------------+------------+",
+            "| day        | ts                  | Count(day) |",
+            "+------------+---------------------+------------+",
+            "| 2020-01-01 | 2020-01-01 10:00:00 | 2          |",
+            "| 2020-01-02 | 2020-01-02 10:00:00 | 1          |",
+            "|            |                     | 0          |",
+            "+------------+---------------------+------------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_add_interval_to_timestamp() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(TimestampMicrosecondArray::from(vec![
+                    parse_timestamp("2020-01-31 10:00:00"),
+                    None,
+                    parse_timestamp("2020-12-31 23:30:00"),
+                ])),
+            ],
+        )?;
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("events".to_string(), vec![batch])?;
+
+        // the month is added on the calendar with the day clamped, then the days are added
+        let executor = build_executor(
+            storage,
+            "select id, ts + interval '1 month 1 day' as next, ts - interval '2' hour as early \
+            from events where interval '1 month' + interval '30 days' = interval '2 months'",
+        )?;
+        let output = try_collect(executor).await?;
+        let table = pretty_format_batches(&output)?.to_string();
+        let expected = vec![
+            "+----+---------------------+---------------------+",
+            "| id | next                | early               |",
+            "+----+---------------------+---------------------+",
+            "| 1  | 2020-03-01 10:00:00 | 2020-01-31 08:00:00 |",
+            "| 2  |                     |                     |",
+            "| 3  | 2021-02-01 23:30:00 | 2020-12-31 21:30:00 |",
+            "+----+---------------------+---------------------+",
+        ];
+        let actual: Vec<&str> = table.lines().collect();
+        assert_eq!(expected, actual, "Actual result:\n{}", table);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_at_time_zone_of_fixed_offset() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(TimestampMicrosecondArray::from(vec![
+                parse_timestamp("2020-01-01 12:00:00"),
+                None,
+            ]))],
+        )?;
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table("events".to_string(), vec![batch])?;
+
+        // the naive local time of +08:00 is 8 hours ahead of UTC, and converting it back to the
+        // local time of -05:30 is 13.5 hours behind
+        let executor = build_executor(
+            storage,
+            "select ts at time zone '+08:00', \
+            ts at time zone '+08:00' at time zone '-05:30' from events",
+        )?;
+        let output = try_collect(executor).await?;
+        let column = |i: usize| {
+            output[0]
+                .column(i)
+                .as_any()
+                .downcast_ref::<TimestampMicrosecondArray>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            output[0].column(0).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("+08:00".to_string()))
+        );
+        assert_eq!(
+            column(0),
+            vec![parse_timestamp("2020-01-01 04:00:00"), None]
+        );
+        assert_eq!(
+            output[0].column(1).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(
+            column(1),
+            vec![parse_timestamp("2019-12-31 22:30:00"), None]
+        );
+        Ok(())
+    }
 }
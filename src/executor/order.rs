@@ -0,0 +1,46 @@
+use arrow::array::ArrayRef;
+use arrow::compute::{lexsort_to_indices, take, SortColumn, SortOptions};
+use arrow::record_batch::RecordBatch;
+
+use super::*;
+use crate::binder::BoundOrderBy;
+
+pub struct OrderExecutor {
+    pub order_by: Vec<BoundOrderBy>,
+    pub child: BoxedExecutor,
+}
+
+impl OrderExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        // sort is a blocking operator, collect all input batches at first
+        let mut batches = vec![];
+        #[for_await]
+        for batch in self.child {
+            batches.push(batch?);
+        }
+        if batches.is_empty() {
+            return Ok(());
+        }
+        let schema = batches[0].schema();
+        let batch = RecordBatch::concat(&schema, &batches)?;
+
+        let mut sort_columns = vec![];
+        for order_by in self.order_by.iter() {
+            sort_columns.push(SortColumn {
+                values: order_by.expr.eval_column(&batch)?,
+                options: Some(SortOptions {
+                    descending: !order_by.asc,
+                    nulls_first: order_by.nulls_first,
+                }),
+            });
+        }
+        let indices = lexsort_to_indices(&sort_columns, None)?;
+        let columns: Vec<ArrayRef> = batch
+            .columns()
+            .iter()
+            .map(|column| take(column.as_ref(), &indices, None))
+            .try_collect()?;
+        yield RecordBatch::try_new(schema, columns)?;
+    }
+}
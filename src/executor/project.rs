@@ -1,30 +1,80 @@
-use arrow::datatypes::{Schema, SchemaRef};
-use arrow::record_batch::RecordBatch;
+use std::collections::HashMap;
 
+use arrow::datatypes::{Field, Schema, SchemaRef};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+
+use super::subquery::resolve_subqueries;
 use super::*;
 use crate::binder::BoundExpr;
+
 pub struct ProjectExecutor {
     pub exprs: Vec<BoundExpr>,
+    /// rename the output column if the expr is aliased
+    pub aliases: Vec<Option<String>>,
     pub child: BoxedExecutor,
+    /// the scalar subqueries referenced by exprs, which are executed before the child.
+    pub subqueries: Vec<BoxedExecutor>,
 }
 
 impl ProjectExecutor {
     #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
     pub async fn execute(self) {
+        // the output fields are still named after the unresolved exprs
+        let mut resolved_exprs = self.exprs.clone();
+        resolve_subqueries(&mut resolved_exprs, self.subqueries).await?;
+
         #[for_await]
         for batch in self.child {
             let batch = batch?;
-            let columns = self
-                .exprs
+            let columns = resolved_exprs
                 .iter()
                 .map(|e| e.eval_column(&batch))
                 .try_collect();
-            let fields = self.exprs.iter().map(|e| e.eval_field(&batch)).collect();
+            let fields = self
+                .exprs
+                .iter()
+                .zip(self.aliases.iter())
+                .map(|(e, alias)| {
+                    let field = e.eval_field(&batch);
+                    match alias {
+                        Some(alias) => {
+                            Field::new(alias, field.data_type().clone(), field.is_nullable())
+                        }
+                        None => field,
+                    }
+                })
+                .collect();
             let schema = SchemaRef::new(Schema::new_with_metadata(
-                fields,
+                dedup_field_names(fields),
                 batch.schema().metadata().clone(),
             ));
-            yield RecordBatch::try_new(schema, columns?)?;
+            // the batch of zero columns carries the row count of input, e.g. `select from t`
+            let options = RecordBatchOptions {
+                row_count: Some(batch.num_rows()),
+                ..Default::default()
+            };
+            yield RecordBatch::try_new_with_options(schema, columns?, &options)?;
         }
     }
 }
+
+/// Suffix the repeated names with the number of previous occurrences, e.g. `select id, id` outputs
+/// `id` and `id:1`, so that every output column is addressable by name.
+fn dedup_field_names(fields: Vec<Field>) -> Vec<Field> {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    fields
+        .into_iter()
+        .map(|field| {
+            let count = occurrences.entry(field.name().clone()).or_insert(0);
+            *count += 1;
+            match *count {
+                1 => field,
+                n => Field::new(
+                    &format!("{}:{}", field.name(), n - 1),
+                    field.data_type().clone(),
+                    field.is_nullable(),
+                ),
+            }
+        })
+        .collect()
+}
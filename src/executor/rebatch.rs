@@ -0,0 +1,67 @@
+use arrow::record_batch::RecordBatch;
+
+use super::*;
+
+/// The default number of rows in the batches of executors.
+pub const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Coalesce the small batches and split the large ones, so that the output batches have
+/// `batch_size` rows except the last one.
+pub struct RebatchExecutor {
+    pub batch_size: usize,
+    pub child: BoxedExecutor,
+}
+
+impl RebatchExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        let batch_size = self.batch_size;
+        let mut buffer: Vec<RecordBatch> = vec![];
+        let mut buffered_rows = 0;
+        // keep an empty batch to output if the child produces no rows, so that the downstream
+        // still gets the schema
+        let mut empty_batch = None;
+        let mut emitted = false;
+
+        #[for_await]
+        for batch in self.child {
+            let batch = batch?;
+            if batch.num_rows() == 0 {
+                empty_batch.get_or_insert(batch);
+                continue;
+            }
+            buffered_rows += batch.num_rows();
+            buffer.push(batch);
+            if buffered_rows < batch_size {
+                continue;
+            }
+
+            let merged = concat_batches(std::mem::take(&mut buffer))?;
+            let mut offset = 0;
+            while merged.num_rows() - offset >= batch_size {
+                emitted = true;
+                yield merged.slice(offset, batch_size);
+                offset += batch_size;
+            }
+            buffered_rows = merged.num_rows() - offset;
+            if buffered_rows > 0 {
+                buffer.push(merged.slice(offset, buffered_rows));
+            }
+        }
+
+        if !buffer.is_empty() {
+            yield concat_batches(buffer)?;
+        } else if !emitted {
+            if let Some(batch) = empty_batch {
+                yield batch;
+            }
+        }
+    }
+}
+
+fn concat_batches(mut batches: Vec<RecordBatch>) -> Result<RecordBatch, ExecutorError> {
+    if batches.len() == 1 {
+        return Ok(batches.pop().unwrap());
+    }
+    Ok(RecordBatch::concat(&batches[0].schema(), &batches)?)
+}
@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+
+use super::ExecutorError;
+use crate::types::{rescale_decimal, ScalarValue};
+
+/// Compare two non-NULL scalar values. The numeric values of different types are compared as the
+/// wider type, e.g. `Int32(1) < Float64(1.5)`. Returns None if either side is NULL or the types are
+/// not comparable.
+pub fn compare_scalar(lhs: &ScalarValue, rhs: &ScalarValue) -> Option<Ordering> {
+    use ScalarValue::*;
+    match (lhs, rhs) {
+        (Boolean(Some(l)), Boolean(Some(r))) => Some(l.cmp(r)),
+        (String(Some(l)), String(Some(r))) => Some(l.cmp(r)),
+        (Int32(Some(l)), Int32(Some(r))) => Some(l.cmp(r)),
+        (Int64(Some(l)), Int64(Some(r))) => Some(l.cmp(r)),
+        (Decimal128(Some(l), _, l_scale), Decimal128(Some(r), _, r_scale)) => {
+            let scale = *l_scale.max(r_scale);
+            let l = rescale_decimal(*l, *l_scale, scale)?;
+            let r = rescale_decimal(*r, *r_scale, scale)?;
+            Some(l.cmp(&r))
+        }
+        (Date32(Some(l)), Date32(Some(r))) => Some(l.cmp(r)),
+        (Timestamp(Some(l)), Timestamp(Some(r))) => Some(l.cmp(r)),
+        (IntervalMonthDayNano(Some(l)), IntervalMonthDayNano(Some(r))) => Some(l.cmp(r)),
+        (Int32(Some(l)), Int64(Some(r))) => Some((*l as i64).cmp(r)),
+        (Int64(Some(l)), Int32(Some(r))) => Some(l.cmp(&(*r as i64))),
+        (Float64(Some(l)), _) => as_f64(rhs).map(|r| compare_f64(*l, r)),
+        (_, Float64(Some(r))) => as_f64(lhs).map(|l| compare_f64(l, *r)),
+        _ => None,
+    }
+}
+
+/// NaN is equal to itself and greater than any other value, which is consistent with the arrow
+/// min/max kernels.
+fn compare_f64(lhs: f64, rhs: f64) -> Ordering {
+    lhs.partial_cmp(&rhs)
+        .unwrap_or_else(|| lhs.is_nan().cmp(&rhs.is_nan()))
+}
+
+fn as_f64(value: &ScalarValue) -> Option<f64> {
+    match value {
+        ScalarValue::Float64(v) => *v,
+        ScalarValue::Int32(v) => v.map(|v| v as f64),
+        ScalarValue::Int64(v) => v.map(|v| v as f64),
+        _ => None,
+    }
+}
+
+/// Cast the numeric value to the wider type of both sides, so that the result type of min/max is
+/// independent of which side wins.
+fn widen(value: &ScalarValue, other: &ScalarValue) -> ScalarValue {
+    use ScalarValue::*;
+    match (value, other) {
+        (Int32(v), Int64(_)) => Int64(v.map(|v| v as i64)),
+        (Int32(_) | Int64(_), Float64(_)) => Float64(as_f64(value)),
+        _ => value.clone(),
+    }
+}
+
+fn pick(
+    lhs: &ScalarValue,
+    rhs: &ScalarValue,
+    keep: Ordering,
+) -> Result<ScalarValue, ExecutorError> {
+    // NULL is ignored, the result is NULL only if both sides are NULL
+    let (winner, other) = match (lhs.is_null(), rhs.is_null()) {
+        (true, true) | (false, true) => (lhs, rhs),
+        (true, false) => (rhs, lhs),
+        (false, false) => match compare_scalar(lhs, rhs) {
+            Some(ord) if ord == keep.reverse() => (rhs, lhs),
+            Some(_) => (lhs, rhs),
+            None => {
+                return Err(ExecutorError::Evaluator(format!(
+                    "unsupported scalar comparison: {} and {}",
+                    lhs.data_type(),
+                    rhs.data_type()
+                )))
+            }
+        },
+    };
+    Ok(widen(winner, other))
+}
+
+/// The smaller one of two scalar values, NULL is ignored.
+pub fn min_scalar(lhs: &ScalarValue, rhs: &ScalarValue) -> Result<ScalarValue, ExecutorError> {
+    pick(lhs, rhs, Ordering::Less)
+}
+
+/// The greater one of two scalar values, NULL is ignored.
+pub fn max_scalar(lhs: &ScalarValue, rhs: &ScalarValue) -> Result<ScalarValue, ExecutorError> {
+    pick(lhs, rhs, Ordering::Greater)
+}
+
+#[cfg(test)]
+mod scalar_cmp_test {
+    use super::*;
+
+    #[test]
+    fn test_min_max_scalar_across_types() -> Result<(), ExecutorError> {
+        let int = ScalarValue::Int32(Some(2));
+        let float = ScalarValue::Float64(Some(1.5));
+        assert_eq!(max_scalar(&int, &float)?, ScalarValue::Float64(Some(2.0)));
+        assert_eq!(min_scalar(&int, &float)?, ScalarValue::Float64(Some(1.5)));
+        assert_eq!(
+            max_scalar(&int, &ScalarValue::Int64(Some(1)))?,
+            ScalarValue::Int64(Some(2))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_scalar_with_nan() -> Result<(), ExecutorError> {
+        let nan = ScalarValue::Float64(Some(f64::NAN));
+        let inf = ScalarValue::Float64(Some(f64::INFINITY));
+        assert_eq!(min_scalar(&nan, &inf)?, inf);
+        assert!(matches!(max_scalar(&inf, &nan)?, ScalarValue::Float64(Some(v)) if v.is_nan()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_scalar_ignores_null() -> Result<(), ExecutorError> {
+        let int = ScalarValue::Int32(Some(2));
+        assert_eq!(max_scalar(&ScalarValue::Int32(None), &int)?, int);
+        assert_eq!(min_scalar(&int, &ScalarValue::Null)?, int);
+        assert_eq!(
+            min_scalar(&ScalarValue::Int32(None), &ScalarValue::Int32(None))?,
+            ScalarValue::Int32(None)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_scalar_rejects_incomparable_types() {
+        let int = ScalarValue::Int32(Some(2));
+        let string = ScalarValue::String(Some("a".to_string()));
+        assert!(matches!(
+            max_scalar(&int, &string),
+            Err(ExecutorError::Evaluator(_))
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayData, ArrayRef, Date32Array, Float64Array, Int32Array, Int64Array, ListArray,
+    StringArray, TimestampMicrosecondArray, UInt32Array,
+};
+use arrow::buffer::Buffer;
+use arrow::compute::kernels::temporal;
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, concat as concat_arrays, is_not_null, take};
+use arrow::datatypes::{DataType, Field};
+
+use super::scalar_cmp::{max_scalar, min_scalar};
+use super::ExecutorError;
+use crate::binder::{DateTimeField, ScalarFunc};
+use crate::types::{
+    append_scalar_value_for_builder, build_scalar_value_builder, format_time_zone, ScalarValue,
+};
+
+/// Apply the per-element math function on numeric array, NULL input produces NULL.
+macro_rules! typed_unary {
+    ($ARRAY:expr, $ARRAYTYPE:ident, $OP:ident) => {{
+        let array = $ARRAY
+            .as_any()
+            .downcast_ref::<$ARRAYTYPE>()
+            .expect("scalar func failed to downcast array");
+        let result = array
+            .iter()
+            .map(|v| v.map(|v| v.$OP()))
+            .collect::<$ARRAYTYPE>();
+        Ok(Arc::new(result))
+    }};
+}
+
+/// Apply the per-element checked integer function, the overflow is an error rather than wrapped,
+/// e.g. `abs` of the minimum integer.
+macro_rules! checked_unary {
+    ($ARRAY:expr, $ARRAYTYPE:ident, $OP:ident, $NAME:expr) => {{
+        let array = $ARRAY
+            .as_any()
+            .downcast_ref::<$ARRAYTYPE>()
+            .expect("scalar func failed to downcast array");
+        let result = array
+            .iter()
+            .map(|v| match v {
+                Some(v) => v
+                    .$OP()
+                    .map(Some)
+                    .ok_or_else(|| ExecutorError::Evaluator(format!("{}({}) overflows", $NAME, v))),
+                None => Ok(None),
+            })
+            .collect::<Result<$ARRAYTYPE, _>>()?;
+        Ok(Arc::new(result))
+    }};
+}
+
+/// Evaluate the scalar function on the evaluated argument arrays.
+pub fn eval_scalar_func(func: &ScalarFunc, args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    match func {
+        ScalarFunc::Concat => concat(args),
+        ScalarFunc::Upper => string_map(&args[0], |s| s.to_uppercase()),
+        ScalarFunc::Lower => string_map(&args[0], |s| s.to_lowercase()),
+        ScalarFunc::Length => length(&args[0]),
+        ScalarFunc::Substring => substring(args),
+        ScalarFunc::Trim | ScalarFunc::Ltrim | ScalarFunc::Rtrim => trim(func, args),
+        ScalarFunc::Abs => match args[0].data_type() {
+            DataType::Int32 => checked_unary!(&args[0], Int32Array, checked_abs, "abs"),
+            DataType::Int64 => checked_unary!(&args[0], Int64Array, checked_abs, "abs"),
+            _ => typed_unary!(&args[0], Float64Array, abs),
+        },
+        // ceil and floor of integer is itself
+        ScalarFunc::Ceil | ScalarFunc::Floor if args[0].data_type() != &DataType::Float64 => {
+            Ok(args[0].clone())
+        }
+        ScalarFunc::Ceil => typed_unary!(&args[0], Float64Array, ceil),
+        ScalarFunc::Floor => typed_unary!(&args[0], Float64Array, floor),
+        ScalarFunc::Round => round(args),
+        ScalarFunc::Greatest => fold_rows(args, max_scalar),
+        ScalarFunc::Least => fold_rows(args, min_scalar),
+        ScalarFunc::Coalesce => coalesce(args),
+        ScalarFunc::Extract(field) => extract(field, &args[0]),
+        ScalarFunc::AtTimeZone(offset) => at_time_zone(*offset, &args[0]),
+        ScalarFunc::MakeArray => make_array(args),
+        ScalarFunc::ArrayLength => array_length(&args[0]),
+        ScalarFunc::ArrayElement => array_element(&args[0], &args[1]),
+        ScalarFunc::CollationKey(collation) => {
+            string_map(&args[0], |s| collation.sort_key(s).into_owned())
+        }
+        ScalarFunc::Random(_) => Err(ExecutorError::Evaluator(
+            "random() is evaluated by the row count of batch".to_string(),
+        )),
+    }
+}
+
+fn as_string_array(array: &ArrayRef) -> &StringArray {
+    array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("scalar func expected string array")
+}
+
+fn as_int64_array(array: &ArrayRef) -> &Int64Array {
+    array
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .expect("scalar func expected int64 array")
+}
+
+fn as_list_array(array: &ArrayRef) -> &ListArray {
+    array
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .expect("scalar func expected list array")
+}
+
+/// CONCAT skips NULL arguments, so the result is never NULL.
+fn concat(args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    let arrays = args.iter().map(as_string_array).collect::<Vec<_>>();
+    let num_rows = arrays.first().map(|a| a.len()).unwrap_or(0);
+    let result = (0..num_rows)
+        .map(|row| {
+            let value = arrays
+                .iter()
+                .filter(|a| a.is_valid(row))
+                .map(|a| a.value(row))
+                .collect::<String>();
+            Some(value)
+        })
+        .collect::<StringArray>();
+    Ok(Arc::new(result))
+}
+
+/// Apply the function on each non-NULL string, NULL input produces NULL.
+fn string_map<F>(array: &ArrayRef, f: F) -> Result<ArrayRef, ExecutorError>
+where
+    F: Fn(&str) -> String,
+{
+    let result = as_string_array(array)
+        .iter()
+        .map(|v| v.map(&f))
+        .collect::<StringArray>();
+    Ok(Arc::new(result))
+}
+
+/// The number of characters rather than bytes of string.
+fn length(array: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    let result = as_string_array(array)
+        .iter()
+        .map(|v| v.map(|s| s.chars().count() as i64))
+        .collect::<Int64Array>();
+    Ok(Arc::new(result))
+}
+
+/// SQL substring with 1-based start, the chars in `[start, start + length)` are kept and out of
+/// range positions are ignored, e.g. `substring('abc', 0, 2)` is 'a'. NULL argument produces NULL.
+fn substring(args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    let strings = as_string_array(&args[0]);
+    let starts = as_int64_array(&args[1]);
+    let lengths = args.get(2).map(as_int64_array);
+    let result = (0..strings.len())
+        .map(|row| {
+            if strings.is_null(row)
+                || starts.is_null(row)
+                || lengths.map_or(false, |l| l.is_null(row))
+            {
+                return None;
+            }
+            let start = starts.value(row);
+            let begin = start.max(1);
+            let take = match lengths {
+                Some(l) => (start.saturating_add(l.value(row).max(0)) - begin).max(0) as usize,
+                None => usize::MAX,
+            };
+            let value = strings
+                .value(row)
+                .chars()
+                .skip((begin - 1) as usize)
+                .take(take)
+                .collect::<String>();
+            Some(value)
+        })
+        .collect::<StringArray>();
+    Ok(Arc::new(result))
+}
+
+/// Remove the leading and/or trailing characters, which are whitespaces if not specified.
+fn trim(func: &ScalarFunc, args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    let strings = as_string_array(&args[0]);
+    let characters = args.get(1).map(as_string_array);
+    let result = (0..strings.len())
+        .map(|row| {
+            if strings.is_null(row) || characters.map_or(false, |c| c.is_null(row)) {
+                return None;
+            }
+            let characters = characters.map(|c| c.value(row).chars().collect::<Vec<_>>());
+            let is_trimmed = |c: char| match &characters {
+                Some(characters) => characters.contains(&c),
+                None => c.is_whitespace(),
+            };
+            let value = strings.value(row);
+            let trimmed = match func {
+                ScalarFunc::Ltrim => value.trim_start_matches(is_trimmed),
+                ScalarFunc::Rtrim => value.trim_end_matches(is_trimmed),
+                _ => value.trim_matches(is_trimmed),
+            };
+            Some(trimmed.to_string())
+        })
+        .collect::<StringArray>();
+    Ok(Arc::new(result))
+}
+
+/// Round half away from zero to the given decimal digits, which is 0 if absent. The negative digits
+/// round the integer part, e.g. `round(1234, -2)` is 1200.
+fn round(args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    let values = cast(&args[0], &DataType::Float64)?;
+    let values = values
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .expect("scalar func expected float64 array");
+    let digits = args.get(1).map(as_int64_array);
+    let result = (0..values.len())
+        .map(|row| {
+            if values.is_null(row) || digits.map_or(false, |d| d.is_null(row)) {
+                return None;
+            }
+            let digits = digits.map_or(0, |d| d.value(row));
+            let factor = 10f64.powi(digits as i32);
+            Some((values.value(row) * factor).round() / factor)
+        })
+        .collect::<Float64Array>();
+    Ok(cast(&(Arc::new(result) as ArrayRef), args[0].data_type())?)
+}
+
+/// Fold the arguments of each row into one value, which shares the comparison of min/max
+/// aggregation, so greatest/least ignore NULL arguments as well.
+fn fold_rows<F>(args: &[ArrayRef], f: F) -> Result<ArrayRef, ExecutorError>
+where
+    F: Fn(&ScalarValue, &ScalarValue) -> Result<ScalarValue, ExecutorError>,
+{
+    let data_type = args[0].data_type();
+    if data_type == &DataType::Null {
+        return Ok(args[0].clone());
+    }
+    let mut builder = build_scalar_value_builder(data_type);
+    for row in 0..args[0].len() {
+        let mut values = args.iter().map(|arg| ScalarValue::try_from_array(arg, row));
+        let first = values
+            .next()
+            .unwrap_or_else(|| ScalarValue::from(data_type));
+        let value = values.try_fold(first, |acc, v| f(&acc, &v))?;
+        append_scalar_value_for_builder(&value, &mut builder)?;
+    }
+    Ok(builder.finish())
+}
+
+/// The first non-NULL argument of each row, which is built from the last argument by successively
+/// picking the valid values of the previous one.
+fn coalesce(args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    let mut result = args[args.len() - 1].clone();
+    for arg in args.iter().rev().skip(1) {
+        let mask = is_not_null(arg.as_ref())?;
+        result = zip(&mask, arg.as_ref(), result.as_ref())?;
+    }
+    Ok(result)
+}
+
+/// Apply the arrow temporal kernel on date or timestamp array.
+macro_rules! temporal_op {
+    ($ARRAY:expr, $OP:ident) => {{
+        match $ARRAY.data_type() {
+            DataType::Date32 => {
+                let array = $ARRAY.as_any().downcast_ref::<Date32Array>().unwrap();
+                temporal::$OP(array)?
+            }
+            _ => {
+                let array = $ARRAY
+                    .as_any()
+                    .downcast_ref::<TimestampMicrosecondArray>()
+                    .unwrap();
+                temporal::$OP(array)?
+            }
+        }
+    }};
+}
+
+/// Extract the field of date or timestamp as Int64.
+fn extract(field: &DateTimeField, array: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    let result = match field {
+        DateTimeField::Year => temporal_op!(array, year),
+        DateTimeField::Month => temporal_op!(array, month),
+        DateTimeField::Day => temporal_op!(array, day),
+        DateTimeField::Hour => temporal_op!(array, hour),
+        DateTimeField::Minute => temporal_op!(array, minute),
+        DateTimeField::Second => temporal_op!(array, second),
+    };
+    Ok(cast(&(Arc::new(result) as ArrayRef), &DataType::Int64)?)
+}
+
+/// Convert the naive timestamp of local time into the UTC one with the zone, or the timestamp with
+/// zone into the naive local time, the zone is the offset in seconds east of UTC.
+fn at_time_zone(offset: i32, array: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    let timestamps = array
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .expect("at time zone expected timestamp array");
+    let micros = offset as i64 * 1_000_000;
+    let (micros, time_zone) = match array.data_type() {
+        DataType::Timestamp(_, None) => (-micros, Some(format_time_zone(offset))),
+        _ => (micros, None),
+    };
+    let values = timestamps
+        .iter()
+        .map(|v| v.map(|v| v + micros))
+        .collect::<Vec<_>>();
+    Ok(Arc::new(TimestampMicrosecondArray::from_opt_vec(
+        values, time_zone,
+    )))
+}
+
+/// Build the list of each row from the arguments, e.g. `array[a, b]` is `[a0, b0], [a1, b1], ..`.
+fn make_array(args: &[ArrayRef]) -> Result<ArrayRef, ExecutorError> {
+    let (rows, width) = (args[0].len(), args.len());
+    let values = concat_arrays(&args.iter().map(|a| a.as_ref()).collect::<Vec<_>>())?;
+    // the j-th element of row i is at `j * rows + i` of the concatenated values
+    let indices = UInt32Array::from_iter_values(
+        (0..rows).flat_map(|i| (0..width).map(move |j| (j * rows + i) as u32)),
+    );
+    let values = take(values.as_ref(), &indices, None)?;
+    let offsets = (0..=rows).map(|i| (i * width) as i32).collect::<Vec<_>>();
+    let item = Field::new("item", values.data_type().clone(), true);
+    let data = ArrayData::builder(DataType::List(Box::new(item)))
+        .len(rows)
+        .add_buffer(Buffer::from_slice_ref(&offsets))
+        .add_child_data(values.data().clone())
+        .build()?;
+    Ok(Arc::new(ListArray::from(data)))
+}
+
+fn array_length(array: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    let list = as_list_array(array);
+    let result = (0..list.len())
+        .map(|i| (!list.is_null(i)).then(|| list.value_length(i) as i64))
+        .collect::<Int64Array>();
+    Ok(Arc::new(result))
+}
+
+/// The 1-based element of list, NULL if the index is out of range.
+fn array_element(array: &ArrayRef, index: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    let list = as_list_array(array);
+    let index = as_int64_array(index);
+    let offsets = list.value_offsets();
+    let indices = (0..list.len())
+        .map(|i| {
+            if list.is_null(i) || index.is_null(i) {
+                return None;
+            }
+            let (start, len) = (offsets[i] as i64, list.value_length(i) as i64);
+            let k = index.value(i);
+            (1..=len).contains(&k).then(|| (start + k - 1) as u32)
+        })
+        .collect::<UInt32Array>();
+    Ok(take(list.values().as_ref(), &indices, None)?)
+}
+
+/// `||` produces NULL if any operand is NULL.
+pub fn string_concat(left: &ArrayRef, right: &ArrayRef) -> Result<ArrayRef, ExecutorError> {
+    let left = as_string_array(left);
+    let right = as_string_array(right);
+    let result = left
+        .iter()
+        .zip(right.iter())
+        .map(|(l, r)| match (l, r) {
+            (Some(l), Some(r)) => Some(format!("{}{}", l, r)),
+            _ => None,
+        })
+        .collect::<StringArray>();
+    Ok(Arc::new(result))
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::binder::{BoundExpr, BoundSubquery, SubqueryKind};
+use crate::optimizer::{ExprRewriter, ExprVisitor};
+use crate::types::ScalarValue;
+
+/// Execute the uncorrelated subqueries referenced by exprs once, and replace them in exprs with
+/// their results as constant.
+pub async fn resolve_subqueries(
+    exprs: &mut [BoundExpr],
+    subqueries: Vec<BoxedExecutor>,
+) -> Result<(), ExecutorError> {
+    if subqueries.is_empty() {
+        return Ok(());
+    }
+    let mut collector = SubqueryKindCollector::default();
+    for expr in exprs.iter() {
+        collector.visit_expr(expr);
+    }
+
+    let mut values = Vec::with_capacity(subqueries.len());
+    for (index, subquery) in subqueries.into_iter().enumerate() {
+        let batches = try_collect(subquery).await?;
+        let num_rows = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+        let value = match collector.kinds.get(&index) {
+            Some(SubqueryKind::Exists { negated }) => {
+                Some(ScalarValue::Boolean(Some((num_rows > 0) != *negated)))
+            }
+            _ => {
+                if num_rows > 1 {
+                    return Err(ExecutorError::Evaluator(
+                        "more than one row returned by a subquery used as an expression"
+                            .to_string(),
+                    ));
+                }
+                batches
+                    .iter()
+                    .find(|b| b.num_rows() == 1)
+                    .map(|b| ScalarValue::try_from_array(b.column(0), 0))
+            }
+        };
+        values.push(value);
+    }
+
+    let resolver = SubqueryResolver { values };
+    for expr in exprs.iter_mut() {
+        resolver.rewrite_expr(expr);
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct SubqueryKindCollector {
+    kinds: HashMap<usize, SubqueryKind>,
+}
+
+impl ExprVisitor for SubqueryKindCollector {
+    fn visit_subquery(&mut self, expr: &BoundSubquery) {
+        self.kinds.insert(expr.index, expr.kind);
+    }
+}
+
+/// Replace the subquery exprs with their results as constant, the empty result is NULL.
+struct SubqueryResolver {
+    values: Vec<Option<ScalarValue>>,
+}
+
+impl ExprRewriter for SubqueryResolver {
+    fn rewrite_subquery(&self, expr: &mut BoundExpr) {
+        let subquery = match expr {
+            BoundExpr::Subquery(subquery) => subquery,
+            _ => unreachable!(),
+        };
+        let value = match &self.values[subquery.index] {
+            Some(value) => value.clone(),
+            None => ScalarValue::from(&subquery.return_type),
+        };
+        *expr = BoundExpr::Constant(value);
+    }
+}
@@ -0,0 +1,18 @@
+use arrow::record_batch::RecordBatch;
+
+use super::*;
+use crate::optimizer::TableFunctionScan;
+
+/// Output the rows computed by the table function of the constant arguments.
+pub struct TableFunctionExecutor {
+    pub plan: TableFunctionScan,
+}
+
+impl TableFunctionExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        for batch in self.plan.function().call(&self.plan.args())? {
+            yield batch;
+        }
+    }
+}
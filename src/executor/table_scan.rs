@@ -1,33 +1,121 @@
 use std::sync::Arc;
 
 use arrow::record_batch::RecordBatch;
+use tokio::sync::mpsc;
+use tokio::task;
 
 use super::*;
+use crate::catalog::TableId;
 use crate::optimizer::PhysicalTableScan;
 use crate::storage::{Storage, Table, Transaction};
 
 pub struct TableScanExecutor<S: Storage> {
     pub plan: PhysicalTableScan,
     pub storage: Arc<S>,
+    /// the number of tasks reading the table partitions concurrently, 1 means serial scan
+    pub parallelism: usize,
 }
 
 impl<S: Storage> TableScanExecutor<S> {
     #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
     pub async fn execute(self) {
         let table_id = self.plan.logical().table_id();
+        let projection = self.projection(&table_id);
         let table = self.storage.get_table(table_id)?;
-        let mut tx = table.read()?;
-        loop {
-            match tx.next_batch() {
-                Ok(batch) => {
-                    if let Some(batch) = batch {
-                        yield batch;
-                    } else {
-                        break;
+        if self.parallelism > 1 && table.partitions() > 1 {
+            #[for_await]
+            for batch in self.execute_parallel(table, projection) {
+                yield batch?;
+            }
+            return Ok(());
+        }
+        // the batches are read one by one as the stream is polled, so the rest of the table is
+        // left unread if the consumer stops early, e.g. the limit is reached
+        let mut tx = table.read_with_projection(projection)?;
+        while let Some(batch) = tx.next_batch()? {
+            yield self.project_batch(batch)?;
+        }
+    }
+
+    /// Spawn `parallelism` tasks to read the partitions of table, partition `i` is read by task
+    /// `i % parallelism`. The output streams of tasks are merged in the order of arrival, so the
+    /// order of rows is not preserved across partitions.
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    async fn execute_parallel(self, table: S::TableType, projection: Vec<usize>) {
+        let partitions = table.partitions();
+        let parallelism = self.parallelism;
+        let mut inputs = vec![];
+        for task in 0..parallelism.min(partitions) {
+            let table = table.clone();
+            let projection = projection.clone();
+            let (sender, receiver) = mpsc::channel(1);
+            inputs.push(receive_batches(receiver));
+            // the read of storage is blocking, e.g. csv file reader
+            task::spawn_blocking(move || {
+                for partition in (task..partitions).step_by(parallelism) {
+                    let mut tx = match table.read_partition(partition, projection.clone()) {
+                        Ok(tx) => tx,
+                        Err(err) => {
+                            _ = sender.blocking_send(Err(err));
+                            return;
+                        }
+                    };
+                    while let Some(batch) = tx.next_batch().transpose() {
+                        let failed = batch.is_err();
+                        // the receiver is dropped if the scan is cancelled
+                        if sender.blocking_send(batch).is_err() || failed {
+                            return;
+                        }
                     }
                 }
-                Err(err) => return Err(err.into()),
-            }
+            });
+        }
+        #[for_await]
+        for batch in (MergeExecutor { inputs }).execute() {
+            yield self.project_batch(batch?)?;
         }
     }
+
+    /// The indices of scan plan columns in the table.
+    fn projection(&self, table_id: &TableId) -> Vec<usize> {
+        let catalog = self.storage.get_catalog();
+        let column_ids = catalog
+            .tables
+            .get(table_id)
+            .map(|t| t.column_ids.clone())
+            .unwrap_or_default();
+        self.plan
+            .logical()
+            .column_ids()
+            .iter()
+            .filter_map(|id| column_ids.iter().position(|c| c == id))
+            .collect()
+    }
+
+    /// Keep the columns of scan plan, the plan may only contain a subset of table columns after
+    /// column pruning.
+    fn project_batch(&self, batch: RecordBatch) -> Result<RecordBatch, ExecutorError> {
+        let schema = batch.schema();
+        let indices = self
+            .plan
+            .logical()
+            .columns()
+            .iter()
+            .map(|c| schema.index_of(c.desc.name.as_str()))
+            .try_collect::<Vec<_>>()?;
+        if indices.len() == schema.fields().len()
+            && indices.iter().enumerate().all(|(i, &j)| i == j)
+        {
+            return Ok(batch);
+        }
+        Ok(batch.project(&indices)?)
+    }
+}
+
+/// The output stream of one scan task, which is closed after the task finished.
+#[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+async fn receive_batches(mut receiver: mpsc::Receiver<Result<RecordBatch, StorageError>>) {
+    while let Some(batch) = receiver.recv().await {
+        yield batch?;
+    }
 }
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+
+use super::*;
+use crate::util::cast_batch_to_schema;
+
+pub struct UnionExecutor {
+    pub inputs: Vec<BoxedExecutor>,
+    /// the output column types, each batch of the inputs is cast to
+    pub types: Vec<DataType>,
+}
+
+impl UnionExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        // the output columns are named after the first batch, and all nullable since the other
+        // inputs may produce NULLs.
+        let mut schema: Option<SchemaRef> = None;
+        let types = self.types;
+        for input in self.inputs {
+            #[for_await]
+            for batch in input {
+                let batch = batch?;
+                let schema = schema.get_or_insert_with(|| {
+                    let fields = batch
+                        .schema()
+                        .fields()
+                        .iter()
+                        .zip(types.iter())
+                        .map(|(f, data_type)| Field::new(f.name(), data_type.clone(), true))
+                        .collect();
+                    Arc::new(Schema::new(fields))
+                });
+                yield cast_batch_to_schema(&batch, schema.clone())?;
+            }
+        }
+    }
+}
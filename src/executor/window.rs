@@ -0,0 +1,322 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Int64Array, UInt32Array};
+use arrow::compute::{lexsort_to_indices, take, SortColumn, SortOptions};
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+
+use super::aggregate::{create_accumulator, Accumulator};
+use super::limit::row_key;
+use super::*;
+use crate::binder::{
+    AggFunc, BoundAggFunc, BoundExpr, BoundWindowFrame, BoundWindowFunc, FrameBound, FrameUnits,
+    WindowFunc,
+};
+use crate::types::{append_scalar_value_for_builder, build_scalar_value_builder, ScalarValue};
+
+pub struct WindowExecutor {
+    pub window_funcs: Vec<BoundExpr>,
+    pub child: BoxedExecutor,
+}
+
+impl WindowExecutor {
+    #[try_stream(boxed, ok = RecordBatch, error = ExecutorError)]
+    pub async fn execute(self) {
+        // window is a blocking operator, the rows of a partition may come from any input batch
+        let mut batches = vec![];
+        #[for_await]
+        for batch in self.child {
+            batches.push(batch?);
+        }
+        if batches.is_empty() {
+            return Ok(());
+        }
+        let schema = batches[0].schema();
+        let batch = RecordBatch::concat(&schema, &batches)?;
+
+        let mut fields = schema.fields().clone();
+        let mut columns = batch.columns().to_vec();
+        for expr in &self.window_funcs {
+            let func = match expr {
+                BoundExpr::WindowFunc(func) => func,
+                _ => unreachable!("window executor expects window functions"),
+            };
+            fields.push(expr.eval_field(&batch));
+            columns.push(eval_window_func(func, &batch)?);
+        }
+        let schema = Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()));
+        yield RecordBatch::try_new(schema, columns)?;
+    }
+}
+
+/// Evaluate the window function for each row. The rows are sorted by the partition keys then the
+/// order keys, so that the rows of a partition are adjacent and in window order, and the results
+/// are put back to the original row positions.
+fn eval_window_func(
+    func: &BoundWindowFunc,
+    batch: &RecordBatch,
+) -> Result<ArrayRef, ExecutorError> {
+    let partition_keys: Vec<ArrayRef> = func
+        .partition_by
+        .iter()
+        .map(|expr| expr.eval_column(batch))
+        .try_collect()?;
+    let order_keys: Vec<ArrayRef> = func
+        .order_by
+        .iter()
+        .map(|order_by| order_by.expr.eval_column(batch))
+        .try_collect()?;
+    let mut sort_columns = partition_keys
+        .iter()
+        .map(|key| SortColumn {
+            values: key.clone(),
+            options: None,
+        })
+        .collect::<Vec<_>>();
+    for (order_by, key) in func.order_by.iter().zip(order_keys.iter()) {
+        sort_columns.push(SortColumn {
+            values: key.clone(),
+            options: Some(SortOptions {
+                descending: !order_by.asc,
+                nulls_first: order_by.nulls_first,
+            }),
+        });
+    }
+    let sorted_rows = if sort_columns.is_empty() {
+        (0..batch.num_rows()).collect::<Vec<_>>()
+    } else {
+        lexsort_to_indices(&sort_columns, None)?
+            .values()
+            .iter()
+            .map(|&i| i as usize)
+            .collect()
+    };
+
+    match &func.func {
+        WindowFunc::Agg(agg) => {
+            return eval_window_agg(func, agg, batch, &partition_keys, &order_keys, &sorted_rows)
+        }
+        WindowFunc::Lag(_) | WindowFunc::Lead(_) => {
+            return eval_window_offset(func, batch, &partition_keys, &sorted_rows)
+        }
+        WindowFunc::Ntile(n) => {
+            return Ok(eval_ntile(
+                *n,
+                &partition_keys,
+                &sorted_rows,
+                batch.num_rows(),
+            ))
+        }
+        _ => {}
+    }
+
+    let mut values = vec![0; batch.num_rows()];
+    let mut last_partition: Option<Vec<ScalarValue>> = None;
+    // the peers are the rows of a partition equal on the order keys, which share the same rank
+    let mut last_peer: Option<Vec<ScalarValue>> = None;
+    let (mut row_number, mut rank, mut dense_rank) = (0, 0, 0);
+    for row in sorted_rows {
+        let partition = row_key(&partition_keys, row);
+        if last_partition.as_ref() != Some(&partition) {
+            (row_number, rank, dense_rank) = (0, 0, 0);
+            last_partition = Some(partition);
+            last_peer = None;
+        }
+        row_number += 1;
+        let peer = row_key(&order_keys, row);
+        if last_peer.as_ref() != Some(&peer) {
+            rank = row_number;
+            dense_rank += 1;
+            last_peer = Some(peer);
+        }
+        values[row] = match func.func {
+            WindowFunc::RowNumber => row_number,
+            WindowFunc::Rank => rank,
+            WindowFunc::DenseRank => dense_rank,
+            _ => unreachable!(),
+        };
+    }
+    Ok(Arc::new(Int64Array::from(values)))
+}
+
+/// Evaluate the aggregate over the frame of each row, the frame is within the partition of the row,
+/// whose rows are adjacent in the sorted rows. The frames of the following rows usually grow from
+/// the same start, e.g. `ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, so a running
+/// accumulator is only updated with the rows added to the frame. It's rebuilt when the start
+/// moves or the end shrinks, as the accumulators can't retract rows.
+fn eval_window_agg(
+    func: &BoundWindowFunc,
+    agg: &AggFunc,
+    batch: &RecordBatch,
+    partition_keys: &[ArrayRef],
+    order_keys: &[ArrayRef],
+    sorted_rows: &[usize],
+) -> Result<ArrayRef, ExecutorError> {
+    let agg_expr = BoundExpr::AggFunc(BoundAggFunc {
+        func: agg.clone(),
+        exprs: func.args.clone(),
+        return_type: func.return_type.clone(),
+        distinct: false,
+    });
+    let arg = func.args[0].eval_column(batch)?;
+    let take_rows = |rows: &[usize]| {
+        let indices = UInt32Array::from(rows.iter().map(|&row| row as u32).collect::<Vec<_>>());
+        take(arg.as_ref(), &indices, None)
+    };
+
+    let mut values = vec![ScalarValue::from(&func.return_type); batch.num_rows()];
+    for partition in adjacent_ranges(partition_keys, sorted_rows) {
+        let rows = &sorted_rows[partition];
+        let peers = adjacent_ranges(order_keys, rows);
+        // the running accumulator and the frame it has accumulated
+        let mut running: Option<(Range<usize>, Box<dyn Accumulator>)> = None;
+        let mut last: Option<(Range<usize>, ScalarValue)> = None;
+        for peer in peers {
+            for pos in peer.clone() {
+                let frame = frame_range(&func.frame, pos, rows.len(), &peer);
+                let value = match &last {
+                    Some((last_frame, value)) if *last_frame == frame => value.clone(),
+                    _ => {
+                        let growing = matches!(
+                            &running,
+                            Some((accumulated, _))
+                                if accumulated.start == frame.start
+                                    && accumulated.end <= frame.end
+                        );
+                        if !growing {
+                            let accumulator = create_accumulator(&agg_expr);
+                            running = Some((frame.start..frame.start, accumulator));
+                        }
+                        let (accumulated, accumulator) = running.as_mut().unwrap();
+                        if accumulated.end < frame.end {
+                            accumulator
+                                .update_batch(&take_rows(&rows[accumulated.end..frame.end])?)?;
+                            accumulated.end = frame.end;
+                        }
+                        accumulator.evaluate()?
+                    }
+                };
+                values[rows[pos]] = value.clone();
+                last = Some((frame, value));
+            }
+        }
+    }
+    build_array(&func.return_type, &values)
+}
+
+/// Take the value of the row at the offset before (lag) or after (lead) the current row within
+/// the partition, or the default if there is no such row.
+fn eval_window_offset(
+    func: &BoundWindowFunc,
+    batch: &RecordBatch,
+    partition_keys: &[ArrayRef],
+    sorted_rows: &[usize],
+) -> Result<ArrayRef, ExecutorError> {
+    let arg = func.args[0].eval_column(batch)?;
+    let default = func
+        .args
+        .get(1)
+        .map(|expr| expr.eval_column(batch))
+        .transpose()?;
+
+    let mut values = vec![ScalarValue::from(&func.return_type); batch.num_rows()];
+    for partition in adjacent_ranges(partition_keys, sorted_rows) {
+        let rows = &sorted_rows[partition];
+        for (pos, &row) in rows.iter().enumerate() {
+            let target = match func.func {
+                WindowFunc::Lag(offset) => pos.checked_sub(offset),
+                WindowFunc::Lead(offset) => pos.checked_add(offset).filter(|&p| p < rows.len()),
+                _ => unreachable!(),
+            };
+            values[row] = match (target, &default) {
+                (Some(target), _) => ScalarValue::try_from_array(&arg, rows[target]),
+                (None, Some(default)) => ScalarValue::try_from_array(default, row),
+                (None, None) => continue,
+            };
+        }
+    }
+    build_array(&func.return_type, &values)
+}
+
+/// Split the rows of each partition into n buckets in order, the sizes of buckets differ by at
+/// most one and the larger buckets come first, e.g. 5 rows in 3 buckets are `1 1 2 2 3`.
+fn eval_ntile(
+    n: usize,
+    partition_keys: &[ArrayRef],
+    sorted_rows: &[usize],
+    num_rows: usize,
+) -> ArrayRef {
+    let mut values = vec![0; num_rows];
+    for partition in adjacent_ranges(partition_keys, sorted_rows) {
+        let rows = &sorted_rows[partition];
+        let (size, remainder) = (rows.len() / n, rows.len() % n);
+        // the rows in the first `remainder` buckets, which have one more row than the others
+        let large_rows = remainder * (size + 1);
+        for (pos, &row) in rows.iter().enumerate() {
+            let bucket = if pos < large_rows {
+                pos / (size + 1)
+            } else {
+                remainder + (pos - large_rows) / size
+            };
+            values[row] = bucket as i64 + 1;
+        }
+    }
+    Arc::new(Int64Array::from(values))
+}
+
+fn build_array(data_type: &DataType, values: &[ScalarValue]) -> Result<ArrayRef, ExecutorError> {
+    let mut builder = build_scalar_value_builder(data_type);
+    for value in values {
+        append_scalar_value_for_builder(value, &mut builder)?;
+    }
+    Ok(builder.finish())
+}
+
+/// Split the sorted rows into the ranges of adjacent rows equal on the keys, e.g. the partitions,
+/// or the peers within a partition.
+fn adjacent_ranges(keys: &[ArrayRef], rows: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    while start < rows.len() {
+        let key = row_key(keys, rows[start]);
+        let end = (start..rows.len())
+            .find(|&i| row_key(keys, rows[i]) != key)
+            .unwrap_or(rows.len());
+        ranges.push(start..end);
+        start = end;
+    }
+    ranges
+}
+
+/// The positions in the partition of the frame of the row at `pos`, the frame is empty if the
+/// start is after the end. The current row of RANGE frame stands for its peers.
+fn frame_range(
+    frame: &BoundWindowFrame,
+    pos: usize,
+    len: usize,
+    peers: &Range<usize>,
+) -> Range<usize> {
+    let (pos, len) = (pos as i64, len as i64);
+    let offset = |n: u64| i64::try_from(n).unwrap_or(i64::MAX);
+    let start = match frame.start {
+        FrameBound::UnboundedPreceding => 0,
+        FrameBound::Preceding(n) => pos.saturating_sub(offset(n)),
+        FrameBound::CurrentRow if frame.units == FrameUnits::Range => peers.start as i64,
+        FrameBound::CurrentRow => pos,
+        FrameBound::Following(n) => pos.saturating_add(offset(n)),
+        FrameBound::UnboundedFollowing => len,
+    };
+    // the end is exclusive
+    let end = match frame.end {
+        FrameBound::UnboundedPreceding => 0,
+        FrameBound::Preceding(n) => pos.saturating_sub(offset(n)) + 1,
+        FrameBound::CurrentRow if frame.units == FrameUnits::Range => peers.end as i64,
+        FrameBound::CurrentRow => pos + 1,
+        FrameBound::Following(n) => pos.saturating_add(offset(n)).saturating_add(1),
+        FrameBound::UnboundedFollowing => len,
+    };
+    let start = start.clamp(0, len) as usize;
+    let end = end.clamp(0, len) as usize;
+    start..end.max(start)
+}
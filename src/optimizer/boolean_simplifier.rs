@@ -0,0 +1,196 @@
+use sqlparser::ast::BinaryOperator;
+
+use super::expr_rewriter::ExprRewriter;
+use super::{LogicalFilter, PlanRef, PlanRewriter, PlanTreeNode, RuleRewriter};
+use crate::binder::BoundExpr;
+use crate::types::ScalarValue;
+
+/// Simplify the boolean algebra with constants bottom-up, so that the folded constants propagate
+/// to the parents:
+/// - `x AND TRUE` -> `x`, `x AND FALSE` -> `FALSE`
+/// - `x OR TRUE` -> `TRUE`, `x OR FALSE` -> `x`
+/// - `NOT TRUE` -> `FALSE`, `NOT FALSE` -> `TRUE`, `NOT NOT x` -> `x`
+///
+/// All of them hold for NULL `x` under three-valued logic.
+pub struct BooleanSimplifier;
+
+impl ExprRewriter for BooleanSimplifier {
+    fn rewrite_binary_op(&self, expr: &mut BoundExpr) {
+        let e = match expr {
+            BoundExpr::BinaryOp(e) => e,
+            _ => unreachable!(),
+        };
+        self.rewrite_expr(&mut e.left);
+        self.rewrite_expr(&mut e.right);
+        let simplified = match (&e.op, as_bool(&e.left), as_bool(&e.right)) {
+            (BinaryOperator::And, Some(false), _) | (BinaryOperator::And, _, Some(false)) => {
+                bool_constant(false)
+            }
+            (BinaryOperator::Or, Some(true), _) | (BinaryOperator::Or, _, Some(true)) => {
+                bool_constant(true)
+            }
+            (BinaryOperator::And, Some(true), _) | (BinaryOperator::Or, Some(false), _) => {
+                (*e.right).clone()
+            }
+            (BinaryOperator::And, _, Some(true)) | (BinaryOperator::Or, _, Some(false)) => {
+                (*e.left).clone()
+            }
+            _ => return,
+        };
+        *expr = simplified;
+    }
+
+    fn rewrite_not(&self, expr: &mut BoundExpr) {
+        let e = match expr {
+            BoundExpr::Not(e) => e,
+            _ => unreachable!(),
+        };
+        self.rewrite_expr(&mut e.expr);
+        let simplified = match &*e.expr {
+            BoundExpr::Not(inner) => (*inner.expr).clone(),
+            inner => match as_bool(inner) {
+                Some(v) => bool_constant(!v),
+                None => return,
+            },
+        };
+        *expr = simplified;
+    }
+}
+
+fn as_bool(expr: &BoundExpr) -> Option<bool> {
+    match expr {
+        BoundExpr::Constant(ScalarValue::Boolean(Some(v))) => Some(*v),
+        _ => None,
+    }
+}
+
+fn bool_constant(v: bool) -> BoundExpr {
+    BoundExpr::Constant(ScalarValue::Boolean(Some(v)))
+}
+
+/// Simplify the predicate of filter by `BooleanSimplifier`, and remove the filter whose predicate
+/// is always true.
+#[derive(Default)]
+pub struct SimplifyFilterRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PlanRewriter for SimplifyFilterRewriter {
+    fn rewrite_logical_filter(&mut self, plan: &LogicalFilter) -> PlanRef {
+        let new_children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect::<Vec<_>>();
+        let mut new_expr = plan.expr();
+        BooleanSimplifier.rewrite_expr(&mut new_expr);
+        // the IN subquery filter is not only the predicate
+        if as_bool(&new_expr) == Some(true) && new_children.len() == 1 {
+            self.changed = true;
+            return new_children[0].clone();
+        }
+        self.changed |= new_expr != plan.expr();
+        plan.clone_with_expr(new_expr)
+            .clone_with_children(new_children)
+    }
+}
+
+impl RuleRewriter for SimplifyFilterRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+#[cfg(test)]
+mod boolean_simplifier_test {
+    use arrow::datatypes::DataType;
+
+    use super::*;
+    use crate::binder::{BoundBinaryOp, BoundColumnRef, BoundNot};
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+
+    fn build_column_ref(column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: ColumnCatalog {
+                table_id: "t".to_string(),
+                id: column_name.to_string(),
+                desc: ColumnDesc {
+                    name: column_name.to_string(),
+                    data_type: DataType::Boolean,
+                },
+            },
+        })
+    }
+
+    fn binary(op: BinaryOperator, left: BoundExpr, right: BoundExpr) -> BoundExpr {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+            return_type: Some(DataType::Boolean),
+        })
+    }
+
+    fn and(left: BoundExpr, right: BoundExpr) -> BoundExpr {
+        binary(BinaryOperator::And, left, right)
+    }
+
+    fn or(left: BoundExpr, right: BoundExpr) -> BoundExpr {
+        binary(BinaryOperator::Or, left, right)
+    }
+
+    fn not(expr: BoundExpr) -> BoundExpr {
+        BoundExpr::Not(BoundNot {
+            expr: Box::new(expr),
+        })
+    }
+
+    fn simplify(mut expr: BoundExpr) -> BoundExpr {
+        BooleanSimplifier.rewrite_expr(&mut expr);
+        expr
+    }
+
+    #[test]
+    fn test_simplify_constant_leaves() {
+        let a = build_column_ref("a");
+        assert_eq!(simplify(and(a.clone(), bool_constant(true))), a);
+        assert_eq!(
+            simplify(and(bool_constant(false), a.clone())),
+            bool_constant(false)
+        );
+        assert_eq!(
+            simplify(or(a.clone(), bool_constant(true))),
+            bool_constant(true)
+        );
+        assert_eq!(simplify(or(bool_constant(false), a.clone())), a);
+        assert_eq!(simplify(not(bool_constant(true))), bool_constant(false));
+        assert_eq!(simplify(not(not(a.clone()))), a);
+        // the non-constant operands are kept
+        let b = build_column_ref("b");
+        assert_eq!(simplify(and(a.clone(), b.clone())), and(a, b));
+    }
+
+    #[test]
+    fn test_simplify_nested_tree_bottom_up() {
+        let a = build_column_ref("a");
+        let b = build_column_ref("b");
+        // (a OR (b AND FALSE)) AND NOT (FALSE OR FALSE) -> a
+        let expr = and(
+            or(a.clone(), and(b.clone(), bool_constant(false))),
+            not(or(bool_constant(false), bool_constant(false))),
+        );
+        assert_eq!(simplify(expr), a);
+
+        // NOT (a AND (b OR TRUE)) -> NOT a
+        let expr = not(and(a.clone(), or(b.clone(), bool_constant(true))));
+        assert_eq!(simplify(expr), not(a.clone()));
+
+        // (a OR NOT TRUE) OR (b AND NOT NOT TRUE) -> a OR b
+        let expr = or(
+            or(a.clone(), not(bool_constant(true))),
+            and(b.clone(), not(not(bool_constant(true)))),
+        );
+        assert_eq!(simplify(expr), or(a, b));
+    }
+}
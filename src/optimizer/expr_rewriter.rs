@@ -9,6 +9,15 @@ pub trait ExprRewriter {
             BoundExpr::BinaryOp(_) => self.rewrite_binary_op(expr),
             BoundExpr::TypeCast(_) => self.rewrite_type_cast(expr),
             BoundExpr::AggFunc(_) => self.rewrite_agg_func(expr),
+            BoundExpr::Case(_) => self.rewrite_case(expr),
+            BoundExpr::InList(_) => self.rewrite_in_list(expr),
+            BoundExpr::Between(_) => self.rewrite_between(expr),
+            BoundExpr::Like(_) => self.rewrite_like(expr),
+            BoundExpr::IsNull(_) => self.rewrite_is_null(expr),
+            BoundExpr::Not(_) => self.rewrite_not(expr),
+            BoundExpr::ScalarFunc(_) => self.rewrite_scalar_func(expr),
+            BoundExpr::Subquery(_) => self.rewrite_subquery(expr),
+            BoundExpr::WindowFunc(_) => self.rewrite_window_func(expr),
         }
     }
 
@@ -18,7 +27,12 @@ pub trait ExprRewriter {
 
     fn rewrite_input_ref(&self, _: &mut BoundExpr) {}
 
-    fn rewrite_type_cast(&self, _: &mut BoundExpr) {}
+    fn rewrite_type_cast(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::TypeCast(e) => self.rewrite_expr(&mut e.expr),
+            _ => unreachable!(),
+        }
+    }
 
     fn rewrite_binary_op(&self, expr: &mut BoundExpr) {
         match expr {
@@ -40,4 +54,96 @@ pub trait ExprRewriter {
             _ => unreachable!(),
         }
     }
+
+    fn rewrite_case(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Case(e) => {
+                for (when, then) in &mut e.when_then {
+                    self.rewrite_expr(when);
+                    self.rewrite_expr(then);
+                }
+                if let Some(else_expr) = &mut e.else_expr {
+                    self.rewrite_expr(else_expr);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_in_list(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::InList(e) => {
+                self.rewrite_expr(&mut e.expr);
+                for item in &mut e.list {
+                    self.rewrite_expr(item);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_between(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Between(e) => {
+                self.rewrite_expr(&mut e.expr);
+                self.rewrite_expr(&mut e.low);
+                self.rewrite_expr(&mut e.high);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_like(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Like(e) => {
+                self.rewrite_expr(&mut e.expr);
+                self.rewrite_expr(&mut e.pattern);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_is_null(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::IsNull(e) => self.rewrite_expr(&mut e.expr),
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_not(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Not(e) => self.rewrite_expr(&mut e.expr),
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_scalar_func(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::ScalarFunc(e) => {
+                for arg in &mut e.args {
+                    self.rewrite_expr(arg);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_subquery(&self, _: &mut BoundExpr) {}
+
+    fn rewrite_window_func(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::WindowFunc(e) => {
+                for arg in &mut e.args {
+                    self.rewrite_expr(arg);
+                }
+                for key in &mut e.partition_by {
+                    self.rewrite_expr(key);
+                }
+                for order_by in &mut e.order_by {
+                    self.rewrite_expr(&mut order_by.expr);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
 }
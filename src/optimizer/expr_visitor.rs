@@ -1,5 +1,7 @@
 use crate::binder::{
-    BoundAggFunc, BoundBinaryOp, BoundColumnRef, BoundExpr, BoundInputRef, BoundTypeCast,
+    BoundAggFunc, BoundBetween, BoundBinaryOp, BoundCase, BoundColumnRef, BoundExpr, BoundInList,
+    BoundInputRef, BoundIsNull, BoundLike, BoundNot, BoundScalarFunc, BoundSubquery, BoundTypeCast,
+    BoundWindowFunc,
 };
 use crate::types::ScalarValue;
 
@@ -8,6 +10,12 @@ pub trait ExprVisitor {
 
     fn visit_expr(&mut self, expr: &BoundExpr) {
         self.pre_visit(expr);
+        self.walk_expr(expr);
+    }
+
+    /// Dispatch the expr to the visit method of its type, which is called by `visit_expr` after
+    /// `pre_visit`. It's useful when overriding `visit_expr` to skip some sub-exprs.
+    fn walk_expr(&mut self, expr: &BoundExpr) {
         match expr {
             BoundExpr::Constant(expr) => self.visit_constant(expr),
             BoundExpr::ColumnRef(expr) => self.visit_column_ref(expr),
@@ -15,6 +23,15 @@ pub trait ExprVisitor {
             BoundExpr::BinaryOp(expr) => self.visit_binary_op(expr),
             BoundExpr::TypeCast(expr) => self.visit_type_cast(expr),
             BoundExpr::AggFunc(expr) => self.visit_agg_func(expr),
+            BoundExpr::Case(expr) => self.visit_case(expr),
+            BoundExpr::InList(expr) => self.visit_in_list(expr),
+            BoundExpr::Between(expr) => self.visit_between(expr),
+            BoundExpr::Like(expr) => self.visit_like(expr),
+            BoundExpr::IsNull(expr) => self.visit_is_null(expr),
+            BoundExpr::Not(expr) => self.visit_not(expr),
+            BoundExpr::ScalarFunc(expr) => self.visit_scalar_func(expr),
+            BoundExpr::Subquery(expr) => self.visit_subquery(expr),
+            BoundExpr::WindowFunc(expr) => self.visit_window_func(expr),
         }
     }
 
@@ -38,4 +55,60 @@ pub trait ExprVisitor {
             self.visit_expr(arg);
         }
     }
+
+    fn visit_case(&mut self, expr: &BoundCase) {
+        for (when, then) in &expr.when_then {
+            self.visit_expr(when);
+            self.visit_expr(then);
+        }
+        if let Some(else_expr) = &expr.else_expr {
+            self.visit_expr(else_expr);
+        }
+    }
+
+    fn visit_in_list(&mut self, expr: &BoundInList) {
+        self.visit_expr(&expr.expr);
+        for item in &expr.list {
+            self.visit_expr(item);
+        }
+    }
+
+    fn visit_between(&mut self, expr: &BoundBetween) {
+        self.visit_expr(&expr.expr);
+        self.visit_expr(&expr.low);
+        self.visit_expr(&expr.high);
+    }
+
+    fn visit_like(&mut self, expr: &BoundLike) {
+        self.visit_expr(&expr.expr);
+        self.visit_expr(&expr.pattern);
+    }
+
+    fn visit_is_null(&mut self, expr: &BoundIsNull) {
+        self.visit_expr(&expr.expr);
+    }
+
+    fn visit_not(&mut self, expr: &BoundNot) {
+        self.visit_expr(&expr.expr);
+    }
+
+    fn visit_scalar_func(&mut self, expr: &BoundScalarFunc) {
+        for arg in &expr.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_subquery(&mut self, _: &BoundSubquery) {}
+
+    fn visit_window_func(&mut self, expr: &BoundWindowFunc) {
+        for arg in &expr.args {
+            self.visit_expr(arg);
+        }
+        for key in &expr.partition_by {
+            self.visit_expr(key);
+        }
+        for order_by in &expr.order_by {
+            self.visit_expr(&order_by.expr);
+        }
+    }
 }
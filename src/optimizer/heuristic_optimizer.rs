@@ -0,0 +1,272 @@
+use super::{PlanRef, PlanRewriter};
+
+/// A transformation of the plan tree, which tells whether it changed the plan, so that the batch
+/// finds the fixed point without comparing the trees.
+pub trait Rule {
+    fn name(&self) -> &str;
+
+    /// Transform the plan, or return `None` if the plan is unchanged.
+    fn apply(&mut self, plan: PlanRef) -> Option<PlanRef>;
+}
+
+/// The `PlanRewriter` applied as a rule, which records whether it has changed the plan. Every
+/// `RuleRewriter` is a rule named by its type name.
+pub trait RuleRewriter: PlanRewriter {
+    /// Whether the plan is changed since the last call, the record is reset then.
+    fn take_changed(&mut self) -> bool;
+}
+
+impl<T: RuleRewriter> Rule for T {
+    fn name(&self) -> &str {
+        let name = std::any::type_name::<T>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    fn apply(&mut self, plan: PlanRef) -> Option<PlanRef> {
+        self.take_changed();
+        let plan = self.rewrite(plan);
+        self.take_changed().then(|| plan)
+    }
+}
+
+/// How many times a batch applies its rules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchStrategy {
+    /// apply the rules once, for the rules which are not idempotent
+    Once,
+    /// apply the rules until the plan is unchanged or the max iterations is reached
+    FixedPoint(usize),
+}
+
+/// The named group of rules which are applied in order.
+pub struct Batch {
+    name: String,
+    strategy: BatchStrategy,
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Batch {
+    pub fn new(name: &str, strategy: BatchStrategy, rules: Vec<Box<dyn Rule>>) -> Self {
+        Self {
+            name: name.to_string(),
+            strategy,
+            rules,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn apply(&mut self, mut plan: PlanRef, fired_rules: &mut Vec<(String, usize)>) -> PlanRef {
+        let max_iterations = match self.strategy {
+            BatchStrategy::Once => 1,
+            BatchStrategy::FixedPoint(max_iterations) => max_iterations,
+        };
+        for _ in 0..max_iterations {
+            let mut changed = false;
+            for rule in self.rules.iter_mut() {
+                if let Some(new_plan) = rule.apply(plan.clone()) {
+                    plan = new_plan;
+                    changed = true;
+                    record_fired_rule(fired_rules, rule.name());
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        plan
+    }
+}
+
+/// Count the rule as fired once more, the rules keep the order they first fired in.
+fn record_fired_rule(fired_rules: &mut Vec<(String, usize)>, name: &str) {
+    match fired_rules.iter_mut().find(|(rule, _)| rule == name) {
+        Some((_, count)) => *count += 1,
+        None => fired_rules.push((name.to_string(), 1)),
+    }
+}
+
+/// The rule-based optimizer runs the batches in the registered order, which gives the control of
+/// the rules' order, e.g. the input refs must be resolved before the rules that depend on them.
+#[derive(Default)]
+pub struct Optimizer {
+    batches: Vec<Batch>,
+    /// the rules that changed the plan in the last `optimize`, with the times each one fired
+    fired_rules: Vec<(String, usize)>,
+}
+
+impl Optimizer {
+    pub fn new(batches: Vec<Batch>) -> Self {
+        Self {
+            batches,
+            fired_rules: vec![],
+        }
+    }
+
+    pub fn with_batch(mut self, batch: Batch) -> Self {
+        self.batches.push(batch);
+        self
+    }
+
+    /// Register the rule at the end of the named batch, the batch is appended to apply the rule
+    /// once if it doesn't exist.
+    pub fn add_rule(&mut self, batch_name: &str, rule: Box<dyn Rule>) {
+        match self.batches.iter_mut().find(|b| b.name() == batch_name) {
+            Some(batch) => batch.rules.push(rule),
+            None => self
+                .batches
+                .push(Batch::new(batch_name, BatchStrategy::Once, vec![rule])),
+        }
+    }
+
+    pub fn optimize(&mut self, mut plan: PlanRef) -> PlanRef {
+        self.fired_rules.clear();
+        for batch in self.batches.iter_mut() {
+            plan = batch.apply(plan, &mut self.fired_rules);
+        }
+        plan
+    }
+
+    pub fn fired_rules(&self) -> &[(String, usize)] {
+        &self.fired_rules
+    }
+}
+
+#[cfg(test)]
+mod heuristic_optimizer_test {
+    use std::sync::Arc;
+
+    use arrow::datatypes::DataType;
+
+    use super::*;
+    use crate::binder::{BoundColumnRef, BoundExpr};
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::optimizer::{
+        count_operators, InputRefRewriter, LogicalLimit, LogicalProject, LogicalTableScan,
+        MergeProjectRewriter, RemoveRedundantProjectRewriter,
+    };
+
+    fn build_column_ref(column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: ColumnCatalog {
+                table_id: "t".to_string(),
+                id: column_name.to_string(),
+                desc: ColumnDesc {
+                    name: column_name.to_string(),
+                    data_type: DataType::Int32,
+                },
+            },
+        })
+    }
+
+    /// select c1 from (select c1 from (select c1, c2 from t))
+    fn build_test_plan() -> PlanRef {
+        let columns = vec![build_column_ref("c1"), build_column_ref("c2")];
+        let catalogs = columns
+            .iter()
+            .map(|expr| match expr {
+                BoundExpr::ColumnRef(column) => column.column_catalog.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        let scan = Arc::new(LogicalTableScan::new("t".to_string(), catalogs));
+        let inner = Arc::new(LogicalProject::new(columns, scan));
+        let middle = Arc::new(LogicalProject::new(vec![build_column_ref("c1")], inner));
+        Arc::new(LogicalProject::new(vec![build_column_ref("c1")], middle))
+    }
+
+    /// Wrap the plan into a limit on every apply, which never reaches a fixed point.
+    struct AddLimit;
+
+    impl Rule for AddLimit {
+        fn name(&self) -> &str {
+            "AddLimit"
+        }
+
+        fn apply(&mut self, plan: PlanRef) -> Option<PlanRef> {
+            Some(Arc::new(LogicalLimit::new(Some(1), None, plan)))
+        }
+    }
+
+    #[test]
+    fn test_batch_applies_rules_in_order() {
+        // merging projects requires the input refs resolved by the previous rule
+        let batch = Batch::new(
+            "Merge Project",
+            BatchStrategy::Once,
+            vec![
+                Box::new(InputRefRewriter::default()),
+                Box::new(MergeProjectRewriter::default()),
+            ],
+        );
+        assert_eq!(batch.rules[1].name(), "MergeProjectRewriter");
+        let mut optimizer = Optimizer::default().with_batch(batch);
+
+        let plan = optimizer.optimize(build_test_plan());
+
+        let counts = count_operators(&plan);
+        assert_eq!(counts.get("LogicalProject"), Some(&1));
+        assert_eq!(counts.get("LogicalTableScan"), Some(&1));
+    }
+
+    #[test]
+    fn test_fixed_point_batch_stops_at_max_iterations() {
+        let mut optimizer = Optimizer::new(vec![
+            Batch::new("Once", BatchStrategy::Once, vec![Box::new(AddLimit)]),
+            Batch::new(
+                "Never Fixed",
+                BatchStrategy::FixedPoint(3),
+                vec![Box::new(AddLimit)],
+            ),
+        ]);
+
+        let plan = optimizer.optimize(build_test_plan());
+
+        assert_eq!(count_operators(&plan).get("LogicalLimit"), Some(&4));
+    }
+
+    #[test]
+    fn test_add_custom_rules() {
+        let mut optimizer = Optimizer::new(vec![Batch::new(
+            "Resolve Input Ref",
+            BatchStrategy::Once,
+            vec![Box::new(InputRefRewriter::default())],
+        )]);
+        optimizer.add_rule("Resolve Input Ref", Box::new(AddLimit));
+        optimizer.add_rule("Custom", Box::new(AddLimit));
+        assert_eq!(optimizer.batches.len(), 2);
+        assert_eq!(optimizer.batches[0].rules[1].name(), "AddLimit");
+        assert_eq!(optimizer.batches[1].name(), "Custom");
+
+        let plan = optimizer.optimize(build_test_plan());
+
+        assert_eq!(count_operators(&plan).get("LogicalLimit"), Some(&2));
+        assert_eq!(count_operators(&plan).get("LogicalProject"), Some(&3));
+    }
+
+    #[test]
+    fn test_optimizer_records_fired_rules() {
+        let mut optimizer = Optimizer::new(vec![Batch::new(
+            "Remove Redundant Project",
+            BatchStrategy::FixedPoint(10),
+            vec![
+                Box::new(InputRefRewriter::default()),
+                Box::new(RemoveRedundantProjectRewriter::default()),
+            ],
+        )]);
+
+        // the inner project outputs all the columns of the table scan
+        let plan = optimizer.optimize(build_test_plan());
+
+        assert_eq!(count_operators(&plan).get("LogicalProject"), Some(&2));
+        assert_eq!(
+            optimizer.fired_rules(),
+            &[
+                ("InputRefRewriter".to_string(), 1),
+                ("RemoveRedundantProjectRewriter".to_string(), 1)
+            ]
+        );
+    }
+}
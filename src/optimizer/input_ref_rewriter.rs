@@ -1,7 +1,12 @@
+use std::cell::Cell;
 use std::sync::Arc;
 
 use super::expr_rewriter::ExprRewriter;
-use super::{LogicalAgg, LogicalFilter, LogicalProject, LogicalTableScan, PlanRef, PlanRewriter};
+use super::{
+    LogicalAgg, LogicalFilter, LogicalJoin, LogicalLimit, LogicalOrder, LogicalProject,
+    LogicalSubqueryAlias, LogicalTableScan, LogicalUnion, LogicalWindow, PlanRef, PlanRewriter,
+    PlanTreeNode, RuleRewriter, TableFunctionScan,
+};
 use crate::binder::{BoundColumnRef, BoundExpr, BoundInputRef};
 
 #[derive(Default)]
@@ -9,16 +14,23 @@ pub struct InputRefRewriter {
     /// The bound exprs of the last visited plan node, which is used to resolve the index of
     /// RecordBatch.
     bindings: Vec<BoundExpr>,
+    /// whether the plan is changed since the last `take_changed`, which is set while rewriting
+    /// the exprs by `&self`
+    changed: Cell<bool>,
 }
 
 impl InputRefRewriter {
     fn rewrite_internal(&self, expr: &mut BoundExpr) {
         // Find input expr in bindings.
         if let Some(idx) = self.bindings.iter().position(|e| *e == expr.clone()) {
-            *expr = BoundExpr::InputRef(BoundInputRef {
+            let input_ref = BoundExpr::InputRef(BoundInputRef {
                 index: idx,
                 return_type: expr.return_type().unwrap(),
             });
+            if *expr != input_ref {
+                self.changed.set(true);
+                *expr = input_ref;
+            }
             return;
         }
 
@@ -34,6 +46,50 @@ impl InputRefRewriter {
                     self.rewrite_expr(arg);
                 }
             }
+            BoundExpr::Case(e) => {
+                for (when, then) in &mut e.when_then {
+                    self.rewrite_expr(when);
+                    self.rewrite_expr(then);
+                }
+                if let Some(else_expr) = &mut e.else_expr {
+                    self.rewrite_expr(else_expr);
+                }
+            }
+            BoundExpr::InList(e) => {
+                self.rewrite_expr(e.expr.as_mut());
+                for item in &mut e.list {
+                    self.rewrite_expr(item);
+                }
+            }
+            BoundExpr::Between(e) => {
+                self.rewrite_expr(e.expr.as_mut());
+                self.rewrite_expr(e.low.as_mut());
+                self.rewrite_expr(e.high.as_mut());
+            }
+            BoundExpr::Like(e) => {
+                self.rewrite_expr(e.expr.as_mut());
+                self.rewrite_expr(e.pattern.as_mut());
+            }
+            BoundExpr::IsNull(e) => self.rewrite_expr(e.expr.as_mut()),
+            BoundExpr::Not(e) => self.rewrite_expr(e.expr.as_mut()),
+            BoundExpr::ScalarFunc(e) => {
+                for arg in &mut e.args {
+                    self.rewrite_expr(arg);
+                }
+            }
+            // resolved by executing the subquery rather than the input
+            BoundExpr::Subquery(_) => {}
+            BoundExpr::WindowFunc(e) => {
+                for arg in &mut e.args {
+                    self.rewrite_expr(arg);
+                }
+                for key in &mut e.partition_by {
+                    self.rewrite_expr(key);
+                }
+                for order_by in &mut e.order_by {
+                    self.rewrite_expr(&mut order_by.expr);
+                }
+            }
             _ => unreachable!(
                 "unexpected expr type {:?} for InputRefRewriter, binding: {:?}",
                 expr, self.bindings
@@ -58,6 +114,38 @@ impl ExprRewriter for InputRefRewriter {
     fn rewrite_agg_func(&self, expr: &mut BoundExpr) {
         self.rewrite_internal(expr);
     }
+
+    fn rewrite_case(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_in_list(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_between(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_like(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_is_null(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_not(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_scalar_func(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
+
+    fn rewrite_window_func(&self, expr: &mut BoundExpr) {
+        self.rewrite_internal(expr);
+    }
 }
 
 impl PlanRewriter for InputRefRewriter {
@@ -74,7 +162,26 @@ impl PlanRewriter for InputRefRewriter {
         Arc::new(plan.clone())
     }
 
+    fn rewrite_table_function_scan(&mut self, plan: &TableFunctionScan) -> PlanRef {
+        self.bindings = plan
+            .columns()
+            .iter()
+            .map(|c| {
+                BoundExpr::ColumnRef(BoundColumnRef {
+                    column_catalog: c.clone(),
+                })
+            })
+            .collect();
+        Arc::new(plan.clone())
+    }
+
     fn rewrite_logical_project(&mut self, plan: &LogicalProject) -> PlanRef {
+        // rewrite the subqueries first, so that the exprs are resolved by the bindings of input
+        let new_subqueries = plan
+            .subqueries()
+            .into_iter()
+            .map(|subquery| self.rewrite(subquery))
+            .collect();
         let new_child = self.rewrite(plan.input());
 
         let bindings = plan.exprs();
@@ -85,18 +192,31 @@ impl PlanRewriter for InputRefRewriter {
         }
 
         self.bindings = bindings;
-        let new_plan = LogicalProject::new(new_exprs, new_child);
+        let new_plan = LogicalProject::new(new_exprs, new_child)
+            .with_aliases(plan.aliases())
+            .with_subqueries(new_subqueries);
         Arc::new(new_plan)
     }
 
     fn rewrite_logical_filter(&mut self, plan: &LogicalFilter) -> PlanRef {
+        // rewrite the subqueries first, so that the expr is resolved by the bindings of input
+        let new_subquery = plan.subquery().map(|subquery| self.rewrite(subquery));
+        let new_subqueries = plan
+            .subqueries()
+            .into_iter()
+            .map(|subquery| self.rewrite(subquery))
+            .collect::<Vec<_>>();
         let new_child = self.rewrite(plan.input());
 
         let mut new_expr = plan.expr();
         self.rewrite_expr(&mut new_expr);
 
-        let new_plan = LogicalFilter::new(new_expr, new_child);
-        Arc::new(new_plan)
+        let new_children = std::iter::once(new_child)
+            .chain(new_subquery)
+            .chain(new_subqueries)
+            .collect();
+        plan.clone_with_expr(new_expr)
+            .clone_with_children(new_children)
     }
 
     fn rewrite_logical_agg(&mut self, plan: &LogicalAgg) -> PlanRef {
@@ -122,6 +242,101 @@ impl PlanRewriter for InputRefRewriter {
         let new_plan = LogicalAgg::new(new_agg_funcs, new_group_exprs, new_child);
         Arc::new(new_plan)
     }
+
+    fn rewrite_logical_order(&mut self, plan: &LogicalOrder) -> PlanRef {
+        let new_child = self.rewrite(plan.input());
+
+        let mut new_order_by = plan.order_by();
+        for order_by in &mut new_order_by {
+            self.rewrite_expr(&mut order_by.expr);
+        }
+
+        let new_plan = LogicalOrder::new(new_order_by, new_child);
+        Arc::new(new_plan)
+    }
+
+    fn rewrite_logical_limit(&mut self, plan: &LogicalLimit) -> PlanRef {
+        let new_child = self.rewrite(plan.input());
+
+        let mut new_tie_keys = plan.tie_keys();
+        for expr in &mut new_tie_keys {
+            self.rewrite_expr(expr);
+        }
+
+        let new_plan =
+            LogicalLimit::new(plan.limit(), plan.offset(), new_child).with_tie_keys(new_tie_keys);
+        Arc::new(new_plan)
+    }
+
+    fn rewrite_logical_union(&mut self, plan: &LogicalUnion) -> PlanRef {
+        let mut bindings = None;
+        let mut new_inputs = vec![];
+        for input in plan.inputs() {
+            new_inputs.push(self.rewrite(input));
+            // the output columns of union are resolved by the first input
+            bindings.get_or_insert_with(|| self.bindings.clone());
+        }
+        self.bindings = bindings.unwrap_or_default();
+        plan.clone_with_children(new_inputs)
+    }
+
+    fn rewrite_logical_join(&mut self, plan: &LogicalJoin) -> PlanRef {
+        let new_left = self.rewrite(plan.left());
+        let left_bindings = std::mem::take(&mut self.bindings);
+        let new_right = self.rewrite(plan.right());
+        let right_bindings = std::mem::take(&mut self.bindings);
+
+        // the keys of each side are resolved by the bindings of that side
+        let mut new_on = plan.on();
+        self.bindings = left_bindings;
+        for (left_key, _) in &mut new_on {
+            self.rewrite_expr(left_key);
+        }
+        let mut bindings = std::mem::replace(&mut self.bindings, right_bindings);
+        for (_, right_key) in &mut new_on {
+            self.rewrite_expr(right_key);
+        }
+
+        // the output is the left columns followed by the right columns
+        bindings.append(&mut self.bindings);
+        self.bindings = bindings;
+        Arc::new(LogicalJoin::new(
+            new_left,
+            new_right,
+            plan.join_type(),
+            new_on,
+        ))
+    }
+
+    fn rewrite_logical_window(&mut self, plan: &LogicalWindow) -> PlanRef {
+        let new_child = self.rewrite(plan.input());
+
+        let mut new_window_funcs = plan.window_funcs();
+        for expr in &mut new_window_funcs {
+            self.rewrite_expr(expr);
+        }
+
+        // the output is the input columns followed by the window functions
+        self.bindings.extend(plan.window_funcs());
+        Arc::new(LogicalWindow::new(new_window_funcs, new_child))
+    }
+
+    fn rewrite_logical_subquery_alias(&mut self, plan: &LogicalSubqueryAlias) -> PlanRef {
+        let new_child = self.rewrite(plan.input());
+        // the outer query references the subquery outputs by the columns of alias
+        self.bindings = plan
+            .columns()
+            .into_iter()
+            .map(|c| BoundExpr::ColumnRef(BoundColumnRef { column_catalog: c }))
+            .collect();
+        plan.clone_with_children(vec![new_child])
+    }
+}
+
+impl RuleRewriter for InputRefRewriter {
+    fn take_changed(&mut self) -> bool {
+        self.changed.take()
+    }
 }
 
 #[cfg(test)]
@@ -130,12 +345,13 @@ mod input_ref_rewriter_test {
     use sqlparser::ast::BinaryOperator;
 
     use super::*;
-    use crate::binder::{AggFunc, BoundAggFunc, BoundBinaryOp};
+    use crate::binder::{AggFunc, BoundAggFunc, BoundBinaryOp, JoinType};
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::types::ScalarValue;
 
     fn build_test_column(column_name: String) -> ColumnCatalog {
         ColumnCatalog {
+            table_id: "t".to_string(),
             id: column_name.clone(),
             desc: ColumnDesc {
                 name: column_name,
@@ -144,6 +360,19 @@ mod input_ref_rewriter_test {
         }
     }
 
+    fn build_test_column_of(table_id: &str, column_name: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: table_id.to_string(),
+            ..build_test_column(column_name.to_string())
+        }
+    }
+
+    fn build_test_column_ref_of(table_id: &str, column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column_of(table_id, column_name),
+        })
+    }
+
     fn build_logical_table_scan() -> LogicalTableScan {
         LogicalTableScan::new(
             "t".to_string(),
@@ -184,6 +413,7 @@ mod input_ref_rewriter_test {
                 column_catalog: build_test_column("c1".to_string()),
             })],
             return_type: DataType::Int32,
+            distinct: false,
         });
         let simple_agg = LogicalAgg::new(vec![expr.clone()], vec![], input);
         LogicalProject::new(vec![expr], Arc::new(simple_agg))
@@ -235,4 +465,57 @@ mod input_ref_rewriter_test {
             })]
         );
     }
+
+    #[test]
+    fn test_rewrite_join_column_ref_to_input_ref() {
+        // select u.c2, t.c2 from t join u on t.c1 = u.c1
+        let left = LogicalTableScan::new(
+            "t".to_string(),
+            vec![
+                build_test_column_of("t", "c1"),
+                build_test_column_of("t", "c2"),
+            ],
+        );
+        let right = LogicalTableScan::new(
+            "u".to_string(),
+            vec![
+                build_test_column_of("u", "c1"),
+                build_test_column_of("u", "c2"),
+            ],
+        );
+        let join = LogicalJoin::new(
+            Arc::new(left),
+            Arc::new(right),
+            JoinType::Inner,
+            vec![(
+                build_test_column_ref_of("t", "c1"),
+                build_test_column_ref_of("u", "c1"),
+            )],
+        );
+        let project = LogicalProject::new(
+            vec![
+                build_test_column_ref_of("u", "c2"),
+                build_test_column_ref_of("t", "c2"),
+            ],
+            Arc::new(join),
+        );
+
+        let mut rewriter = InputRefRewriter::default();
+        let new_plan = rewriter.rewrite(Arc::new(project));
+
+        let input_ref = |index| {
+            BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: DataType::Int32,
+            })
+        };
+        assert_eq!(
+            new_plan.as_logical_project().unwrap().exprs(),
+            vec![input_ref(3), input_ref(1)]
+        );
+        assert_eq!(
+            new_plan.children()[0].as_logical_join().unwrap().on(),
+            vec![(input_ref(0), input_ref(0))]
+        );
+    }
 }
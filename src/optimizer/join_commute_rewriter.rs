@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::join_reorder_rewriter::estimate_cardinality;
+use super::{LogicalJoin, PlanRef, PlanRewriter, PlanTreeNode, RuleRewriter};
+use crate::binder::JoinType;
+use crate::catalog::TableId;
+
+/// Swap the children of inner join if the right one is estimated larger, so that the smaller side
+/// becomes the build side of hash join, which is the right child.
+///
+/// Like `JoinReorderRewriter`, the rewriter should run before `InputRefRewriter`, the swapped
+/// join outputs the columns of the right child first, which are still referenced by column ref.
+pub struct JoinCommuteRewriter {
+    /// the estimated number of rows of each table
+    cardinalities: HashMap<TableId, usize>,
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl JoinCommuteRewriter {
+    pub fn new(cardinalities: HashMap<TableId, usize>) -> Self {
+        Self {
+            cardinalities,
+            changed: false,
+        }
+    }
+}
+
+impl PlanRewriter for JoinCommuteRewriter {
+    fn rewrite_logical_join(&mut self, plan: &LogicalJoin) -> PlanRef {
+        let left = self.rewrite(plan.left());
+        let right = self.rewrite(plan.right());
+        let swap = plan.join_type() == JoinType::Inner
+            && estimate_cardinality(&right, &self.cardinalities)
+                > estimate_cardinality(&left, &self.cardinalities);
+        if !swap {
+            return plan.clone_with_children(vec![left, right]);
+        }
+        self.changed = true;
+        let on = plan.on().into_iter().map(|(l, r)| (r, l)).collect();
+        Arc::new(LogicalJoin::new(right, left, JoinType::Inner, on))
+    }
+}
+
+impl RuleRewriter for JoinCommuteRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+#[cfg(test)]
+mod join_commute_rewriter_test {
+    use std::sync::Arc;
+
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    use super::*;
+    use crate::binder::{Binder, BoundExpr};
+    use crate::optimizer::{PhysicalRewriter, PlanNode};
+    use crate::parser::parse;
+    use crate::planner::Planner;
+    use crate::storage::{InMemoryStorage, Storage};
+
+    fn build_table(num_rows: i64) -> Vec<RecordBatch> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let ids = Int64Array::from((0..num_rows).collect::<Vec<_>>());
+        vec![RecordBatch::try_new(schema, vec![Arc::new(ids)]).unwrap()]
+    }
+
+    #[test]
+    fn test_join_commute_builds_on_smaller_table() {
+        let storage = Arc::new(InMemoryStorage::new());
+        storage
+            .create_mem_table("tiny".to_string(), build_table(2))
+            .unwrap();
+        storage
+            .create_mem_table("large".to_string(), build_table(100))
+            .unwrap();
+
+        let stats = parse("select tiny.id from large join tiny on large.id = tiny.id").unwrap();
+        let mut binder = Binder::new(storage.clone());
+        let bound_stmt = binder.bind(&stats[0]).unwrap();
+        let plan = Planner::default().plan(bound_stmt).unwrap();
+
+        let mut rewriter = JoinCommuteRewriter::new(storage.table_cardinalities());
+        let plan = rewriter.rewrite(plan);
+        let mut physical_rewriter = PhysicalRewriter {};
+        let plan = physical_rewriter.rewrite(plan);
+
+        // project -> hash join, whose right child is the build side
+        let join = plan.children()[0].clone();
+        let join = join.as_physical_hash_join().unwrap().logical();
+        assert_eq!(join.right().schema()[0].table_id, "tiny");
+        assert_eq!(join.left().schema()[0].table_id, "large");
+        // the keys are swapped along with the children
+        let table_ids = match join.on()[0].clone() {
+            (BoundExpr::ColumnRef(l), BoundExpr::ColumnRef(r)) => {
+                (l.column_catalog.table_id, r.column_catalog.table_id)
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(table_ids, ("large".to_string(), "tiny".to_string()));
+    }
+}
@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::BinaryOperator;
+
+use super::{
+    ExprVisitor, LogicalFilter, LogicalJoin, PlanNode, PlanRef, PlanRewriter, PlanTreeNode,
+    RuleRewriter,
+};
+use crate::binder::{BoundBinaryOp, BoundColumnRef, BoundExpr, JoinType};
+use crate::catalog::TableId;
+
+/// the cardinality of the table without statistics
+const DEFAULT_CARDINALITY: usize = 1000;
+
+/// Estimate the cardinality of the plan by the table it scans, the plan above the scan, e.g.
+/// filter, is assumed to keep all rows, and the join takes the estimation of its left child.
+pub(super) fn estimate_cardinality(
+    plan: &PlanRef,
+    cardinalities: &HashMap<TableId, usize>,
+) -> usize {
+    if let Ok(scan) = plan.as_logical_table_scan() {
+        return cardinalities
+            .get(&scan.table_id())
+            .cloned()
+            .unwrap_or(DEFAULT_CARDINALITY);
+    }
+    plan.children()
+        .first()
+        .map(|child| estimate_cardinality(child, cardinalities))
+        .unwrap_or(DEFAULT_CARDINALITY)
+}
+
+/// Reorder the tree of inner and cross joins into a left-deep tree, which greedily starts from the
+/// smallest relation, then joins the smallest relation connected by the join keys, and falls back
+/// to the smallest one of the rest if none is connected.
+///
+/// The rewriter should run before `InputRefRewriter`, the join keys are still column refs so they
+/// can be reassigned to the join where both sides are available. The reordered join outputs the
+/// columns in another order, which is fine as the project above references them by column ref.
+pub struct JoinReorderRewriter {
+    /// the estimated number of rows of each table
+    cardinalities: HashMap<TableId, usize>,
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl JoinReorderRewriter {
+    pub fn new(cardinalities: HashMap<TableId, usize>) -> Self {
+        Self {
+            cardinalities,
+            changed: false,
+        }
+    }
+}
+
+/// Flatten the reorderable joins into the relations and the join keys among them.
+fn flatten_joins(
+    plan: &PlanRef,
+    relations: &mut Vec<PlanRef>,
+    on: &mut Vec<(BoundExpr, BoundExpr)>,
+) {
+    match plan.as_logical_join() {
+        Ok(join) if matches!(join.join_type(), JoinType::Inner | JoinType::Cross) => {
+            flatten_joins(&join.left(), relations, on);
+            flatten_joins(&join.right(), relations, on);
+            on.extend(join.on());
+        }
+        _ => relations.push(plan.clone()),
+    }
+}
+
+/// Whether the trees of reorderable joins are of the same shape, join types and keys, the
+/// relations at the leaves are not compared.
+fn same_joins(old: &PlanRef, new: &PlanRef) -> bool {
+    let reorderable = |plan: &PlanRef| match plan.as_logical_join() {
+        Ok(join) if matches!(join.join_type(), JoinType::Inner | JoinType::Cross) => {
+            Some(join.clone())
+        }
+        _ => None,
+    };
+    match (reorderable(old), reorderable(new)) {
+        (Some(old), Some(new)) => {
+            old.join_type() == new.join_type()
+                && old.on() == new.on()
+                && same_joins(&old.left(), &new.left())
+                && same_joins(&old.right(), &new.right())
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+pub(super) fn table_ids_of_plan(plan: &PlanRef) -> HashSet<TableId> {
+    plan.schema().into_iter().map(|c| c.table_id).collect()
+}
+
+pub(super) fn table_ids_of_expr(expr: &BoundExpr) -> HashSet<TableId> {
+    let mut collector = TableIdCollector::default();
+    collector.visit_expr(expr);
+    collector.table_ids
+}
+
+#[derive(Default)]
+struct TableIdCollector {
+    table_ids: HashSet<TableId>,
+}
+
+impl ExprVisitor for TableIdCollector {
+    fn visit_column_ref(&mut self, expr: &BoundColumnRef) {
+        self.table_ids.insert(expr.column_catalog.table_id.clone());
+    }
+}
+
+impl PlanRewriter for JoinReorderRewriter {
+    fn rewrite_logical_join(&mut self, plan: &LogicalJoin) -> PlanRef {
+        let mut relations = vec![];
+        let mut on = vec![];
+        let plan: PlanRef = Arc::new(plan.clone());
+        flatten_joins(&plan, &mut relations, &mut on);
+        let relations = relations
+            .into_iter()
+            .map(|relation| self.rewrite(relation))
+            .collect::<Vec<_>>();
+
+        let cardinalities = relations
+            .iter()
+            .map(|relation| estimate_cardinality(relation, &self.cardinalities))
+            .collect::<Vec<_>>();
+        let relation_ids = relations.iter().map(table_ids_of_plan).collect::<Vec<_>>();
+        let mut keys = on
+            .into_iter()
+            .map(|(l, r)| {
+                let (l_ids, r_ids) = (table_ids_of_expr(&l), table_ids_of_expr(&r));
+                (l, r, l_ids, r_ids)
+            })
+            .collect::<Vec<_>>();
+
+        // the smallest one goes first, the ties keep the original order
+        let mut remaining = (0..relations.len()).collect::<Vec<_>>();
+        let first = *remaining.iter().min_by_key(|&&i| cardinalities[i]).unwrap();
+        remaining.retain(|&i| i != first);
+        let mut joined_ids = relation_ids[first].clone();
+        let mut new_plan = relations[first].clone();
+        let mut order = vec![first];
+
+        while !remaining.is_empty() {
+            let connected = |i: usize| {
+                keys.iter().any(|(_, _, l_ids, r_ids)| {
+                    (l_ids.is_subset(&joined_ids) && r_ids.is_subset(&relation_ids[i]))
+                        || (r_ids.is_subset(&joined_ids) && l_ids.is_subset(&relation_ids[i]))
+                })
+            };
+            let next = remaining
+                .iter()
+                .cloned()
+                .filter(|&i| connected(i))
+                .min_by_key(|&i| cardinalities[i])
+                .or_else(|| remaining.iter().cloned().min_by_key(|&i| cardinalities[i]))
+                .unwrap();
+            remaining.retain(|&i| i != next);
+            order.push(next);
+
+            // take the keys available once the next relation is joined, oriented to the sides
+            let mut join_on = vec![];
+            let mut rest = vec![];
+            for (l, r, l_ids, r_ids) in keys {
+                if l_ids.is_subset(&joined_ids) && r_ids.is_subset(&relation_ids[next]) {
+                    join_on.push((l, r));
+                } else if r_ids.is_subset(&joined_ids) && l_ids.is_subset(&relation_ids[next]) {
+                    join_on.push((r, l));
+                } else {
+                    rest.push((l, r, l_ids, r_ids));
+                }
+            }
+            keys = rest;
+
+            let join_type = match join_on.is_empty() {
+                true => JoinType::Cross,
+                false => JoinType::Inner,
+            };
+            new_plan = Arc::new(LogicalJoin::new(
+                new_plan,
+                relations[next].clone(),
+                join_type,
+                join_on,
+            ));
+            joined_ids.extend(relation_ids[next].iter().cloned());
+        }
+
+        // the keys never assigned to a join step, e.g. `a join b on a.x = a.y` whose both sides are
+        // of one relation, are kept as the filter above the joins
+        let binary = |op, left, right| {
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+                return_type: Some(DataType::Boolean),
+            })
+        };
+        let predicate = keys
+            .into_iter()
+            .map(|(l, r, _, _)| binary(BinaryOperator::Eq, l, r))
+            .reduce(|acc, expr| binary(BinaryOperator::And, acc, expr));
+
+        // the changes inside the relations are recorded by the rewrites of them
+        let reordered = order.iter().enumerate().any(|(i, relation)| i != *relation);
+        self.changed |= reordered || predicate.is_some() || !same_joins(&plan, &new_plan);
+        match predicate {
+            Some(predicate) => Arc::new(LogicalFilter::new(predicate, new_plan)),
+            None => new_plan,
+        }
+    }
+}
+
+impl RuleRewriter for JoinReorderRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+#[cfg(test)]
+mod join_reorder_rewriter_test {
+    use super::*;
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::optimizer::LogicalTableScan;
+
+    fn build_test_column(table_id: &str, column_name: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: table_id.to_string(),
+            id: column_name.to_string(),
+            desc: ColumnDesc {
+                name: column_name.to_string(),
+                data_type: DataType::Int32,
+            },
+        }
+    }
+
+    fn build_test_column_ref(table_id: &str, column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column(table_id, column_name),
+        })
+    }
+
+    fn build_table_scan(table_id: &str) -> PlanRef {
+        Arc::new(LogicalTableScan::new(
+            table_id.to_string(),
+            vec![
+                build_test_column(table_id, "a"),
+                build_test_column(table_id, "b"),
+            ],
+        ))
+    }
+
+    /// the table ids of the left-deep join tree from the leftmost
+    fn join_order(plan: &PlanRef) -> Vec<TableId> {
+        match plan.as_logical_join() {
+            Ok(join) => {
+                let mut order = join_order(&join.left());
+                order.extend(join_order(&join.right()));
+                order
+            }
+            Err(_) => vec![plan.as_logical_table_scan().unwrap().table_id()],
+        }
+    }
+
+    #[test]
+    fn test_join_reorder_puts_smallest_relation_first() {
+        // (big join small on big.a = small.a) join mid on small.b = mid.b
+        let plan: PlanRef = Arc::new(LogicalJoin::new(
+            Arc::new(LogicalJoin::new(
+                build_table_scan("big"),
+                build_table_scan("small"),
+                JoinType::Inner,
+                vec![(
+                    build_test_column_ref("big", "a"),
+                    build_test_column_ref("small", "a"),
+                )],
+            )),
+            build_table_scan("mid"),
+            JoinType::Inner,
+            vec![(
+                build_test_column_ref("small", "b"),
+                build_test_column_ref("mid", "b"),
+            )],
+        ));
+        let cardinalities = HashMap::from([
+            ("big".to_string(), 100000),
+            ("small".to_string(), 10),
+            ("mid".to_string(), 1000),
+        ]);
+
+        let mut rewriter = JoinReorderRewriter::new(cardinalities);
+        let new_plan = rewriter.rewrite(plan);
+        assert_eq!(join_order(&new_plan), vec!["small", "mid", "big"]);
+
+        // the keys are reassigned to the joins, and oriented to the sides
+        let top = new_plan.as_logical_join().unwrap();
+        assert_eq!(
+            top.on(),
+            vec![(
+                build_test_column_ref("small", "a"),
+                build_test_column_ref("big", "a"),
+            )]
+        );
+        let bottom = top.left();
+        let bottom = bottom.as_logical_join().unwrap();
+        assert_eq!(bottom.join_type(), JoinType::Inner);
+        assert_eq!(
+            bottom.on(),
+            vec![(
+                build_test_column_ref("small", "b"),
+                build_test_column_ref("mid", "b"),
+            )]
+        );
+        assert_eq!(new_plan.schema().len(), 6);
+    }
+
+    #[test]
+    fn test_join_reorder_avoids_cross_product() {
+        // big.a = small.a and big.b = mid.b, the mid is not connected to small
+        let plan: PlanRef = Arc::new(LogicalJoin::new(
+            Arc::new(LogicalJoin::new(
+                build_table_scan("big"),
+                build_table_scan("small"),
+                JoinType::Inner,
+                vec![(
+                    build_test_column_ref("big", "a"),
+                    build_test_column_ref("small", "a"),
+                )],
+            )),
+            build_table_scan("mid"),
+            JoinType::Inner,
+            vec![(
+                build_test_column_ref("big", "b"),
+                build_test_column_ref("mid", "b"),
+            )],
+        ));
+        let cardinalities = HashMap::from([
+            ("big".to_string(), 100000),
+            ("small".to_string(), 10),
+            ("mid".to_string(), 1000),
+        ]);
+
+        let mut rewriter = JoinReorderRewriter::new(cardinalities);
+        let new_plan = rewriter.rewrite(plan);
+        assert_eq!(join_order(&new_plan), vec!["small", "big", "mid"]);
+        let top = new_plan.as_logical_join().unwrap();
+        assert_eq!(top.join_type(), JoinType::Inner);
+    }
+
+    #[test]
+    fn test_join_reorder_keeps_unassigned_keys_as_filter() {
+        // big join small on big.a = big.b, both sides of the key are of big
+        let key = (
+            build_test_column_ref("big", "a"),
+            build_test_column_ref("big", "b"),
+        );
+        let plan: PlanRef = Arc::new(LogicalJoin::new(
+            build_table_scan("big"),
+            build_table_scan("small"),
+            JoinType::Inner,
+            vec![key.clone()],
+        ));
+        let cardinalities = HashMap::from([("big".to_string(), 100000), ("small".to_string(), 10)]);
+
+        let mut rewriter = JoinReorderRewriter::new(cardinalities);
+        let new_plan = rewriter.rewrite(plan);
+
+        let filter = new_plan.as_logical_filter().unwrap();
+        assert_eq!(
+            filter.expr(),
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Eq,
+                left: Box::new(key.0),
+                right: Box::new(key.1),
+                return_type: Some(DataType::Boolean),
+            })
+        );
+        let join = filter.input();
+        let join = join.as_logical_join().unwrap();
+        assert_eq!(join.join_type(), JoinType::Cross);
+        assert_eq!(join_order(&filter.input()), vec!["small", "big"]);
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use itertools::Itertools;
+
+use super::expr_rewriter::ExprRewriter;
+use super::push_limit_through_project_rewriter::is_deterministic_expr;
+use super::{ExprVisitor, LogicalProject, PlanRef, PlanRewriter, RuleRewriter};
+use crate::binder::{BoundColumnRef, BoundExpr, BoundInputRef};
+
+/// Collapse `Project(Project(child))` into a single `Project(child)`.
+///
+/// The rewriter should run after `InputRefRewriter`, the outer project exprs reference the inner
+/// project outputs by index, so we substitute each `InputRef` with the referenced inner expr.
+/// Non-deterministic inner exprs such as `random()` are never inlined, otherwise every reference
+/// would be evaluated separately and yield a different value.
+#[derive(Default)]
+pub struct MergeProjectRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PlanRewriter for MergeProjectRewriter {
+    fn rewrite_logical_project(&mut self, plan: &LogicalProject) -> PlanRef {
+        let new_children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect_vec();
+        let new_child = new_children[0].clone();
+        let inner = match new_child.as_logical_project() {
+            // the subqueries are referenced by the index in their own project
+            Ok(inner) if inner.subqueries().is_empty() && plan.subqueries().is_empty() => {
+                inner.clone()
+            }
+            _ => return plan.clone_with_children(new_children),
+        };
+
+        // only merge when all outer exprs reference the inner project outputs
+        let inner_exprs = inner.exprs();
+        let mut checker = InputRefChecker {
+            bound: inner_exprs.len(),
+            referenced: vec![],
+            valid: true,
+        };
+        for expr in plan.exprs().iter() {
+            checker.visit_expr(expr);
+        }
+        if !checker.valid
+            || checker
+                .referenced
+                .iter()
+                .any(|index| !is_deterministic_expr(&inner_exprs[*index]))
+        {
+            return plan.clone_with_children(new_children);
+        }
+
+        self.changed = true;
+        let substitutor = InputRefSubstitutor { exprs: inner_exprs };
+        let mut new_exprs = plan.exprs();
+        for expr in &mut new_exprs {
+            substitutor.rewrite_expr(expr);
+        }
+        Arc::new(LogicalProject::new(new_exprs, inner.input()).with_aliases(plan.aliases()))
+    }
+}
+
+impl RuleRewriter for MergeProjectRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+struct InputRefChecker {
+    bound: usize,
+    /// the inner project outputs referenced by the outer exprs
+    referenced: Vec<usize>,
+    valid: bool,
+}
+
+impl ExprVisitor for InputRefChecker {
+    fn visit_column_ref(&mut self, _: &BoundColumnRef) {
+        self.valid = false;
+    }
+
+    fn visit_input_ref(&mut self, expr: &BoundInputRef) {
+        if expr.index >= self.bound {
+            self.valid = false;
+        } else {
+            self.referenced.push(expr.index);
+        }
+    }
+}
+
+struct InputRefSubstitutor {
+    /// the inner project exprs
+    exprs: Vec<BoundExpr>,
+}
+
+impl ExprRewriter for InputRefSubstitutor {
+    fn rewrite_input_ref(&self, expr: &mut BoundExpr) {
+        let index = match expr {
+            BoundExpr::InputRef(e) => e.index,
+            _ => unreachable!(),
+        };
+        *expr = self.exprs[index].clone();
+    }
+}
+
+#[cfg(test)]
+mod merge_project_rewriter_test {
+    use arrow::datatypes::DataType;
+    use sqlparser::ast::BinaryOperator;
+
+    use super::*;
+    use crate::binder::{BoundBinaryOp, BoundScalarFunc, ScalarFunc};
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::optimizer::{InputRefRewriter, LogicalTableScan, PlanNodeType};
+    use crate::types::{ScalarValue, SessionRng};
+
+    fn build_test_column(column_name: String) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: "t".to_string(),
+            id: column_name.clone(),
+            desc: ColumnDesc {
+                name: column_name,
+                data_type: DataType::Int32,
+            },
+        }
+    }
+
+    fn build_column_ref(column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column(column_name.to_string()),
+        })
+    }
+
+    fn count_project_nodes(plan: &PlanRef) -> usize {
+        let current = (plan.node_type() == PlanNodeType::LogicalProject) as usize;
+        current
+            + plan
+                .children()
+                .iter()
+                .map(count_project_nodes)
+                .sum::<usize>()
+    }
+
+    #[test]
+    fn test_merge_nested_projects() {
+        let scan = LogicalTableScan::new(
+            "t".to_string(),
+            vec![
+                build_test_column("c1".to_string()),
+                build_test_column("c2".to_string()),
+            ],
+        );
+        let c1_plus_one = BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Plus,
+            left: Box::new(build_column_ref("c1")),
+            right: Box::new(BoundExpr::Constant(ScalarValue::Int32(Some(1)))),
+            return_type: Some(DataType::Int32),
+        });
+        let inner = LogicalProject::new(
+            vec![build_column_ref("c2"), c1_plus_one.clone()],
+            Arc::new(scan),
+        );
+        let outer = LogicalProject::new(vec![c1_plus_one], Arc::new(inner));
+        let plan = InputRefRewriter::default().rewrite(Arc::new(outer));
+        assert_eq!(count_project_nodes(&plan), 2);
+
+        let new_plan = MergeProjectRewriter::default().rewrite(plan);
+
+        assert_eq!(count_project_nodes(&new_plan), 1);
+        assert_eq!(
+            new_plan.children()[0].node_type(),
+            PlanNodeType::LogicalTableScan
+        );
+        assert_eq!(
+            new_plan.as_logical_project().unwrap().exprs(),
+            vec![BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Plus,
+                left: Box::new(BoundExpr::InputRef(BoundInputRef {
+                    index: 0,
+                    return_type: DataType::Int32,
+                })),
+                right: Box::new(BoundExpr::Constant(ScalarValue::Int32(Some(1)))),
+                return_type: Some(DataType::Int32),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_keep_projects_with_non_deterministic_inner_expr() {
+        // select r, r + 1 from (select random() as r from t)
+        let scan =
+            LogicalTableScan::new("t".to_string(), vec![build_test_column("c1".to_string())]);
+        let inner = LogicalProject::new(
+            vec![BoundExpr::ScalarFunc(BoundScalarFunc {
+                func: ScalarFunc::Random(SessionRng::default()),
+                args: vec![],
+                return_type: DataType::Float64,
+            })],
+            Arc::new(scan),
+        );
+        let r = BoundExpr::InputRef(BoundInputRef {
+            index: 0,
+            return_type: DataType::Float64,
+        });
+        let r_plus_one = BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Plus,
+            left: Box::new(r.clone()),
+            right: Box::new(BoundExpr::Constant(ScalarValue::Float64(Some(1.0)))),
+            return_type: Some(DataType::Float64),
+        });
+        let outer = LogicalProject::new(vec![r, r_plus_one], Arc::new(inner));
+
+        let new_plan = MergeProjectRewriter::default().rewrite(Arc::new(outer));
+
+        assert_eq!(count_project_nodes(&new_plan), 2);
+    }
+}
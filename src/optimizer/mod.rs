@@ -1,14 +1,37 @@
+mod boolean_simplifier;
 mod expr_rewriter;
 mod expr_visitor;
+mod heuristic_optimizer;
 mod input_ref_rewriter;
+mod join_commute_rewriter;
+mod join_reorder_rewriter;
+mod merge_project_rewriter;
 mod physical_rewriter;
+mod plan_counter;
 mod plan_node;
 mod plan_rewriter;
 mod plan_visitor;
+mod prune_agg_rewriter;
+mod push_agg_through_union_rewriter;
+mod push_filter_through_join_rewriter;
+mod push_limit_through_project_rewriter;
+mod remove_redundant_project_rewriter;
 
+pub use boolean_simplifier::*;
+pub use expr_rewriter::*;
 pub use expr_visitor::*;
+pub use heuristic_optimizer::*;
 pub use input_ref_rewriter::*;
+pub use join_commute_rewriter::*;
+pub use join_reorder_rewriter::*;
+pub use merge_project_rewriter::*;
 pub use physical_rewriter::*;
+pub use plan_counter::*;
 pub use plan_node::*;
 pub use plan_rewriter::*;
 pub use plan_visitor::*;
+pub use prune_agg_rewriter::*;
+pub use push_agg_through_union_rewriter::*;
+pub use push_filter_through_join_rewriter::*;
+pub use push_limit_through_project_rewriter::*;
+pub use remove_redundant_project_rewriter::*;
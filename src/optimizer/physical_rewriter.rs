@@ -2,9 +2,13 @@ use std::sync::Arc;
 
 use super::plan_rewriter::PlanRewriter;
 use super::{
-    LogicalAgg, LogicalFilter, LogicalProject, LogicalTableScan, PhysicalHashAgg,
-    PhysicalSimpleAgg, PhysicalTableScan, PlanRef, PlanTreeNode,
+    LogicalAgg, LogicalExplain, LogicalFilter, LogicalJoin, LogicalLimit, LogicalOrder,
+    LogicalProject, LogicalSubqueryAlias, LogicalTableScan, LogicalUnion, LogicalWindow,
+    PhysicalCrossJoin, PhysicalExplain, PhysicalHashAgg, PhysicalHashJoin, PhysicalLimit,
+    PhysicalOrder, PhysicalSimpleAgg, PhysicalTableScan, PhysicalUnion, PhysicalWindow, PlanRef,
+    PlanTreeNode,
 };
+use crate::binder::{BoundExpr, BoundInputRef, JoinType};
 use crate::optimizer::{PhysicalFilter, PhysicalProject};
 
 pub struct PhysicalRewriter {}
@@ -15,16 +19,24 @@ impl PlanRewriter for PhysicalRewriter {
     }
 
     fn rewrite_logical_project(&mut self, plan: &LogicalProject) -> PlanRef {
-        let child = self.rewrite(plan.children().first().unwrap().clone());
-        let logical = plan.clone_with_children([child].to_vec());
+        let children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect();
+        let logical = plan.clone_with_children(children);
         Arc::new(PhysicalProject::new(
             logical.as_logical_project().unwrap().clone(),
         ))
     }
 
     fn rewrite_logical_filter(&mut self, plan: &LogicalFilter) -> PlanRef {
-        let child = self.rewrite(plan.children().first().unwrap().clone());
-        let logical = plan.clone_with_children([child].to_vec());
+        let children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect();
+        let logical = plan.clone_with_children(children);
         Arc::new(PhysicalFilter::new(
             logical.as_logical_filter().unwrap().clone(),
         ))
@@ -44,6 +56,82 @@ impl PlanRewriter for PhysicalRewriter {
             ))
         }
     }
+
+    fn rewrite_logical_order(&mut self, plan: &LogicalOrder) -> PlanRef {
+        let child = self.rewrite(plan.children().first().unwrap().clone());
+        let logical = plan.clone_with_children([child].to_vec());
+        Arc::new(PhysicalOrder::new(
+            logical.as_logical_order().unwrap().clone(),
+        ))
+    }
+
+    fn rewrite_logical_window(&mut self, plan: &LogicalWindow) -> PlanRef {
+        let child = self.rewrite(plan.input());
+        let logical = plan.clone_with_children(vec![child]);
+        Arc::new(PhysicalWindow::new(
+            logical.as_logical_window().unwrap().clone(),
+        ))
+    }
+
+    fn rewrite_logical_limit(&mut self, plan: &LogicalLimit) -> PlanRef {
+        let child = self.rewrite(plan.children().first().unwrap().clone());
+        let logical = plan.clone_with_children([child].to_vec());
+        Arc::new(PhysicalLimit::new(
+            logical.as_logical_limit().unwrap().clone(),
+        ))
+    }
+
+    /// The alias only renames the subquery outputs, which is a project of them in order.
+    fn rewrite_logical_subquery_alias(&mut self, plan: &LogicalSubqueryAlias) -> PlanRef {
+        let columns = plan.columns();
+        let exprs = columns
+            .iter()
+            .enumerate()
+            .map(|(index, c)| {
+                BoundExpr::InputRef(BoundInputRef {
+                    index,
+                    return_type: c.desc.data_type.clone(),
+                })
+            })
+            .collect();
+        let aliases = columns.into_iter().map(|c| Some(c.desc.name)).collect();
+        let project = LogicalProject::new(exprs, plan.input()).with_aliases(aliases);
+        self.rewrite_logical_project(&project)
+    }
+
+    fn rewrite_logical_explain(&mut self, plan: &LogicalExplain) -> PlanRef {
+        let child = self.rewrite(plan.children().first().unwrap().clone());
+        let logical = plan.clone_with_children([child].to_vec());
+        Arc::new(PhysicalExplain::new(
+            logical.as_logical_explain().unwrap().clone(),
+        ))
+    }
+
+    fn rewrite_logical_union(&mut self, plan: &LogicalUnion) -> PlanRef {
+        let children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect();
+        let logical = plan.clone_with_children(children);
+        Arc::new(PhysicalUnion::new(
+            logical.as_logical_union().unwrap().clone(),
+        ))
+    }
+
+    fn rewrite_logical_join(&mut self, plan: &LogicalJoin) -> PlanRef {
+        let children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect();
+        let logical = plan.clone_with_children(children);
+        let logical = logical.as_logical_join().unwrap().clone();
+        match logical.join_type() {
+            JoinType::Inner => Arc::new(PhysicalHashJoin::new(logical)),
+            JoinType::Cross => Arc::new(PhysicalCrossJoin::new(logical)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -59,6 +147,7 @@ mod physical_rewriter_test {
 
     fn build_test_column(column_name: String) -> ColumnCatalog {
         ColumnCatalog {
+            table_id: "t".to_string(),
             id: column_name.clone(),
             desc: ColumnDesc {
                 name: column_name,
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use paste::paste;
+
+use super::plan_node::*;
+use super::plan_visitor::PlanVisitor;
+use crate::for_all_plan_nodes;
+
+/// Count the plan nodes by type name, e.g. `{"LogicalFilter": 1, "LogicalTableScan": 1}`, which is
+/// mainly used to assert the plan shape in optimizer tests.
+#[derive(Default)]
+pub struct PlanCounter {
+    counts: HashMap<&'static str, usize>,
+}
+
+macro_rules! impl_plan_counter {
+    ($($node_name:ident),*) => {
+        impl PlanVisitor<()> for PlanCounter {
+            paste! {
+                $(
+                    fn [<visit_$node_name:snake>](&mut self, plan: &$node_name) -> Option<()> {
+                        *self.counts.entry(stringify!($node_name)).or_default() += 1;
+                        for child in plan.children() {
+                            self.visit(child);
+                        }
+                        Some(())
+                    }
+                )*
+            }
+        }
+    };
+}
+
+for_all_plan_nodes! { impl_plan_counter }
+
+/// Count the plan nodes of the whole plan tree by type name.
+pub fn count_operators(plan: &PlanRef) -> HashMap<&'static str, usize> {
+    let mut counter = PlanCounter::default();
+    counter.visit(plan.clone());
+    counter.counts
+}
+
+#[cfg(test)]
+mod plan_counter_test {
+    use std::sync::Arc;
+
+    use arrow::datatypes::DataType;
+    use sqlparser::ast::BinaryOperator;
+
+    use super::*;
+    use crate::binder::{BoundBinaryOp, BoundColumnRef, BoundExpr};
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::types::ScalarValue;
+
+    #[test]
+    fn test_count_operators() {
+        let column = ColumnCatalog {
+            table_id: "t".to_string(),
+            id: "c1".to_string(),
+            desc: ColumnDesc {
+                name: "c1".to_string(),
+                data_type: DataType::Int32,
+            },
+        };
+        let scan = LogicalTableScan::new("t".to_string(), vec![column.clone()]);
+        let filter_expr = BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Gt,
+            left: Box::new(BoundExpr::ColumnRef(BoundColumnRef {
+                column_catalog: column.clone(),
+            })),
+            right: Box::new(BoundExpr::Constant(ScalarValue::Int32(Some(1)))),
+            return_type: Some(DataType::Boolean),
+        });
+        let filter = LogicalFilter::new(filter_expr, Arc::new(scan));
+        let project = LogicalProject::new(
+            vec![BoundExpr::ColumnRef(BoundColumnRef {
+                column_catalog: column,
+            })],
+            Arc::new(filter),
+        );
+        let plan: PlanRef = Arc::new(project);
+
+        let counts = count_operators(&plan);
+        assert_eq!(counts.get("LogicalProject"), Some(&1));
+        assert_eq!(counts.get("LogicalFilter"), Some(&1));
+        assert_eq!(counts.get("LogicalTableScan"), Some(&1));
+        assert_eq!(counts.get("LogicalAgg"), None);
+    }
+}
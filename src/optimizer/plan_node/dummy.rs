@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use super::{PlanNode, PlanRef, PlanTreeNode};
 
+/// The input of a select without FROM clause, which outputs a single row of no columns.
 #[derive(Debug, Clone)]
 pub struct Dummy {}
 
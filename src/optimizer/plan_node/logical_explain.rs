@@ -0,0 +1,94 @@
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::{ColumnCatalog, ColumnDesc};
+
+/// Output the plan tree of the input as lines of text rather than executing it.
+#[derive(Debug, Clone)]
+pub struct LogicalExplain {
+    input: PlanRef,
+    /// execute the input and annotate the plan with the metrics of each node
+    analyze: bool,
+    /// list the optimizer rules which changed the plan after the plan tree
+    verbose: bool,
+    /// the optimizer rules fired on the plan with the times each one fired, filled in after the
+    /// optimization since the rules are applied on this node as well
+    fired_rules: Vec<(String, usize)>,
+}
+
+impl LogicalExplain {
+    pub fn new(input: PlanRef, analyze: bool) -> Self {
+        Self {
+            input,
+            analyze,
+            verbose: false,
+            fired_rules: vec![],
+        }
+    }
+
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    pub fn with_fired_rules(mut self, fired_rules: Vec<(String, usize)>) -> Self {
+        self.fired_rules = fired_rules;
+        self
+    }
+
+    pub fn analyze(&self) -> bool {
+        self.analyze
+    }
+
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    pub fn fired_rules(&self) -> &[(String, usize)] {
+        &self.fired_rules
+    }
+
+    pub fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+}
+
+impl PlanNode for LogicalExplain {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        vec![ColumnCatalog {
+            table_id: String::new(),
+            id: "plan".to_string(),
+            desc: ColumnDesc {
+                name: "plan".to_string(),
+                data_type: DataType::Utf8,
+            },
+        }]
+    }
+}
+
+impl PlanTreeNode for LogicalExplain {
+    fn children(&self) -> Vec<PlanRef> {
+        vec![self.input.clone()]
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), 1);
+        Arc::new(Self {
+            input: children[0].clone(),
+            ..self.clone()
+        })
+    }
+}
+
+impl fmt::Display for LogicalExplain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "LogicalExplain: analyze {}, verbose {}",
+            self.analyze, self.verbose
+        )
+    }
+}
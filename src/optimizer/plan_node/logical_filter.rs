@@ -11,11 +11,44 @@ pub struct LogicalFilter {
     expr: BoundExpr,
     /// the child PlanRef to be projected
     input: PlanRef,
+    /// the uncorrelated subquery of `expr [NOT] IN (subquery)`, the rows are filtered by the
+    /// membership of `expr` in its result rather than evaluating `expr` as predicate.
+    subquery: Option<PlanRef>,
+    negated: bool,
+    /// the subqueries referenced by expr, e.g. `EXISTS (subquery)`, which are the children after
+    /// input and the IN subquery.
+    subqueries: Vec<PlanRef>,
 }
 
 impl LogicalFilter {
     pub fn new(expr: BoundExpr, input: PlanRef) -> Self {
-        Self { expr, input }
+        Self {
+            expr,
+            input,
+            subquery: None,
+            negated: false,
+            subqueries: vec![],
+        }
+    }
+
+    pub fn new_in_subquery(
+        expr: BoundExpr,
+        negated: bool,
+        input: PlanRef,
+        subquery: PlanRef,
+    ) -> Self {
+        Self {
+            expr,
+            input,
+            subquery: Some(subquery),
+            negated,
+            subqueries: vec![],
+        }
+    }
+
+    pub fn with_subqueries(mut self, subqueries: Vec<PlanRef>) -> Self {
+        self.subqueries = subqueries;
+        self
     }
 
     pub fn expr(&self) -> BoundExpr {
@@ -25,6 +58,33 @@ impl LogicalFilter {
     pub fn input(&self) -> PlanRef {
         self.input.clone()
     }
+
+    pub fn subquery(&self) -> Option<PlanRef> {
+        self.subquery.clone()
+    }
+
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+
+    pub fn subqueries(&self) -> Vec<PlanRef> {
+        self.subqueries.clone()
+    }
+
+    pub fn clone_with_expr(&self, expr: BoundExpr) -> Self {
+        Self {
+            expr,
+            ..self.clone()
+        }
+    }
+
+    pub(super) fn explain_expr(&self) -> String {
+        match (&self.subquery, self.negated) {
+            (None, _) => format!("{:?}", self.expr),
+            (Some(_), false) => format!("{:?} IN subquery", self.expr),
+            (Some(_), true) => format!("{:?} NOT IN subquery", self.expr),
+        }
+    }
 }
 
 impl PlanNode for LogicalFilter {
@@ -34,18 +94,37 @@ impl PlanNode for LogicalFilter {
 }
 
 impl PlanTreeNode for LogicalFilter {
+    /// the IN subquery is the second child if any, followed by the subqueries of expr
     fn children(&self) -> Vec<PlanRef> {
-        vec![self.input.clone()]
+        let mut children = vec![self.input.clone()];
+        children.extend(self.subquery.clone());
+        children.extend(self.subqueries.clone());
+        children
     }
 
     fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
-        assert_eq!(children.len(), 1);
-        Arc::new(Self::new(self.expr.clone(), children[0].clone()))
+        assert_eq!(
+            children.len(),
+            1 + self.subquery.iter().len() + self.subqueries.len()
+        );
+        let mut children = children.into_iter();
+        let input = children.next().unwrap();
+        let subquery = match self.subquery {
+            Some(_) => children.next(),
+            None => None,
+        };
+        Arc::new(Self {
+            expr: self.expr.clone(),
+            input,
+            subquery,
+            negated: self.negated,
+            subqueries: children.collect(),
+        })
     }
 }
 
 impl fmt::Display for LogicalFilter {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "LogicalFilter: expr {:?}", self.expr)
+        writeln!(f, "LogicalFilter: expr {}", self.explain_expr())
     }
 }
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::binder::{BoundExpr, JoinType};
+use crate::catalog::ColumnCatalog;
+
+/// Join the rows of left and right inputs, the output columns are the left columns followed by the
+/// right columns.
+#[derive(Debug, Clone)]
+pub struct LogicalJoin {
+    left: PlanRef,
+    right: PlanRef,
+    join_type: JoinType,
+    /// the equi-join keys, each pair is evaluated on the left rows and the right rows
+    /// respectively.
+    on: Vec<(BoundExpr, BoundExpr)>,
+}
+
+impl LogicalJoin {
+    pub fn new(
+        left: PlanRef,
+        right: PlanRef,
+        join_type: JoinType,
+        on: Vec<(BoundExpr, BoundExpr)>,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            join_type,
+            on,
+        }
+    }
+
+    pub fn left(&self) -> PlanRef {
+        self.left.clone()
+    }
+
+    pub fn right(&self) -> PlanRef {
+        self.right.clone()
+    }
+
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    pub fn on(&self) -> Vec<(BoundExpr, BoundExpr)> {
+        self.on.clone()
+    }
+}
+
+impl PlanNode for LogicalJoin {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        let mut schema = self.left.schema();
+        schema.extend(self.right.schema());
+        schema
+    }
+}
+
+impl PlanTreeNode for LogicalJoin {
+    fn children(&self) -> Vec<PlanRef> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), 2);
+        Arc::new(Self::new(
+            children[0].clone(),
+            children[1].clone(),
+            self.join_type,
+            self.on.clone(),
+        ))
+    }
+}
+
+impl fmt::Display for LogicalJoin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.join_type {
+            JoinType::Cross => writeln!(f, "LogicalJoin: type {:?}", self.join_type),
+            _ => writeln!(
+                f,
+                "LogicalJoin: type {:?}, on {:?}",
+                self.join_type, self.on
+            ),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::binder::BoundExpr;
+use crate::catalog::ColumnCatalog;
+
+#[derive(Debug, Clone)]
+pub struct LogicalLimit {
+    /// the max number of rows to output, no limit if None
+    limit: Option<usize>,
+    /// the number of rows to skip before output, 0 if None
+    offset: Option<usize>,
+    /// the child PlanRef to be limited
+    input: PlanRef,
+    /// the ORDER BY keys of `WITH TIES`, the rows tie with the last row on them are also
+    /// output. Empty if without ties.
+    tie_keys: Vec<BoundExpr>,
+}
+
+impl LogicalLimit {
+    pub fn new(limit: Option<usize>, offset: Option<usize>, input: PlanRef) -> Self {
+        Self {
+            limit,
+            offset,
+            input,
+            tie_keys: vec![],
+        }
+    }
+
+    pub fn with_tie_keys(mut self, tie_keys: Vec<BoundExpr>) -> Self {
+        self.tie_keys = tie_keys;
+        self
+    }
+
+    pub fn tie_keys(&self) -> Vec<BoundExpr> {
+        self.tie_keys.clone()
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+}
+
+impl PlanNode for LogicalLimit {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.input.schema()
+    }
+}
+
+impl PlanTreeNode for LogicalLimit {
+    fn children(&self) -> Vec<PlanRef> {
+        vec![self.input.clone()]
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), 1);
+        Arc::new(
+            Self::new(self.limit, self.offset, children[0].clone())
+                .with_tie_keys(self.tie_keys.clone()),
+        )
+    }
+}
+
+impl fmt::Display for LogicalLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LogicalLimit: limit {:?}, offset {:?}",
+            self.limit, self.offset
+        )?;
+        if !self.tie_keys.is_empty() {
+            write!(f, ", with ties on {:?}", self.tie_keys)?;
+        }
+        writeln!(f)
+    }
+}
@@ -11,20 +11,49 @@ pub struct LogicalProject {
     exprs: Vec<BoundExpr>,
     /// the child PlanRef to be projected
     input: PlanRef,
+    /// the output column names given by `expr AS alias`, parallel to exprs
+    aliases: Vec<Option<String>>,
+    /// the scalar subqueries referenced by exprs, which are the children after input.
+    subqueries: Vec<PlanRef>,
 }
 
 impl LogicalProject {
     pub fn new(exprs: Vec<BoundExpr>, input: PlanRef) -> Self {
-        Self { exprs, input }
+        let aliases = vec![None; exprs.len()];
+        Self {
+            exprs,
+            input,
+            aliases,
+            subqueries: vec![],
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: Vec<Option<String>>) -> Self {
+        assert_eq!(aliases.len(), self.exprs.len());
+        self.aliases = aliases;
+        self
+    }
+
+    pub fn with_subqueries(mut self, subqueries: Vec<PlanRef>) -> Self {
+        self.subqueries = subqueries;
+        self
     }
 
     pub fn exprs(&self) -> Vec<BoundExpr> {
         self.exprs.clone()
     }
 
+    pub fn aliases(&self) -> Vec<Option<String>> {
+        self.aliases.clone()
+    }
+
     pub fn input(&self) -> PlanRef {
         self.input.clone()
     }
+
+    pub fn subqueries(&self) -> Vec<PlanRef> {
+        self.subqueries.clone()
+    }
 }
 
 impl PlanNode for LogicalProject {
@@ -35,12 +64,19 @@ impl PlanNode for LogicalProject {
 
 impl PlanTreeNode for LogicalProject {
     fn children(&self) -> Vec<PlanRef> {
-        vec![self.input.clone()]
+        let mut children = vec![self.input.clone()];
+        children.extend(self.subqueries.clone());
+        children
     }
 
     fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
-        assert_eq!(children.len(), 1);
-        Arc::new(Self::new(self.exprs.clone(), children[0].clone()))
+        assert_eq!(children.len(), 1 + self.subqueries.len());
+        let mut children = children.into_iter();
+        Arc::new(
+            Self::new(self.exprs.clone(), children.next().unwrap())
+                .with_aliases(self.aliases.clone())
+                .with_subqueries(children.collect()),
+        )
     }
 }
 
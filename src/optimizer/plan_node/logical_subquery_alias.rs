@@ -0,0 +1,73 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::{ColumnCatalog, TableId};
+
+/// Expose the outputs of the subquery as the columns of a named relation, e.g. the reference of a
+/// CTE, so that the outer query resolves its columns like the ones of a table.
+#[derive(Debug, Clone)]
+pub struct LogicalSubqueryAlias {
+    table_id: TableId,
+    /// the columns named for the subquery outputs in order
+    columns: Vec<ColumnCatalog>,
+    input: PlanRef,
+}
+
+impl LogicalSubqueryAlias {
+    pub fn new(table_id: TableId, columns: Vec<ColumnCatalog>, input: PlanRef) -> Self {
+        Self {
+            table_id,
+            columns,
+            input,
+        }
+    }
+
+    pub fn table_id(&self) -> TableId {
+        self.table_id.clone()
+    }
+
+    pub fn columns(&self) -> Vec<ColumnCatalog> {
+        self.columns.clone()
+    }
+
+    pub fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+}
+
+impl PlanNode for LogicalSubqueryAlias {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.columns.clone()
+    }
+}
+
+impl PlanTreeNode for LogicalSubqueryAlias {
+    fn children(&self) -> Vec<PlanRef> {
+        vec![self.input.clone()]
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), 1);
+        Arc::new(Self::new(
+            self.table_id.clone(),
+            self.columns.clone(),
+            children[0].clone(),
+        ))
+    }
+}
+
+impl fmt::Display for LogicalSubqueryAlias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "LogicalSubqueryAlias: table: #{}, columns: [{}]",
+            self.table_id,
+            self.columns
+                .iter()
+                .map(|c| c.id.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::ColumnCatalog;
+
+/// Concatenate the outputs of all inputs in order, the output columns are named after the first
+/// input and of the `types` which each input is cast to.
+#[derive(Debug, Clone)]
+pub struct LogicalUnion {
+    inputs: Vec<PlanRef>,
+    types: Vec<DataType>,
+}
+
+impl LogicalUnion {
+    pub fn new(inputs: Vec<PlanRef>, types: Vec<DataType>) -> Self {
+        assert!(!inputs.is_empty());
+        Self { inputs, types }
+    }
+
+    pub fn inputs(&self) -> Vec<PlanRef> {
+        self.inputs.clone()
+    }
+
+    pub fn types(&self) -> Vec<DataType> {
+        self.types.clone()
+    }
+}
+
+impl PlanNode for LogicalUnion {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.inputs[0]
+            .schema()
+            .into_iter()
+            .zip(self.types.iter())
+            .map(|(mut column, data_type)| {
+                column.desc.data_type = data_type.clone();
+                column
+            })
+            .collect()
+    }
+}
+
+impl PlanTreeNode for LogicalUnion {
+    fn children(&self) -> Vec<PlanRef> {
+        self.inputs.clone()
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), self.inputs.len());
+        Arc::new(Self::new(children, self.types.clone()))
+    }
+}
+
+impl fmt::Display for LogicalUnion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LogicalUnion: inputs {}", self.inputs.len())
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::binder::BoundExpr;
+use crate::catalog::ColumnCatalog;
+
+/// Compute the window functions over the input rows, the output is the input columns followed by
+/// one column of each window function.
+#[derive(Debug, Clone)]
+pub struct LogicalWindow {
+    window_funcs: Vec<BoundExpr>,
+    input: PlanRef,
+}
+
+impl LogicalWindow {
+    pub fn new(window_funcs: Vec<BoundExpr>, input: PlanRef) -> Self {
+        Self {
+            window_funcs,
+            input,
+        }
+    }
+
+    pub fn window_funcs(&self) -> Vec<BoundExpr> {
+        self.window_funcs.clone()
+    }
+
+    pub fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+}
+
+impl PlanNode for LogicalWindow {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.input.schema()
+    }
+}
+
+impl PlanTreeNode for LogicalWindow {
+    fn children(&self) -> Vec<PlanRef> {
+        vec![self.input.clone()]
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), 1);
+        Arc::new(Self::new(self.window_funcs.clone(), children[0].clone()))
+    }
+}
+
+impl fmt::Display for LogicalWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LogicalWindow: window_funcs {:?}", self.window_funcs)
+    }
+}
@@ -1,14 +1,29 @@
 mod dummy;
 mod logical_agg;
+mod logical_explain;
 mod logical_filter;
+mod logical_join;
+mod logical_limit;
+mod logical_order;
 mod logical_project;
+mod logical_subquery_alias;
 mod logical_table_scan;
+mod logical_union;
+mod logical_window;
+mod physical_cross_join;
+mod physical_explain;
 mod physical_filter;
 mod physical_hash_agg;
+mod physical_hash_join;
+mod physical_limit;
+mod physical_order;
 mod physical_project;
 mod physical_simple_agg;
 mod physical_table_scan;
+mod physical_union;
+mod physical_window;
 mod plan_node_traits;
+mod table_function_scan;
 
 use std::fmt::{Debug, Display, Write};
 use std::sync::Arc;
@@ -16,16 +31,31 @@ use std::sync::Arc;
 use downcast_rs::{impl_downcast, Downcast};
 pub use dummy::*;
 pub use logical_agg::*;
+pub use logical_explain::*;
 pub use logical_filter::*;
+pub use logical_join::*;
+pub use logical_limit::*;
+pub use logical_order::*;
 pub use logical_project::*;
+pub use logical_subquery_alias::*;
 pub use logical_table_scan::*;
+pub use logical_union::*;
+pub use logical_window::*;
 use paste::paste;
+pub use physical_cross_join::*;
+pub use physical_explain::*;
 pub use physical_filter::*;
 pub use physical_hash_agg::*;
+pub use physical_hash_join::*;
+pub use physical_limit::*;
+pub use physical_order::*;
 pub use physical_project::*;
 pub use physical_simple_agg::*;
 pub use physical_table_scan::*;
+pub use physical_union::*;
+pub use physical_window::*;
 pub use plan_node_traits::*;
+pub use table_function_scan::*;
 
 use crate::catalog::ColumnCatalog;
 
@@ -43,11 +73,26 @@ impl_downcast!(PlanNode);
 
 impl dyn PlanNode {
     pub fn explain(&self, level: usize, explain_result: &mut dyn Write) {
-        let indented_self =
+        self.explain_with_annotation(level, explain_result, &|_| None);
+    }
+
+    /// Explain the plan tree, and append the annotation of each node to its first line, e.g. the
+    /// metrics of EXPLAIN ANALYZE.
+    pub fn explain_with_annotation(
+        &self,
+        level: usize,
+        explain_result: &mut dyn Write,
+        annotate: &dyn Fn(&dyn PlanNode) -> Option<String>,
+    ) {
+        let mut indented_self =
             format!("{}", self).replace("\n  ", &format!("\n{}", " ".repeat(level * 2 + 4)));
+        if let Some(annotation) = annotate(self) {
+            let end = indented_self.find('\n').unwrap_or(indented_self.len());
+            indented_self.insert_str(end, &format!(" {}", annotation));
+        }
         write!(explain_result, "{}{}", " ".repeat(level * 2), indented_self).unwrap();
         for child in self.children() {
-            child.explain(level + 1, explain_result);
+            child.explain_with_annotation(level + 1, explain_result, annotate);
         }
     }
 }
@@ -64,15 +109,30 @@ macro_rules! for_all_plan_nodes {
     ($macro:ident) => {
         $macro! {
             Dummy,
+            TableFunctionScan,
             LogicalTableScan,
             LogicalProject,
             LogicalFilter,
             LogicalAgg,
+            LogicalOrder,
+            LogicalLimit,
+            LogicalUnion,
+            LogicalJoin,
+            LogicalSubqueryAlias,
+            LogicalWindow,
+            LogicalExplain,
             PhysicalTableScan,
             PhysicalProject,
             PhysicalFilter,
             PhysicalSimpleAgg,
-            PhysicalHashAgg
+            PhysicalHashAgg,
+            PhysicalOrder,
+            PhysicalLimit,
+            PhysicalUnion,
+            PhysicalCrossJoin,
+            PhysicalHashJoin,
+            PhysicalWindow,
+            PhysicalExplain
         }
     };
 }
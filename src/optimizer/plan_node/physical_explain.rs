@@ -0,0 +1,41 @@
+use std::fmt;
+
+use super::{LogicalExplain, PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::ColumnCatalog;
+
+#[derive(Debug, Clone)]
+pub struct PhysicalExplain {
+    logical: LogicalExplain,
+}
+
+impl PhysicalExplain {
+    pub fn new(logical: LogicalExplain) -> Self {
+        Self { logical }
+    }
+
+    pub fn logical(&self) -> &LogicalExplain {
+        &self.logical
+    }
+}
+
+impl PlanNode for PhysicalExplain {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.logical().schema()
+    }
+}
+
+impl PlanTreeNode for PhysicalExplain {
+    fn children(&self) -> Vec<PlanRef> {
+        self.logical().children()
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        self.logical().clone_with_children(children)
+    }
+}
+
+impl fmt::Display for PhysicalExplain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PhysicalExplain: analyze {}", self.logical().analyze())
+    }
+}
@@ -36,6 +36,6 @@ impl PlanTreeNode for PhysicalFilter {
 
 impl fmt::Display for PhysicalFilter {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "PhysicalFilter: expr {:?}", self.logical().expr())
+        writeln!(f, "PhysicalFilter: expr {}", self.logical().explain_expr())
     }
 }
@@ -0,0 +1,41 @@
+use std::fmt;
+
+use super::{LogicalJoin, PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::ColumnCatalog;
+
+#[derive(Debug, Clone)]
+pub struct PhysicalHashJoin {
+    logical: LogicalJoin,
+}
+
+impl PhysicalHashJoin {
+    pub fn new(logical: LogicalJoin) -> Self {
+        Self { logical }
+    }
+
+    pub fn logical(&self) -> &LogicalJoin {
+        &self.logical
+    }
+}
+
+impl PlanNode for PhysicalHashJoin {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.logical().schema()
+    }
+}
+
+impl PlanTreeNode for PhysicalHashJoin {
+    fn children(&self) -> Vec<PlanRef> {
+        self.logical().children()
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        self.logical().clone_with_children(children)
+    }
+}
+
+impl fmt::Display for PhysicalHashJoin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PhysicalHashJoin: on {:?}", self.logical().on())
+    }
+}
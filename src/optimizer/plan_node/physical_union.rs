@@ -0,0 +1,41 @@
+use std::fmt;
+
+use super::{LogicalUnion, PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::ColumnCatalog;
+
+#[derive(Debug, Clone)]
+pub struct PhysicalUnion {
+    logical: LogicalUnion,
+}
+
+impl PhysicalUnion {
+    pub fn new(logical: LogicalUnion) -> Self {
+        Self { logical }
+    }
+
+    pub fn logical(&self) -> &LogicalUnion {
+        &self.logical
+    }
+}
+
+impl PlanNode for PhysicalUnion {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.logical().schema()
+    }
+}
+
+impl PlanTreeNode for PhysicalUnion {
+    fn children(&self) -> Vec<PlanRef> {
+        self.logical().children()
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        self.logical().clone_with_children(children)
+    }
+}
+
+impl fmt::Display for PhysicalUnion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PhysicalUnion: inputs {}", self.logical().inputs().len())
+    }
+}
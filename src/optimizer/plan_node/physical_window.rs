@@ -0,0 +1,45 @@
+use std::fmt;
+
+use super::{LogicalWindow, PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::ColumnCatalog;
+
+#[derive(Debug, Clone)]
+pub struct PhysicalWindow {
+    logical: LogicalWindow,
+}
+
+impl PhysicalWindow {
+    pub fn new(logical: LogicalWindow) -> Self {
+        Self { logical }
+    }
+
+    pub fn logical(&self) -> &LogicalWindow {
+        &self.logical
+    }
+}
+
+impl PlanNode for PhysicalWindow {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.logical().schema()
+    }
+}
+
+impl PlanTreeNode for PhysicalWindow {
+    fn children(&self) -> Vec<PlanRef> {
+        self.logical().children()
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        self.logical().clone_with_children(children)
+    }
+}
+
+impl fmt::Display for PhysicalWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "PhysicalWindow: window_funcs {:?}",
+            self.logical().window_funcs()
+        )
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::sync::Arc;
+
+use itertools::Itertools;
+
+use super::{PlanNode, PlanRef, PlanTreeNode};
+use crate::catalog::{ColumnCatalog, TableFunctionRef};
+use crate::types::ScalarValue;
+
+/// The rows computed by a table function in FROM, which is both the logical and physical plan.
+#[derive(Clone)]
+pub struct TableFunctionScan {
+    name: String,
+    function: TableFunctionRef,
+    args: Vec<ScalarValue>,
+    columns: Vec<ColumnCatalog>,
+}
+
+impl TableFunctionScan {
+    pub fn new(
+        name: String,
+        function: TableFunctionRef,
+        args: Vec<ScalarValue>,
+        columns: Vec<ColumnCatalog>,
+    ) -> Self {
+        Self {
+            name,
+            function,
+            args,
+            columns,
+        }
+    }
+
+    pub fn function(&self) -> TableFunctionRef {
+        self.function.clone()
+    }
+
+    pub fn args(&self) -> Vec<ScalarValue> {
+        self.args.clone()
+    }
+
+    pub fn columns(&self) -> Vec<ColumnCatalog> {
+        self.columns.clone()
+    }
+}
+
+impl PlanNode for TableFunctionScan {
+    fn schema(&self) -> Vec<ColumnCatalog> {
+        self.columns.clone()
+    }
+}
+
+impl PlanTreeNode for TableFunctionScan {
+    fn children(&self) -> Vec<PlanRef> {
+        vec![]
+    }
+
+    fn clone_with_children(&self, children: Vec<PlanRef>) -> PlanRef {
+        assert_eq!(children.len(), 0);
+        Arc::new(self.clone())
+    }
+}
+
+impl fmt::Debug for TableFunctionScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TableFunctionScan")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+impl fmt::Display for TableFunctionScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "TableFunctionScan: function: {}, args: {:?}, columns: [{}]",
+            self.name,
+            self.args,
+            self.columns.iter().map(|c| &c.id).join(", ")
+        )
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use itertools::Itertools;
+
+use super::expr_rewriter::ExprRewriter;
+use super::{ExprVisitor, LogicalAgg, LogicalProject, PlanRef, PlanRewriter, RuleRewriter};
+use crate::binder::{BoundExpr, BoundInputRef};
+
+/// Remove the aggregations of `LogicalAgg` which are not referenced by its parent `LogicalProject`.
+///
+/// The rewriter should run after `InputRefRewriter`, so the project exprs reference the agg output
+/// by index, the agg output is the `group_by` exprs followed by the `agg_funcs`. After pruning,
+/// the indices of project exprs are adjusted to the new agg output.
+#[derive(Default)]
+pub struct PruneAggRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PlanRewriter for PruneAggRewriter {
+    fn rewrite_logical_project(&mut self, plan: &LogicalProject) -> PlanRef {
+        let new_children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect_vec();
+        let new_child = new_children[0].clone();
+        let new_subqueries = new_children[1..].to_vec();
+        let agg = match new_child.as_logical_agg() {
+            Ok(agg) => agg.clone(),
+            Err(_) => return plan.clone_with_children(new_children),
+        };
+
+        let mut collector = InputRefCollector::default();
+        for expr in plan.exprs().iter() {
+            collector.visit_expr(expr);
+        }
+
+        let group_len = agg.group_by().len();
+        let agg_funcs = agg.agg_funcs();
+        let mut mapping: HashMap<usize, usize> = (0..group_len).map(|idx| (idx, idx)).collect();
+        let mut new_agg_funcs = vec![];
+        for (idx, agg_func) in agg_funcs.iter().enumerate() {
+            if collector.indices.contains(&(group_len + idx)) {
+                mapping.insert(group_len + idx, group_len + new_agg_funcs.len());
+                new_agg_funcs.push(agg_func.clone());
+            }
+        }
+
+        // nothing to prune, or simple agg would output an empty RecordBatch
+        if new_agg_funcs.len() == agg_funcs.len() || (group_len == 0 && new_agg_funcs.is_empty()) {
+            return plan.clone_with_children(new_children);
+        }
+
+        self.changed = true;
+        let remapper = InputRefRemapper { mapping };
+        let mut new_exprs = plan.exprs();
+        for expr in &mut new_exprs {
+            remapper.rewrite_expr(expr);
+        }
+
+        let new_agg = LogicalAgg::new(new_agg_funcs, agg.group_by(), agg.input());
+        Arc::new(
+            LogicalProject::new(new_exprs, Arc::new(new_agg))
+                .with_aliases(plan.aliases())
+                .with_subqueries(new_subqueries),
+        )
+    }
+}
+
+impl RuleRewriter for PruneAggRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+#[derive(Default)]
+struct InputRefCollector {
+    indices: HashSet<usize>,
+}
+
+impl ExprVisitor for InputRefCollector {
+    fn visit_input_ref(&mut self, expr: &BoundInputRef) {
+        self.indices.insert(expr.index);
+    }
+}
+
+struct InputRefRemapper {
+    /// old input index -> new input index
+    mapping: HashMap<usize, usize>,
+}
+
+impl ExprRewriter for InputRefRemapper {
+    fn rewrite_input_ref(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::InputRef(e) => e.index = *self.mapping.get(&e.index).unwrap(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod prune_agg_rewriter_test {
+    use arrow::datatypes::DataType;
+
+    use super::*;
+    use crate::binder::{AggFunc, BoundAggFunc, BoundColumnRef};
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::optimizer::{InputRefRewriter, LogicalTableScan};
+
+    fn build_test_column(column_name: String) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: "t".to_string(),
+            id: column_name.clone(),
+            desc: ColumnDesc {
+                name: column_name,
+                data_type: DataType::Int32,
+            },
+        }
+    }
+
+    fn build_column_ref(column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column(column_name.to_string()),
+        })
+    }
+
+    fn build_agg_func(func: AggFunc, column_name: &str) -> BoundExpr {
+        BoundExpr::AggFunc(BoundAggFunc {
+            func,
+            exprs: vec![build_column_ref(column_name)],
+            return_type: DataType::Int32,
+            distinct: false,
+        })
+    }
+
+    /// select {project_exprs} from (select a, sum(b), max(c) from t group by a)
+    fn build_test_plan(project_exprs: Vec<BoundExpr>) -> PlanRef {
+        let scan = LogicalTableScan::new(
+            "t".to_string(),
+            vec![
+                build_test_column("a".to_string()),
+                build_test_column("b".to_string()),
+                build_test_column("c".to_string()),
+            ],
+        );
+        let agg = LogicalAgg::new(
+            vec![
+                build_agg_func(AggFunc::Sum, "b"),
+                build_agg_func(AggFunc::Max, "c"),
+            ],
+            vec![build_column_ref("a")],
+            Arc::new(scan),
+        );
+        let project = LogicalProject::new(project_exprs, Arc::new(agg));
+        InputRefRewriter::default().rewrite(Arc::new(project))
+    }
+
+    #[test]
+    fn test_prune_all_unused_agg_funcs() {
+        let plan = build_test_plan(vec![build_column_ref("a")]);
+
+        let new_plan = PruneAggRewriter::default().rewrite(plan);
+
+        let agg = new_plan.children()[0].as_logical_agg().unwrap().clone();
+        assert!(agg.agg_funcs().is_empty());
+        assert_eq!(agg.group_by().len(), 1);
+        assert_eq!(
+            new_plan.as_logical_project().unwrap().exprs(),
+            vec![BoundExpr::InputRef(BoundInputRef {
+                index: 0,
+                return_type: DataType::Int32,
+            })]
+        );
+    }
+
+    #[test]
+    fn test_prune_agg_funcs_and_adjust_indices() {
+        let plan = build_test_plan(vec![
+            build_column_ref("a"),
+            build_agg_func(AggFunc::Max, "c"),
+        ]);
+
+        let new_plan = PruneAggRewriter::default().rewrite(plan);
+
+        let agg = new_plan.children()[0].as_logical_agg().unwrap().clone();
+        assert_eq!(agg.agg_funcs().len(), 1);
+        assert_eq!(
+            new_plan.as_logical_project().unwrap().exprs(),
+            vec![
+                BoundExpr::InputRef(BoundInputRef {
+                    index: 0,
+                    return_type: DataType::Int32,
+                }),
+                BoundExpr::InputRef(BoundInputRef {
+                    index: 1,
+                    return_type: DataType::Int32,
+                }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use super::{LogicalAgg, LogicalUnion, PlanRef, PlanRewriter, PlanTreeNode, RuleRewriter};
+use crate::binder::{AggFunc, BoundAggFunc, BoundExpr, BoundInputRef};
+
+/// Push the `count` and `sum` aggregates without GROUP BY down to the inputs of UNION ALL, each
+/// input computes the partial aggregates, and the partials are summed up above the union, e.g.
+/// `count(*)` over the union is the sum of the counts of each input.
+///
+/// The rewriter should run after `InputRefRewriter`, the aggregate args reference the union output
+/// by index, which is the same index in the output of each input. The union whose inputs are cast
+/// to wider types is kept, as the partial aggregates would see the narrower input types.
+#[derive(Default)]
+pub struct PushAggThroughUnionRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PlanRewriter for PushAggThroughUnionRewriter {
+    fn rewrite_logical_agg(&mut self, plan: &LogicalAgg) -> PlanRef {
+        let input = self.rewrite(plan.input());
+        let union = match input.as_logical_union() {
+            Ok(union)
+                if plan.group_by().is_empty()
+                    && is_pushable(&plan.agg_funcs())
+                    && !is_cast(union) =>
+            {
+                union
+            }
+            _ => return plan.clone_with_children(vec![input]),
+        };
+
+        self.changed = true;
+        let partial_inputs = union
+            .inputs()
+            .into_iter()
+            .map(|input| Arc::new(LogicalAgg::new(plan.agg_funcs(), vec![], input)) as PlanRef)
+            .collect();
+        // the i-th partial aggregate is the i-th output of each input
+        let final_agg_funcs = plan
+            .agg_funcs()
+            .into_iter()
+            .enumerate()
+            .map(|(index, expr)| {
+                let return_type = expr.return_type().unwrap();
+                BoundExpr::AggFunc(BoundAggFunc {
+                    func: AggFunc::Sum,
+                    exprs: vec![BoundExpr::InputRef(BoundInputRef {
+                        index,
+                        return_type: return_type.clone(),
+                    })],
+                    return_type,
+                    distinct: false,
+                })
+            })
+            .collect();
+        let partial_types = plan
+            .agg_funcs()
+            .iter()
+            .map(|expr| expr.return_type().unwrap())
+            .collect();
+        Arc::new(LogicalAgg::new(
+            final_agg_funcs,
+            vec![],
+            Arc::new(LogicalUnion::new(partial_inputs, partial_types)),
+        ))
+    }
+}
+
+impl RuleRewriter for PushAggThroughUnionRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+fn is_cast(union: &LogicalUnion) -> bool {
+    union.inputs().iter().any(|input| {
+        input
+            .schema()
+            .iter()
+            .map(|column| &column.desc.data_type)
+            .ne(union.types().iter())
+    })
+}
+
+fn is_pushable(agg_funcs: &[BoundExpr]) -> bool {
+    !agg_funcs.is_empty()
+        && agg_funcs.iter().all(|expr| {
+            matches!(
+                expr,
+                BoundExpr::AggFunc(agg)
+                    if !agg.distinct && matches!(agg.func, AggFunc::Count | AggFunc::Sum)
+            )
+        })
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+use sqlparser::ast::BinaryOperator;
+
+use super::join_reorder_rewriter::{table_ids_of_expr, table_ids_of_plan};
+use super::push_limit_through_project_rewriter::is_deterministic_expr;
+use super::{LogicalFilter, PlanRef, PlanRewriter, PlanTreeNode, RuleRewriter};
+use crate::binder::{BoundBinaryOp, BoundExpr, JoinType};
+
+/// Push the conjuncts of `Filter(Join)` which only reference one side of the join down to that
+/// side, so that the rows are filtered before joining. The other conjuncts, e.g. the ones
+/// referencing both sides or no column, are kept above the join.
+///
+/// The rewriter should run before `InputRefRewriter`, the side of each conjunct is found by the
+/// table ids of its column refs. The filter of subqueries and the non-deterministic conjuncts,
+/// e.g. `random() < 0.5`, are kept, since they're evaluated once per joined row.
+#[derive(Default)]
+pub struct PushFilterThroughJoinRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PushFilterThroughJoinRewriter {
+    fn push_down(&mut self, conjuncts: Vec<BoundExpr>, child: PlanRef) -> PlanRef {
+        match conjunction(conjuncts) {
+            Some(expr) => self.rewrite(Arc::new(LogicalFilter::new(expr, child))),
+            None => child,
+        }
+    }
+}
+
+impl PlanRewriter for PushFilterThroughJoinRewriter {
+    fn rewrite_logical_filter(&mut self, plan: &LogicalFilter) -> PlanRef {
+        let mut children = plan.children();
+        children[0] = self.rewrite(plan.input());
+        let join = match children[0].as_logical_join() {
+            Ok(join)
+                if matches!(join.join_type(), JoinType::Inner | JoinType::Cross)
+                    && plan.subquery().is_none()
+                    && plan.subqueries().is_empty() =>
+            {
+                join.clone()
+            }
+            _ => return plan.clone_with_children(children),
+        };
+
+        let (left_ids, right_ids) = (
+            table_ids_of_plan(&join.left()),
+            table_ids_of_plan(&join.right()),
+        );
+        let (mut left, mut right, mut rest) = (vec![], vec![], vec![]);
+        for conjunct in split_conjunction(plan.expr()) {
+            let ids = table_ids_of_expr(&conjunct);
+            if ids.is_empty() || !is_deterministic_expr(&conjunct) {
+                rest.push(conjunct);
+            } else if ids.is_subset(&left_ids) {
+                left.push(conjunct);
+            } else if ids.is_subset(&right_ids) {
+                right.push(conjunct);
+            } else {
+                rest.push(conjunct);
+            }
+        }
+        if left.is_empty() && right.is_empty() {
+            return plan.clone_with_children(children);
+        }
+
+        self.changed = true;
+        let new_join = join.clone_with_children(vec![
+            self.push_down(left, join.left()),
+            self.push_down(right, join.right()),
+        ]);
+        match conjunction(rest) {
+            Some(expr) => Arc::new(LogicalFilter::new(expr, new_join)),
+            None => new_join,
+        }
+    }
+}
+
+impl RuleRewriter for PushFilterThroughJoinRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+fn split_conjunction(expr: BoundExpr) -> Vec<BoundExpr> {
+    match expr {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::And,
+            left,
+            right,
+            ..
+        }) => {
+            let mut conjuncts = split_conjunction(*left);
+            conjuncts.extend(split_conjunction(*right));
+            conjuncts
+        }
+        expr => vec![expr],
+    }
+}
+
+fn conjunction(conjuncts: Vec<BoundExpr>) -> Option<BoundExpr> {
+    conjuncts.into_iter().reduce(|acc, expr| {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::And,
+            left: Box::new(acc),
+            right: Box::new(expr),
+            return_type: Some(DataType::Boolean),
+        })
+    })
+}
+
+#[cfg(test)]
+mod push_filter_through_join_rewriter_test {
+    use super::*;
+    use crate::binder::BoundColumnRef;
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::optimizer::{LogicalJoin, LogicalTableScan, PlanNodeType};
+    use crate::types::ScalarValue;
+
+    fn build_test_column(table_id: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: table_id.to_string(),
+            id: "c1".to_string(),
+            desc: ColumnDesc {
+                name: "c1".to_string(),
+                data_type: DataType::Int32,
+            },
+        }
+    }
+
+    fn column_ref(table_id: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column(table_id),
+        })
+    }
+
+    fn eq(left: BoundExpr, right: BoundExpr) -> BoundExpr {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Eq,
+            left: Box::new(left),
+            right: Box::new(right),
+            return_type: Some(DataType::Boolean),
+        })
+    }
+
+    #[test]
+    fn test_push_filter_to_each_side_of_join() {
+        // select * from a, b where a.c1 = 1 and b.c1 = 2 and a.c1 = b.c1
+        let scan = |table_id: &str| -> PlanRef {
+            Arc::new(LogicalTableScan::new(
+                table_id.to_string(),
+                vec![build_test_column(table_id)],
+            ))
+        };
+        let join = LogicalJoin::new(scan("a"), scan("b"), JoinType::Cross, vec![]);
+        let one = BoundExpr::Constant(ScalarValue::Int32(Some(1)));
+        let two = BoundExpr::Constant(ScalarValue::Int32(Some(2)));
+        let expr = conjunction(vec![
+            eq(column_ref("a"), one),
+            eq(column_ref("b"), two),
+            eq(column_ref("a"), column_ref("b")),
+        ])
+        .unwrap();
+        let plan = Arc::new(LogicalFilter::new(expr, Arc::new(join)));
+
+        let new_plan = PushFilterThroughJoinRewriter::default().rewrite(plan);
+
+        // the conjunct of both sides is kept above the join
+        let filter = new_plan.as_logical_filter().unwrap();
+        assert_eq!(filter.expr(), eq(column_ref("a"), column_ref("b")));
+        let join = filter.input();
+        assert_eq!(join.node_type(), PlanNodeType::LogicalJoin);
+        for (child, table_id) in join.children().iter().zip(["a", "b"]) {
+            let filter = child.as_logical_filter().unwrap();
+            assert_eq!(split_conjunction(filter.expr()).len(), 1);
+            assert_eq!(
+                filter.input().as_logical_table_scan().unwrap().table_id(),
+                table_id
+            );
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use super::{
+    ExprVisitor, LogicalLimit, LogicalProject, PlanRef, PlanRewriter, PlanTreeNode, RuleRewriter,
+};
+use crate::binder::{BoundExpr, BoundScalarFunc, ScalarFunc};
+
+/// Push `Limit(Project(child))` down to `Project(Limit(child))`, since the project outputs one row
+/// for each input row, so that only the limited rows are projected.
+///
+/// The limit is kept above the project whose exprs are non-deterministic, e.g. `random()`, which
+/// draws a value per projected row.
+#[derive(Default)]
+pub struct PushLimitThroughProjectRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PlanRewriter for PushLimitThroughProjectRewriter {
+    fn rewrite_logical_limit(&mut self, plan: &LogicalLimit) -> PlanRef {
+        let input = self.rewrite(plan.input());
+        let project = match input.as_logical_project() {
+            Ok(project) if plan.tie_keys().is_empty() && is_deterministic(project) => project,
+            _ => return plan.clone_with_children(vec![input]),
+        };
+
+        // push further down if the project is above another project
+        self.changed = true;
+        let limit = LogicalLimit::new(plan.limit(), plan.offset(), project.input());
+        let new_input = self.rewrite_logical_limit(&limit);
+        let mut children = project.children();
+        children[0] = new_input;
+        project.clone_with_children(children)
+    }
+}
+
+impl RuleRewriter for PushLimitThroughProjectRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+fn is_deterministic(project: &LogicalProject) -> bool {
+    project.exprs().iter().all(is_deterministic_expr)
+}
+
+/// Whether the expr produces the same value for the same input row, which is false if it calls
+/// `random()`.
+pub(super) fn is_deterministic_expr(expr: &BoundExpr) -> bool {
+    let mut finder = NonDeterministicFinder { found: false };
+    finder.visit_expr(expr);
+    !finder.found
+}
+
+struct NonDeterministicFinder {
+    found: bool,
+}
+
+impl ExprVisitor for NonDeterministicFinder {
+    fn visit_scalar_func(&mut self, expr: &BoundScalarFunc) {
+        if matches!(expr.func, ScalarFunc::Random(_)) {
+            self.found = true;
+        }
+        for arg in &expr.args {
+            self.visit_expr(arg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod push_limit_through_project_rewriter_test {
+    use arrow::datatypes::DataType;
+
+    use super::*;
+    use crate::binder::BoundColumnRef;
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::optimizer::{InputRefRewriter, LogicalTableScan, PlanNodeType};
+    use crate::types::SessionRng;
+
+    fn build_test_column(column_name: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: "t".to_string(),
+            id: column_name.to_string(),
+            desc: ColumnDesc {
+                name: column_name.to_string(),
+                data_type: DataType::Int32,
+            },
+        }
+    }
+
+    /// select {expr} from t limit 2 offset 1
+    fn build_test_plan(expr: BoundExpr) -> PlanRef {
+        let scan = LogicalTableScan::new("t".to_string(), vec![build_test_column("c1")]);
+        let project = LogicalProject::new(vec![expr], Arc::new(scan));
+        let limit = LogicalLimit::new(Some(2), Some(1), Arc::new(project));
+        InputRefRewriter::default().rewrite(Arc::new(limit))
+    }
+
+    #[test]
+    fn test_push_limit_below_project() {
+        let plan = build_test_plan(BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column("c1"),
+        }));
+
+        let new_plan = PushLimitThroughProjectRewriter::default().rewrite(plan);
+
+        assert_eq!(new_plan.node_type(), PlanNodeType::LogicalProject);
+        let limit = new_plan.children()[0].as_logical_limit().unwrap().clone();
+        assert_eq!((limit.limit(), limit.offset()), (Some(2), Some(1)));
+        assert_eq!(limit.input().node_type(), PlanNodeType::LogicalTableScan);
+    }
+
+    #[test]
+    fn test_keep_limit_above_non_deterministic_project() {
+        let random = BoundExpr::ScalarFunc(BoundScalarFunc {
+            func: ScalarFunc::Random(SessionRng::default()),
+            args: vec![],
+            return_type: DataType::Float64,
+        });
+        let plan = build_test_plan(random);
+
+        let new_plan = PushLimitThroughProjectRewriter::default().rewrite(plan);
+
+        assert_eq!(new_plan.node_type(), PlanNodeType::LogicalLimit);
+        assert_eq!(
+            new_plan.children()[0].node_type(),
+            PlanNodeType::LogicalProject
+        );
+    }
+}
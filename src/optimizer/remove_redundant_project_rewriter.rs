@@ -0,0 +1,154 @@
+use super::{LogicalProject, PlanRef, PlanRewriter, PlanTreeNode, RuleRewriter};
+use crate::binder::{BoundExpr, BoundInputRef};
+use crate::catalog::ColumnCatalog;
+
+/// Remove the `LogicalProject` which outputs exactly the columns of its input in the same order,
+/// e.g. the project of all the pruned columns over the table scan, or the project of all the
+/// subquery outputs over the subquery alias.
+///
+/// The project is kept if it renames any column by alias, or if the output columns of its input are
+/// not known, e.g. the aggregate whose schema doesn't include the aggregate outputs.
+#[derive(Default)]
+pub struct RemoveRedundantProjectRewriter {
+    /// whether the plan is changed since the last `take_changed`
+    changed: bool,
+}
+
+impl PlanRewriter for RemoveRedundantProjectRewriter {
+    fn rewrite_logical_project(&mut self, plan: &LogicalProject) -> PlanRef {
+        let new_children = plan
+            .children()
+            .into_iter()
+            .map(|child| self.rewrite(child))
+            .collect::<Vec<_>>();
+        let input = new_children[0].clone();
+        if plan.subqueries().is_empty() && is_identity(plan, &input) {
+            self.changed = true;
+            return input;
+        }
+        plan.clone_with_children(new_children)
+    }
+}
+
+impl RuleRewriter for RemoveRedundantProjectRewriter {
+    fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+fn is_identity(plan: &LogicalProject, input: &PlanRef) -> bool {
+    let columns = match output_columns(input) {
+        Some(columns) if columns.len() == plan.exprs().len() => columns,
+        _ => return false,
+    };
+    plan.exprs()
+        .iter()
+        .zip(plan.aliases().iter())
+        .zip(columns.iter())
+        .enumerate()
+        .all(|(index, ((expr, alias), column))| {
+            let renamed = matches!(alias, Some(alias) if *alias != column.desc.name);
+            let same_column = match expr {
+                BoundExpr::InputRef(BoundInputRef { index: i, .. }) => *i == index,
+                BoundExpr::ColumnRef(column_ref) => column_ref.column_catalog == *column,
+                _ => false,
+            };
+            same_column && !renamed
+        })
+}
+
+/// The output columns of the plan if they are known by the schema, the schema of some plan nodes
+/// is the one of their input rather than the outputs, e.g. the project and aggregate.
+fn output_columns(plan: &PlanRef) -> Option<Vec<ColumnCatalog>> {
+    if plan.as_logical_table_scan().is_ok()
+        || plan.as_table_function_scan().is_ok()
+        || plan.as_logical_subquery_alias().is_ok()
+    {
+        return Some(plan.schema());
+    }
+    if let Ok(join) = plan.as_logical_join() {
+        let mut columns = output_columns(&join.left())?;
+        columns.extend(output_columns(&join.right())?);
+        return Some(columns);
+    }
+    if let Ok(filter) = plan.as_logical_filter() {
+        return output_columns(&filter.input());
+    }
+    if let Ok(order) = plan.as_logical_order() {
+        return output_columns(&order.input());
+    }
+    if let Ok(limit) = plan.as_logical_limit() {
+        return output_columns(&limit.input());
+    }
+    None
+}
+
+#[cfg(test)]
+mod remove_redundant_project_rewriter_test {
+    use std::sync::Arc;
+
+    use arrow::datatypes::DataType;
+
+    use super::*;
+    use crate::binder::BoundColumnRef;
+    use crate::catalog::ColumnDesc;
+    use crate::optimizer::{InputRefRewriter, LogicalTableScan, PlanNodeType};
+
+    fn build_test_column(column_name: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            table_id: "t".to_string(),
+            id: column_name.to_string(),
+            desc: ColumnDesc {
+                name: column_name.to_string(),
+                data_type: DataType::Int32,
+            },
+        }
+    }
+
+    fn build_column_ref(column_name: &str) -> BoundExpr {
+        BoundExpr::ColumnRef(BoundColumnRef {
+            column_catalog: build_test_column(column_name),
+        })
+    }
+
+    /// select c1, c2 from t, where the aliases are given to the project
+    fn build_test_plan(aliases: Vec<Option<String>>) -> PlanRef {
+        let scan = LogicalTableScan::new(
+            "t".to_string(),
+            vec![build_test_column("c1"), build_test_column("c2")],
+        );
+        let project = LogicalProject::new(
+            vec![build_column_ref("c1"), build_column_ref("c2")],
+            Arc::new(scan),
+        )
+        .with_aliases(aliases);
+        InputRefRewriter::default().rewrite(Arc::new(project))
+    }
+
+    #[test]
+    fn test_remove_identity_project() {
+        let plan = build_test_plan(vec![None, Some("c2".to_string())]);
+
+        let new_plan = RemoveRedundantProjectRewriter::default().rewrite(plan);
+
+        assert_eq!(new_plan.node_type(), PlanNodeType::LogicalTableScan);
+    }
+
+    #[test]
+    fn test_keep_renaming_and_reordering_projects() {
+        let plan = build_test_plan(vec![None, Some("total".to_string())]);
+        let new_plan = RemoveRedundantProjectRewriter::default().rewrite(plan);
+        assert_eq!(new_plan.node_type(), PlanNodeType::LogicalProject);
+
+        let scan = LogicalTableScan::new(
+            "t".to_string(),
+            vec![build_test_column("c1"), build_test_column("c2")],
+        );
+        let project = LogicalProject::new(
+            vec![build_column_ref("c2"), build_column_ref("c1")],
+            Arc::new(scan),
+        );
+        let new_plan = RemoveRedundantProjectRewriter::default().rewrite(Arc::new(project));
+        assert_eq!(new_plan.node_type(), PlanNodeType::LogicalProject);
+    }
+}
@@ -1,24 +1,146 @@
 mod select;
+mod union;
 mod util;
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::binder::BoundStatement;
-use crate::optimizer::PlanRef;
+use crate::catalog::TableId;
+use crate::optimizer::{
+    Batch, BatchStrategy, InputRefRewriter, JoinCommuteRewriter, JoinReorderRewriter,
+    LogicalExplain, MergeProjectRewriter, Optimizer, PlanRef, PruneAggRewriter,
+    PushAggThroughUnionRewriter, PushFilterThroughJoinRewriter, PushLimitThroughProjectRewriter,
+    RemoveRedundantProjectRewriter, Rule, SimplifyFilterRewriter,
+};
+
+#[derive(Debug, Clone)]
+pub struct PlannerConfig {
+    /// Only scan the columns referenced by the query, and prune the aggregates not referenced by
+    /// the parent project. Disable it to preserve the full table schema in plan, which is useful
+    /// for debugging.
+    pub column_pruning: bool,
+    /// Push the filter conjuncts of one side down through the join.
+    pub predicate_pushdown: bool,
+}
+
+impl Default for PlannerConfig {
+    fn default() -> Self {
+        Self {
+            column_pruning: true,
+            predicate_pushdown: true,
+        }
+    }
+}
 
-pub struct Planner {}
+#[derive(Default)]
+pub struct Planner {
+    config: PlannerConfig,
+}
 
 impl Planner {
+    pub fn new(config: PlannerConfig) -> Self {
+        Self { config }
+    }
+
     pub fn plan(&self, stmt: BoundStatement) -> Result<PlanRef, LogicalPlanError> {
         match stmt {
             BoundStatement::Select(stmt) => self.plan_select(stmt),
+            BoundStatement::Union(stmt) => self.plan_union(stmt),
+            BoundStatement::Explain(stmt) => Ok(Arc::new(
+                LogicalExplain::new(self.plan(*stmt.statement)?, stmt.analyze)
+                    .with_verbose(stmt.verbose),
+            )),
+        }
+    }
+}
+
+impl Planner {
+    /// The optimizer of the batches enabled by the config, the cardinalities of tables decide the
+    /// join order.
+    pub fn optimizer(&self, cardinalities: HashMap<TableId, usize>) -> Optimizer {
+        let mut optimizer = Optimizer::default().with_batch(Batch::new(
+            "Simplify Filter",
+            BatchStrategy::Once,
+            vec![Box::new(SimplifyFilterRewriter::default())],
+        ));
+        if self.config.predicate_pushdown {
+            optimizer = optimizer.with_batch(Batch::new(
+                "Push Down Filter",
+                BatchStrategy::Once,
+                vec![Box::new(PushFilterThroughJoinRewriter::default())],
+            ));
+        }
+        // the joins start from the smallest relation, and the smaller side of inner join is the
+        // build side
+        optimizer = optimizer
+            .with_batch(Batch::new(
+                "Join Reorder",
+                BatchStrategy::Once,
+                vec![Box::new(JoinReorderRewriter::new(cardinalities.clone()))],
+            ))
+            .with_batch(Batch::new(
+                "Join Commute",
+                BatchStrategy::Once,
+                vec![Box::new(JoinCommuteRewriter::new(cardinalities))],
+            ))
+            .with_batch(Batch::new(
+                "Resolve Input Ref",
+                BatchStrategy::Once,
+                vec![Box::new(InputRefRewriter::default())],
+            ));
+        // pushing aggregates through union again would stack the partial aggregates
+        let mut agg_rules: Vec<Box<dyn Rule>> = vec![];
+        if self.config.column_pruning {
+            agg_rules.push(Box::new(PruneAggRewriter::default()));
+        }
+        agg_rules.push(Box::new(PushAggThroughUnionRewriter::default()));
+        optimizer
+            .with_batch(Batch::new(
+                "Aggregate Rewrite",
+                BatchStrategy::Once,
+                agg_rules,
+            ))
+            .with_batch(Batch::new(
+                "Simplify Project",
+                BatchStrategy::FixedPoint(10),
+                vec![
+                    Box::new(MergeProjectRewriter::default()),
+                    Box::new(RemoveRedundantProjectRewriter::default()),
+                ],
+            ))
+            .with_batch(Batch::new(
+                "Push Down Limit",
+                BatchStrategy::Once,
+                vec![Box::new(PushLimitThroughProjectRewriter::default())],
+            ))
+    }
+
+    /// Optimize the logical plan by the optimizer of config, the EXPLAIN VERBOSE is filled with
+    /// the rules fired on its plan.
+    pub fn optimize(&self, plan: PlanRef, cardinalities: HashMap<TableId, usize>) -> PlanRef {
+        let mut optimizer = self.optimizer(cardinalities);
+        let plan = optimizer.optimize(plan);
+        match plan.as_logical_explain() {
+            Ok(explain) if explain.verbose() => Arc::new(
+                explain
+                    .clone()
+                    .with_fired_rules(optimizer.fired_rules().to_vec()),
+            ),
+            _ => plan,
         }
     }
 }
 
 #[derive(thiserror::Error, Debug, PartialEq)]
-pub enum LogicalPlanError {}
+pub enum LogicalPlanError {
+    #[error("invalid limit or offset {0}, expected a non-negative integer constant")]
+    InvalidLimit(String),
+}
 
 #[cfg(test)]
 mod planner_test {
+    use std::assert_matches::assert_matches;
     use std::collections::BTreeMap;
 
     use arrow::datatypes::DataType::{self, Int32};
@@ -26,15 +148,17 @@ mod planner_test {
 
     use super::*;
     use crate::binder::{
-        BoundBinaryOp, BoundColumnRef, BoundExpr, BoundSelect, BoundStatement, BoundTableRef,
+        BoundBinaryOp, BoundColumnRef, BoundExpr, BoundSelect, BoundStatement, BoundTableRef, Join,
+        JoinType,
     };
     use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog};
-    use crate::optimizer::PlanNodeType;
+    use crate::optimizer::{count_operators, PlanNodeType, PlanRef};
     use crate::types::ScalarValue;
 
     fn build_test_column(column_name: String) -> BoundExpr {
         BoundExpr::ColumnRef(BoundColumnRef {
             column_catalog: ColumnCatalog {
+                table_id: "t".to_string(),
                 id: column_name.clone(),
                 desc: ColumnDesc {
                     name: column_name,
@@ -52,6 +176,7 @@ mod planner_test {
             column_map.insert(
                 column.clone(),
                 ColumnCatalog {
+                    table_id: table_name.clone(),
                     id: column.clone(),
                     desc: ColumnDesc {
                         name: column,
@@ -60,14 +185,12 @@ mod planner_test {
                 },
             );
         }
-        Some(BoundTableRef {
-            table_catalog: TableCatalog {
-                id: table_name.clone(),
-                name: table_name,
-                columns: column_map,
-                column_ids,
-            },
-        })
+        Some(BoundTableRef::Table(TableCatalog {
+            id: table_name.clone(),
+            name: table_name,
+            columns: column_map,
+            column_ids,
+        }))
     }
 
     fn build_test_select_stmt() -> BoundStatement {
@@ -83,16 +206,25 @@ mod planner_test {
 
         BoundStatement::Select(BoundSelect {
             select_list: vec![c1],
+            select_aliases: vec![None],
             from_table: t,
             where_clause: Some(where_clause),
             group_by: vec![],
+            having: None,
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            with_ties: false,
+            in_subqueries: vec![],
+            subqueries: vec![],
+            where_subqueries: vec![],
         })
     }
 
     #[test]
     fn test_plan_select_works() {
         let stmt = build_test_select_stmt();
-        let p = Planner {};
+        let p = Planner::default();
         let node = p.plan(stmt);
         assert!(node.is_ok());
         let plan_ref = node.unwrap();
@@ -100,4 +232,137 @@ mod planner_test {
         assert_eq!(plan_ref.schema().len(), 2);
         dbg!(plan_ref);
     }
+
+    fn find_table_scan_columns(plan: &PlanRef) -> Vec<String> {
+        if let Ok(scan) = plan.as_logical_table_scan() {
+            return scan.column_ids();
+        }
+        find_table_scan_columns(&plan.children()[0])
+    }
+
+    fn build_test_pruning_stmt() -> BoundStatement {
+        // select c1 from t where c2 = 2
+        let t = build_test_table(
+            "t".to_string(),
+            vec!["c1".to_string(), "c2".to_string(), "c3".to_string()],
+        );
+        let where_clause = BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Eq,
+            left: Box::new(build_test_column("c2".to_string())),
+            right: Box::new(BoundExpr::Constant(ScalarValue::Int32(Some(2)))),
+            return_type: Some(DataType::Boolean),
+        });
+        BoundStatement::Select(BoundSelect {
+            select_list: vec![build_test_column("c1".to_string())],
+            select_aliases: vec![None],
+            from_table: t,
+            where_clause: Some(where_clause),
+            group_by: vec![],
+            having: None,
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            with_ties: false,
+            in_subqueries: vec![],
+            subqueries: vec![],
+            where_subqueries: vec![],
+        })
+    }
+
+    #[test]
+    fn test_plan_select_with_column_pruning() {
+        let p = Planner::new(PlannerConfig {
+            column_pruning: true,
+            ..Default::default()
+        });
+        let plan = p.plan(build_test_pruning_stmt()).unwrap();
+        assert_eq!(
+            find_table_scan_columns(&plan),
+            vec!["c1".to_string(), "c2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_plan_select_without_column_pruning() {
+        let p = Planner::new(PlannerConfig {
+            column_pruning: false,
+            ..Default::default()
+        });
+        let plan = p.plan(build_test_pruning_stmt()).unwrap();
+        assert_eq!(
+            find_table_scan_columns(&plan),
+            vec!["c1".to_string(), "c2".to_string(), "c3".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_plan_select_rejects_negative_limit() {
+        let mut stmt = match build_test_select_stmt() {
+            BoundStatement::Select(select) => select,
+            _ => unreachable!(),
+        };
+        stmt.limit = Some(BoundExpr::Constant(ScalarValue::Int32(Some(-1))));
+        let result = Planner::default().plan(BoundStatement::Select(stmt));
+        assert_matches!(result, Err(LogicalPlanError::InvalidLimit(_)));
+    }
+
+    #[test]
+    fn test_predicate_pushdown_toggles_optimizer_batch() {
+        // select a.c1 from a, b where a.c1 = 2
+        let table = |name: &str| build_test_table(name.to_string(), vec!["c1".to_string()]);
+        let from_table = BoundTableRef::Join(Join {
+            left: Box::new(table("a").unwrap()),
+            right: Box::new(table("b").unwrap()),
+            join_type: JoinType::Cross,
+            on: vec![],
+            using: vec![],
+        });
+        let column = |table_id: &str| {
+            BoundExpr::ColumnRef(BoundColumnRef {
+                column_catalog: from_table
+                    .columns()
+                    .into_iter()
+                    .find(|c| c.table_id == table_id)
+                    .unwrap(),
+            })
+        };
+        let stmt = || {
+            BoundStatement::Select(BoundSelect {
+                select_list: vec![column("a")],
+                select_aliases: vec![None],
+                from_table: Some(from_table.clone()),
+                where_clause: Some(BoundExpr::BinaryOp(BoundBinaryOp {
+                    op: BinaryOperator::Eq,
+                    left: Box::new(column("a")),
+                    right: Box::new(BoundExpr::Constant(ScalarValue::Int32(Some(2)))),
+                    return_type: Some(DataType::Boolean),
+                })),
+                group_by: vec![],
+                having: None,
+                order_by: vec![],
+                limit: None,
+                offset: None,
+                with_ties: false,
+                in_subqueries: vec![],
+                subqueries: vec![],
+                where_subqueries: vec![],
+            })
+        };
+
+        for predicate_pushdown in [true, false] {
+            let p = Planner::new(PlannerConfig {
+                predicate_pushdown,
+                ..Default::default()
+            });
+            let plan = p.optimize(p.plan(stmt()).unwrap(), HashMap::new());
+            let counts = count_operators(&plan);
+            assert_eq!(counts.get("LogicalFilter"), Some(&1));
+            // the filter is pushed below the join, which is under the project
+            let join_input = plan.children()[0].node_type();
+            match predicate_pushdown {
+                true => assert_eq!(join_input, PlanNodeType::LogicalJoin),
+                false => assert_eq!(join_input, PlanNodeType::LogicalFilter),
+            }
+        }
+    }
 }
@@ -1,38 +1,213 @@
 use std::sync::Arc;
 
-use super::util::find_aggregate_exprs;
+use super::util::{find_aggregate_exprs, find_column_ref_exprs, find_window_exprs};
 use super::*;
-use crate::binder::BoundSelect;
+use crate::binder::{BoundColumnRef, BoundExpr, BoundSelect, BoundTableRef};
+use crate::catalog::ColumnCatalog;
 use crate::optimizer::*;
+use crate::types::ScalarValue;
 
 impl Planner {
     pub fn plan_select(&self, stmt: BoundSelect) -> Result<PlanRef, LogicalPlanError> {
         let mut plan: PlanRef;
 
-        if let Some(table_ref) = stmt.from_table {
-            // plan table_ref into LogicalTableScan
-            plan = Arc::new(LogicalTableScan::new(
-                table_ref.table_catalog.id.clone(),
-                table_ref.table_catalog.get_all_columns(),
-            ));
+        if let Some(table_ref) = &stmt.from_table {
+            plan = self.plan_table_ref(table_ref, &stmt)?;
         } else {
-            todo!("need logical values")
+            // the select without FROM clause is evaluated over a single row, e.g. `select 1`
+            plan = Arc::new(Dummy {});
         }
 
         if let Some(expr) = stmt.where_clause {
-            plan = Arc::new(LogicalFilter::new(expr, plan));
+            let subqueries = stmt
+                .where_subqueries
+                .into_iter()
+                .map(|subquery| self.plan_select(subquery))
+                .try_collect()?;
+            plan = Arc::new(LogicalFilter::new(expr, plan).with_subqueries(subqueries));
+        }
+
+        for in_subquery in stmt.in_subqueries {
+            let subquery = self.plan_select(*in_subquery.subquery)?;
+            plan = Arc::new(LogicalFilter::new_in_subquery(
+                in_subquery.expr,
+                in_subquery.negated,
+                plan,
+                subquery,
+            ));
         }
 
-        let agg = find_aggregate_exprs(stmt.select_list.as_slice());
+        // the aggregates only in HAVING are also computed, which are pruned from the output by
+        // the project
+        let mut agg_exprs = stmt.select_list.clone();
+        agg_exprs.extend(stmt.having.clone());
+        let agg = find_aggregate_exprs(agg_exprs.as_slice());
 
         if !agg.is_empty() || !stmt.group_by.is_empty() {
             plan = Arc::new(LogicalAgg::new(agg, stmt.group_by, plan));
         }
 
-        if !stmt.select_list.is_empty() {
-            plan = Arc::new(LogicalProject::new(stmt.select_list, plan));
+        if let Some(expr) = stmt.having {
+            plan = Arc::new(LogicalFilter::new(expr, plan));
+        }
+
+        // the window functions are computed over the rows after aggregation, including the ones
+        // nested in select exprs, e.g. `salary - avg(salary) over ()`, so that the project
+        // evaluates the exprs by referencing the window outputs
+        let window_funcs = find_window_exprs(stmt.select_list.as_slice());
+        if !window_funcs.is_empty() {
+            plan = Arc::new(LogicalWindow::new(window_funcs, plan));
+        }
+
+        // sort before project, so that the sort keys could reference the columns not in select list
+        let tie_keys = match stmt.with_ties {
+            true => stmt.order_by.iter().map(|o| o.expr.clone()).collect(),
+            false => vec![],
+        };
+        if !stmt.order_by.is_empty() {
+            plan = Arc::new(LogicalOrder::new(stmt.order_by, plan));
+        }
+
+        let limit = stmt
+            .limit
+            .map(|expr| self.plan_limit_value(expr))
+            .transpose()?;
+        let offset = stmt
+            .offset
+            .map(|expr| self.plan_limit_value(expr))
+            .transpose()?;
+        // limit with ties before project, since the ties are found by the sort keys
+        if stmt.with_ties {
+            plan = Arc::new(LogicalLimit::new(limit, offset, plan).with_tie_keys(tie_keys));
+        }
+
+        // the empty select list projects zero columns, which still outputs the rows
+        let subqueries = stmt
+            .subqueries
+            .into_iter()
+            .map(|subquery| self.plan_select(subquery))
+            .try_collect()?;
+        plan = Arc::new(
+            LogicalProject::new(stmt.select_list, plan)
+                .with_aliases(stmt.select_aliases)
+                .with_subqueries(subqueries),
+        );
+
+        if !stmt.with_ties && (limit.is_some() || offset.is_some()) {
+            plan = Arc::new(LogicalLimit::new(limit, offset, plan));
         }
 
         Ok(plan)
     }
+
+    /// Plan the table into LogicalTableScan, the join of tables into LogicalJoin, the table
+    /// function into TableFunctionScan, and the subquery into its plan under LogicalSubqueryAlias.
+    fn plan_table_ref(
+        &self,
+        table_ref: &BoundTableRef,
+        stmt: &BoundSelect,
+    ) -> Result<PlanRef, LogicalPlanError> {
+        Ok(match table_ref {
+            BoundTableRef::Table(table_catalog) => {
+                let mut columns = table_catalog.get_all_columns();
+                if self.config.column_pruning {
+                    columns = self.prune_columns(stmt, columns);
+                }
+                Arc::new(LogicalTableScan::new(table_catalog.id.clone(), columns))
+            }
+            BoundTableRef::Join(join) => {
+                let plan: PlanRef = Arc::new(LogicalJoin::new(
+                    self.plan_table_ref(&join.left, stmt)?,
+                    self.plan_table_ref(&join.right, stmt)?,
+                    join.join_type,
+                    join.on.clone(),
+                ));
+                if join.using.is_empty() {
+                    return Ok(plan);
+                }
+                // project out the right keys of `USING` or `NATURAL`, which are merged into the
+                // left keys, including the ones of the nested joins
+                let merged = table_ref.using_columns();
+                let exprs = plan
+                    .schema()
+                    .into_iter()
+                    .filter(|c| !merged.contains(c))
+                    .map(|c| BoundExpr::ColumnRef(BoundColumnRef { column_catalog: c }))
+                    .collect();
+                Arc::new(LogicalProject::new(exprs, plan))
+            }
+            BoundTableRef::TableFunction(function) => Arc::new(TableFunctionScan::new(
+                function.catalog.name.clone(),
+                function.function.clone(),
+                function.args.clone(),
+                function.catalog.get_all_columns(),
+            )),
+            BoundTableRef::Subquery(subquery) => Arc::new(LogicalSubqueryAlias::new(
+                subquery.catalog.id.clone(),
+                subquery.catalog.get_all_columns(),
+                self.plan_select((*subquery.subquery).clone())?,
+            )),
+        })
+    }
+
+    /// Keep the columns referenced by the select stmt, in the order of table columns.
+    fn prune_columns(&self, stmt: &BoundSelect, columns: Vec<ColumnCatalog>) -> Vec<ColumnCatalog> {
+        let mut exprs = stmt.select_list.clone();
+        exprs.extend(stmt.where_clause.clone());
+        exprs.extend(stmt.in_subqueries.iter().map(|s| s.expr.clone()));
+        exprs.extend(stmt.group_by.clone());
+        exprs.extend(stmt.having.clone());
+        exprs.extend(stmt.order_by.iter().map(|o| o.expr.clone()));
+        if let Some(table_ref) = &stmt.from_table {
+            exprs.extend(join_keys(table_ref));
+        }
+        let referenced_ids = find_column_ref_exprs(&exprs)
+            .into_iter()
+            .filter_map(|e| match e {
+                BoundExpr::ColumnRef(col) => {
+                    Some((col.column_catalog.table_id, col.column_catalog.id))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // the same name columns of joined tables are told apart by the table id
+        let pruned = columns
+            .iter()
+            .filter(|c| referenced_ids.contains(&(c.table_id.clone(), c.id.clone())))
+            .cloned()
+            .collect::<Vec<_>>();
+        // keep at least one column, so that the scan output still carries the number of rows
+        if pruned.is_empty() {
+            columns.into_iter().take(1).collect()
+        } else {
+            pruned
+        }
+    }
+
+    fn plan_limit_value(&self, expr: BoundExpr) -> Result<usize, LogicalPlanError> {
+        match expr {
+            BoundExpr::Constant(ScalarValue::Int32(Some(v))) if v >= 0 => Ok(v as usize),
+            BoundExpr::Constant(ScalarValue::Int64(Some(v))) if v >= 0 => Ok(v as usize),
+            expr => Err(LogicalPlanError::InvalidLimit(format!("{:?}", expr))),
+        }
+    }
+}
+
+/// the keys of all joins in the table ref
+fn join_keys(table_ref: &BoundTableRef) -> Vec<BoundExpr> {
+    match table_ref {
+        BoundTableRef::Table(_) | BoundTableRef::Subquery(_) | BoundTableRef::TableFunction(_) => {
+            vec![]
+        }
+        BoundTableRef::Join(join) => {
+            let mut keys = join_keys(&join.left);
+            keys.extend(join_keys(&join.right));
+            for (left_key, right_key) in &join.on {
+                keys.push(left_key.clone());
+                keys.push(right_key.clone());
+            }
+            keys
+        }
+    }
 }
@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use super::*;
+use crate::binder::BoundUnion;
+use crate::optimizer::LogicalUnion;
+
+impl Planner {
+    pub fn plan_union(&self, stmt: BoundUnion) -> Result<PlanRef, LogicalPlanError> {
+        let inputs = stmt
+            .inputs
+            .into_iter()
+            .map(|select| self.plan_select(select))
+            .try_collect()?;
+        Ok(Arc::new(LogicalUnion::new(inputs, stmt.types)))
+    }
+}
@@ -70,3 +70,19 @@ pub fn find_aggregate_exprs(exprs: &[BoundExpr]) -> Vec<BoundExpr> {
         matches!(nested_expr, BoundExpr::AggFunc { .. })
     })
 }
+
+/// Collect all deeply nested `Expr::WindowFunc`. They are returned in order of occurrence (depth
+/// first), with duplicates omitted.
+pub fn find_window_exprs(exprs: &[BoundExpr]) -> Vec<BoundExpr> {
+    find_exprs_in_exprs(exprs, &|nested_expr| {
+        matches!(nested_expr, BoundExpr::WindowFunc { .. })
+    })
+}
+
+/// Collect all deeply nested `Expr::ColumnRef`. They are returned in order of occurrence (depth
+/// first), with duplicates omitted.
+pub fn find_column_ref_exprs(exprs: &[BoundExpr]) -> Vec<BoundExpr> {
+    find_exprs_in_exprs(exprs, &|nested_expr| {
+        matches!(nested_expr, BoundExpr::ColumnRef { .. })
+    })
+}
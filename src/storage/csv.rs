@@ -1,14 +1,15 @@
 use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::sync::{Arc, Mutex};
 use std::usize;
 
 use arrow::array::StringArray;
-use arrow::csv::{reader, Reader};
-use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::csv::{reader, Reader, ReaderBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
 use arrow::record_batch::RecordBatch;
 
-use super::{Storage, StorageError, Table, Transaction};
+use super::{Storage, StorageError, Table, TableStats, Transaction};
 use crate::catalog::{ColumnCatalog, ColumnDesc, RootCatalog, TableCatalog, TableId};
 
 pub struct CsvStorage {
@@ -29,19 +30,50 @@ impl CsvStorage {
             tables: Mutex::new(HashMap::new()),
         }
     }
-}
 
-impl Storage for CsvStorage {
-    type TableType = CsvTable;
+    /// Register the csv file as a table, whose schema is inferred from the first rows of the file.
+    /// The columns are named `column_1`, `column_2`... if the file has no header.
+    pub fn register_csv(
+        &self,
+        table_name: &str,
+        path: &str,
+        has_header: bool,
+        delimiter: u8,
+    ) -> Result<(), StorageError> {
+        let cfg = CsvConfig::default()
+            .with_header(has_header)
+            .with_delimiter(delimiter);
+        self.register_csv_with_config(table_name, path, cfg)
+    }
 
-    fn create_csv_table(&self, id: String, filepath: String) -> Result<(), StorageError> {
-        let table = CsvTable::new(id.clone(), filepath, CsvConfig::default())?;
+    pub fn register_csv_with_config(
+        &self,
+        table_name: &str,
+        path: &str,
+        cfg: CsvConfig,
+    ) -> Result<(), StorageError> {
+        let table = CsvTable::new(table_name.to_string(), path.to_string(), cfg)?;
+        self.add_table(table);
+        Ok(())
+    }
+
+    fn add_table(&self, table: CsvTable) {
+        let id = table.catalog.id.clone();
         self.catalog
             .lock()
             .unwrap()
             .tables
             .insert(id.clone(), table.catalog.clone());
         self.tables.lock().unwrap().insert(id, table);
+    }
+}
+
+impl Storage for CsvStorage {
+    type TableType = CsvTable;
+
+    fn create_csv_table(&self, id: String, filepath: String) -> Result<(), StorageError> {
+        let table = CsvTable::new(id, filepath, CsvConfig::default())?;
+        self.add_table(table);
         Ok(())
     }
 
@@ -62,6 +94,26 @@ impl Storage for CsvStorage {
         self.catalog.lock().unwrap().clone()
     }
 
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog> {
+        self.catalog.lock().unwrap().get_table_by_name(name)
+    }
+
+    /// Only the row count is estimated from the file size and a sample of lines, the column stats
+    /// are left unknown as they need a full scan of the file.
+    fn table_stats(&self, table: &str) -> TableStats {
+        let row_count = self
+            .tables
+            .lock()
+            .unwrap()
+            .get(table)
+            .and_then(|table| table.estimate_row_count().ok())
+            .unwrap_or_default();
+        TableStats {
+            row_count,
+            ..Default::default()
+        }
+    }
+
     fn show_tables(&self) -> Result<RecordBatch, StorageError> {
         let schema = Arc::new(Schema::new(vec![
             Field::new("table_name", DataType::Utf8, false),
@@ -85,6 +137,8 @@ impl Storage for CsvStorage {
     }
 }
 
+/// The format of csv file, which is used by both the schema inference and the reader. The fields
+/// may be quoted by `"`, the only quote the arrow csv reader unquotes.
 #[derive(Clone)]
 pub struct CsvConfig {
     has_header: bool,
@@ -108,6 +162,26 @@ impl Default for CsvConfig {
     }
 }
 
+impl CsvConfig {
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// The number of the first rows to infer the column types from, the type of a column is Utf8
+    /// if its sampled values are of conflicting types, except that Int64 and Float64 are inferred
+    /// as Float64.
+    pub fn with_infer_schema_rows(mut self, rows: usize) -> Self {
+        self.infer_schema_max_read_records = Some(rows);
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct CsvTable {
     _id: TableId,
@@ -130,6 +204,7 @@ impl CsvTable {
         })
     }
 
+    /// The columns are named `column_1`, `column_2`... if the file has no header.
     fn infer_arrow_schema(filepath: String, cfg: &CsvConfig) -> Result<Schema, StorageError> {
         let mut file = File::open(filepath)?;
         let (schema, _) = reader::infer_reader_schema(
@@ -138,7 +213,44 @@ impl CsvTable {
             cfg.infer_schema_max_read_records,
             cfg.has_header,
         )?;
-        Ok(schema)
+        // the datetime column is inferred as Date64, which is read as timestamp instead
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|f| match f.data_type() {
+                DataType::Date64 => Field::new(
+                    f.name(),
+                    DataType::Timestamp(TimeUnit::Microsecond, None),
+                    f.is_nullable(),
+                ),
+                _ => f.clone(),
+            })
+            .collect();
+        Ok(Schema::new(fields))
+    }
+
+    /// Estimate the number of rows by the file size divided by the average length of the sampled
+    /// lines, which is exact if the file is no longer than the sample.
+    fn estimate_row_count(&self) -> Result<usize, StorageError> {
+        const SAMPLE_LINES: usize = 100;
+        let file = File::open(&self.filepath)?;
+        let file_size = file.metadata()?.len() as usize;
+        let mut lines = BufReader::new(file).split(b'\n');
+        let header_size = match self.arrow_csv_cfg.has_header {
+            true => lines.next().transpose()?.map_or(0, |line| line.len() + 1),
+            false => 0,
+        };
+        let mut sampled_rows = 0;
+        let mut sampled_size = 0;
+        for line in lines.take(SAMPLE_LINES) {
+            sampled_rows += 1;
+            sampled_size += line?.len() + 1;
+        }
+        let data_size = file_size.saturating_sub(header_size);
+        if sampled_rows < SAMPLE_LINES || sampled_size >= data_size {
+            return Ok(sampled_rows);
+        }
+        Ok(data_size * sampled_rows / sampled_size)
     }
 
     fn infer_catalog(id: String, name: String, schema: &Schema) -> TableCatalog {
@@ -150,6 +262,7 @@ impl CsvTable {
             columns.insert(
                 field_name.clone(),
                 ColumnCatalog {
+                    table_id: id.clone(),
                     id: field_name.clone(),
                     desc: ColumnDesc {
                         name: field_name,
@@ -173,6 +286,16 @@ impl Table for CsvTable {
     fn read(&self) -> Result<Self::TransactionType, StorageError> {
         CsvTransaction::start(self)
     }
+
+    /// The csv reader only parses the projected columns.
+    fn read_with_projection(
+        &self,
+        projection: Vec<usize>,
+    ) -> Result<Self::TransactionType, StorageError> {
+        let mut table = self.clone();
+        table.arrow_csv_cfg.projection = Some(projection);
+        CsvTransaction::start(&table)
+    }
 }
 
 pub struct CsvTransaction {
@@ -196,17 +319,18 @@ impl CsvTransaction {
         cfg: &CsvConfig,
     ) -> Result<Reader<File>, StorageError> {
         let file = File::open(filepath)?;
-        let reader = Reader::new(
-            file,
-            schema,
-            cfg.has_header,
-            Some(cfg.delimiter),
-            cfg.batch_size,
-            None,
-            cfg.projection.clone(),
-            cfg.datetime_format.clone(),
-        );
-        Ok(reader)
+        let mut builder = ReaderBuilder::new()
+            .with_schema(schema)
+            .has_header(cfg.has_header)
+            .with_delimiter(cfg.delimiter)
+            .with_batch_size(cfg.batch_size);
+        if let Some(projection) = &cfg.projection {
+            builder = builder.with_projection(projection.clone());
+        }
+        if let Some(datetime_format) = &cfg.datetime_format {
+            builder = builder.with_datetime_format(datetime_format.clone());
+        }
+        Ok(builder.build(file)?)
     }
 }
 
@@ -219,7 +343,13 @@ impl Transaction for CsvTransaction {
 
 #[cfg(test)]
 mod tests {
+    use arrow::array::{BooleanArray, Float64Array};
+
     use super::*;
+    use crate::binder::{Binder, BoundStatement, BoundTableRef};
+    use crate::db::{Database, DatabaseError};
+    use crate::parser::parse;
+    use crate::util::TempFile;
 
     #[test]
     fn test_csv_storage_works() -> Result<(), StorageError> {
@@ -234,7 +364,146 @@ mod tests {
         assert!(batch.is_some());
         let batch = batch.unwrap();
         assert_eq!(batch.num_rows(), 4);
+        assert_eq!(storage.table_stats("test").row_count, 4);
+
+        Ok(())
+    }
+    #[test]
+    fn test_bind_csv_table_by_schema_only() {
+        let storage = Arc::new(CsvStorage::new());
+        storage
+            .create_csv_table(
+                "employee".to_string(),
+                "./tests/csv/employee.csv".to_string(),
+            )
+            .unwrap();
+
+        // the binder resolves the table by the schema, the csv file is not read until scan
+        let mut binder = Binder::new(storage.clone());
+        let stmts = parse("select id, first_name from employee").unwrap();
+        match binder.bind(&stmts[0]).unwrap() {
+            BoundStatement::Select(select) => {
+                assert!(matches!(
+                    select.from_table,
+                    Some(BoundTableRef::Table(table)) if table.id == "employee"
+                ));
+                assert_eq!(select.select_list.len(), 2);
+            }
+            _ => unreachable!(),
+        }
+        assert!(storage.get_table_schema("missing").is_none());
+    }
+
+    fn column_names(storage: &CsvStorage, table: &str) -> Vec<String> {
+        let catalog = storage.get_table_schema(table).unwrap();
+        catalog
+            .get_all_columns()
+            .into_iter()
+            .map(|c| c.desc.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_storage_reads_tab_delimited_file() -> Result<(), StorageError> {
+        let file = TempFile::with_content(".tsv", "id\tname\n1\t\"Bill, Jr\"\n2\tGregg\n")?;
+        let storage = CsvStorage::new();
+        let cfg = CsvConfig::default().with_delimiter(b'\t');
+        storage.register_csv_with_config("t", file.path().to_str().unwrap(), cfg)?;
+        assert_eq!(column_names(&storage, "t"), vec!["id", "name"]);
+
+        // select name from t
+        let mut tx = storage
+            .get_table("t".to_string())?
+            .read_with_projection(vec![1])?;
+        let batch = tx.next_batch()?.unwrap();
+        let names = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(0), "Bill, Jr");
+        assert_eq!(names.value(1), "Gregg");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_csv_storage_infers_types_from_sample_rows() -> Result<(), DatabaseError> {
+        let file = TempFile::with_content(
+            ".csv",
+            "id,score,passed,note\n1,90,true,1\n2,85.5,false,a\n3,70,TRUE,2\n",
+        )
+        .map_err(StorageError::from)?;
+        let storage = CsvStorage::new();
+        storage.register_csv("t", file.path().to_str().unwrap(), true, b',')?;
+        let types = storage
+            .get_table_schema("t")
+            .unwrap()
+            .get_all_columns()
+            .into_iter()
+            .map(|c| c.desc.data_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Int64,
+                DataType::Float64,
+                DataType::Boolean,
+                DataType::Utf8
+            ]
+        );
+
+        // the inferred columns are compared with the literals of the same types
+        let db = Database::new_on_csv();
+        db.register_csv("t", file.path().to_str().unwrap(), true, b',')?;
+        let batches = db.run("select score, passed from t where id = 1").await?;
+        let scores = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        let passed = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert_eq!(*scores, Float64Array::from(vec![90.0]));
+        assert_eq!(*passed, BooleanArray::from(vec![true]));
+
+        // the conflicting value of note is out of the sample
+        let cfg = CsvConfig::default().with_infer_schema_rows(1);
+        storage.register_csv_with_config("t", file.path().to_str().unwrap(), cfg)?;
+        let catalog = storage.get_table_schema("t").unwrap();
+        assert_eq!(
+            catalog.get_column_by_name("note").unwrap().desc.data_type,
+            DataType::Int64
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_storage_reads_headerless_file() -> Result<(), StorageError> {
+        let file = TempFile::with_content(".csv", "1,Bill,100\n2,Gregg,200\n")?;
+        let storage = CsvStorage::new();
+        storage.register_csv("t", file.path().to_str().unwrap(), false, b',')?;
+        assert_eq!(
+            column_names(&storage, "t"),
+            vec!["column_1", "column_2", "column_3"]
+        );
+        let catalog = storage.get_table_schema("t").unwrap();
+        assert_eq!(
+            catalog
+                .get_column_by_name("column_3")
+                .unwrap()
+                .desc
+                .data_type,
+            DataType::Int64
+        );
 
+        // the first line is a row rather than the header
+        let mut tx = storage.get_table("t".to_string())?.read()?;
+        let batch = tx.next_batch()?.unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(storage.table_stats("t").row_count, 2);
         Ok(())
     }
 }
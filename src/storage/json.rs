@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::{Arc, Mutex};
+
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::json::reader::{infer_json_schema, Reader, ReaderBuilder};
+use arrow::record_batch::RecordBatch;
+
+use super::{Storage, StorageError, Table, TableStats, Transaction};
+use crate::catalog::{ColumnCatalog, ColumnDesc, RootCatalog, TableCatalog, TableId};
+
+/// The storage of newline-delimited json files, each line is an object of one row.
+pub struct JsonStorage {
+    catalog: Mutex<RootCatalog>,
+    tables: Mutex<HashMap<TableId, JsonTable>>,
+}
+
+impl Default for JsonStorage {
+    fn default() -> Self {
+        JsonStorage::new()
+    }
+}
+
+impl JsonStorage {
+    pub fn new() -> Self {
+        JsonStorage {
+            catalog: Mutex::new(RootCatalog::new()),
+            tables: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn create_json_table(&self, id: String, filepath: String) -> Result<(), StorageError> {
+        let table = JsonTable::new(id.clone(), filepath)?;
+        self.catalog
+            .lock()
+            .unwrap()
+            .tables
+            .insert(id.clone(), table.catalog.clone());
+        self.tables.lock().unwrap().insert(id, table);
+        Ok(())
+    }
+}
+
+impl Storage for JsonStorage {
+    type TableType = JsonTable;
+
+    fn create_csv_table(&self, _id: String, _filepath: String) -> Result<(), StorageError> {
+        unreachable!("json storage does not support create csv table")
+    }
+
+    fn create_mem_table(&self, _id: String, _data: Vec<RecordBatch>) -> Result<(), StorageError> {
+        unreachable!("json storage does not support create memory table")
+    }
+
+    fn get_table(&self, id: String) -> Result<Self::TableType, StorageError> {
+        self.tables
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(StorageError::TableNotFound(id))
+    }
+
+    fn get_catalog(&self) -> RootCatalog {
+        self.catalog.lock().unwrap().clone()
+    }
+
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog> {
+        self.catalog.lock().unwrap().get_table_by_name(name)
+    }
+
+    /// The row count is the number of non-empty lines, the column stats are left unknown.
+    fn table_stats(&self, table: &str) -> TableStats {
+        let row_count = self
+            .tables
+            .lock()
+            .unwrap()
+            .get(table)
+            .and_then(|table| table.count_rows().ok())
+            .unwrap_or_default();
+        TableStats {
+            row_count,
+            ..Default::default()
+        }
+    }
+
+    fn show_tables(&self) -> Result<RecordBatch, StorageError> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("columns", DataType::Utf8, false),
+        ]));
+        let mut ids = Vec::new();
+        let mut columns = Vec::new();
+        for (id, table) in self.tables.lock().unwrap().iter() {
+            ids.push(id.clone());
+            columns.push(format!("{:?}", table.catalog.get_all_columns()));
+        }
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(ids)),
+                Arc::new(StringArray::from(columns)),
+            ],
+        )?;
+
+        Ok(batch)
+    }
+}
+
+#[derive(Clone)]
+pub struct JsonTable {
+    _id: TableId,
+    arrow_schema: SchemaRef,
+    filepath: String,
+    catalog: TableCatalog,
+}
+
+impl JsonTable {
+    const INFER_SCHEMA_MAX_READ_RECORDS: usize = 100;
+    const BATCH_SIZE: usize = 1024;
+
+    pub fn new(id: String, filepath: String) -> Result<Self, StorageError> {
+        let schema = Self::infer_arrow_schema(&filepath)?;
+        let catalog = Self::infer_catalog(id.clone(), &schema);
+        Ok(Self {
+            _id: id,
+            arrow_schema: Arc::new(schema),
+            filepath,
+            catalog,
+        })
+    }
+
+    /// Infer the schema from the first rows. The nested objects and arrays are not flattened but
+    /// rejected, as the columns are only of scalar types.
+    fn infer_arrow_schema(filepath: &str) -> Result<Schema, StorageError> {
+        let mut reader = BufReader::new(File::open(filepath)?);
+        let schema = infer_json_schema(&mut reader, Some(Self::INFER_SCHEMA_MAX_READ_RECORDS))?;
+        if let Some(field) = schema
+            .fields()
+            .iter()
+            .find(|f| matches!(f.data_type(), DataType::Struct(_) | DataType::List(_)))
+        {
+            return Err(StorageError::UnsupportedJsonField(field.name().clone()));
+        }
+        Ok(schema)
+    }
+
+    fn count_rows(&self) -> Result<usize, StorageError> {
+        let file = File::open(&self.filepath)?;
+        let mut rows = 0;
+        for line in BufReader::new(file).lines() {
+            if !line?.trim().is_empty() {
+                rows += 1;
+            }
+        }
+        Ok(rows)
+    }
+
+    fn infer_catalog(id: String, schema: &Schema) -> TableCatalog {
+        let mut columns = BTreeMap::new();
+        let mut column_ids = Vec::new();
+        for f in schema.fields().iter() {
+            let field_name = f.name().to_string();
+            column_ids.push(field_name.clone());
+            columns.insert(
+                field_name.clone(),
+                ColumnCatalog {
+                    table_id: id.clone(),
+                    id: field_name.clone(),
+                    desc: ColumnDesc {
+                        name: field_name,
+                        data_type: f.data_type().clone(),
+                    },
+                },
+            );
+        }
+        TableCatalog {
+            id: id.clone(),
+            name: id,
+            columns,
+            column_ids,
+        }
+    }
+}
+
+impl Table for JsonTable {
+    type TransactionType = JsonTransaction;
+
+    /// The json reader decodes all the fields of each line, the unneeded columns are dropped by
+    /// the scan.
+    fn read(&self) -> Result<Self::TransactionType, StorageError> {
+        JsonTransaction::start(self)
+    }
+}
+
+pub struct JsonTransaction {
+    reader: Reader<File>,
+}
+
+impl JsonTransaction {
+    pub fn start(table: &JsonTable) -> Result<Self, StorageError> {
+        let file = File::open(&table.filepath)?;
+        let reader = ReaderBuilder::new()
+            .with_schema(table.arrow_schema.clone())
+            .with_batch_size(JsonTable::BATCH_SIZE)
+            .build(file)?;
+        Ok(Self { reader })
+    }
+}
+
+impl Transaction for JsonTransaction {
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>, StorageError> {
+        Ok(self.reader.next()?)
+    }
+}
+
+#[cfg(test)]
+mod json_storage_test {
+    use std::assert_matches::assert_matches;
+
+    use arrow::array::{Float64Array, Int64Array};
+
+    use super::*;
+    use crate::util::TempFile;
+
+    #[test]
+    fn test_json_storage_works() -> Result<(), StorageError> {
+        let storage = JsonStorage::new();
+        storage.create_json_table("people".to_string(), "./tests/json/people.json".to_string())?;
+
+        let catalog = storage.get_table_schema("people").unwrap();
+        let types = ["id", "name", "score", "active"]
+            .iter()
+            .map(|name| catalog.get_column_by_name(name).unwrap().desc.data_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Int64,
+                DataType::Utf8,
+                DataType::Float64,
+                DataType::Boolean
+            ]
+        );
+        assert_eq!(storage.table_stats("people").row_count, 3);
+
+        let mut tx = storage.get_table("people".to_string())?.read()?;
+        let batch = tx.next_batch()?.unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        let schema = batch.schema();
+        let ids = batch.column(schema.index_of("id")?);
+        let ids = ids.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(ids.values(), &[1, 2, 3]);
+        // the missing field is read as null
+        let scores = batch.column(schema.index_of("score")?);
+        let scores = scores.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert!(scores.is_null(2));
+        assert!(tx.next_batch()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_storage_rejects_nested_objects() {
+        let file =
+            TempFile::with_content(".json", "{\"id\": 1, \"address\": {\"city\": \"SF\"}}\n")
+                .unwrap();
+        let storage = JsonStorage::new();
+        let result =
+            storage.create_json_table("t".to_string(), file.path().to_str().unwrap().to_string());
+        assert_matches!(result, Err(StorageError::UnsupportedJsonField(field)) if field == "address");
+    }
+}
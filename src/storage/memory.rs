@@ -5,7 +5,7 @@ use arrow::array::StringArray;
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::record_batch::RecordBatch;
 
-use super::{Storage, StorageError, Table, Transaction};
+use super::{Storage, StorageError, Table, TableStats, Transaction};
 use crate::catalog::{ColumnCatalog, ColumnDesc, RootCatalog, TableCatalog, TableId};
 
 pub struct InMemoryStorage {
@@ -26,6 +26,17 @@ impl InMemoryStorage {
             tables: Mutex::new(HashMap::new()),
         }
     }
+
+    /// Remove all rows of the table. Only the data is replaced, the catalog is kept as is, so
+    /// that the holders of the table catalog still see the same columns.
+    pub fn truncate_table(&self, id: &str) -> Result<(), StorageError> {
+        let mut tables = self.tables.lock().unwrap();
+        let table = tables
+            .get_mut(id)
+            .ok_or_else(|| StorageError::TableNotFound(id.to_string()))?;
+        table.data = Arc::new(vec![]);
+        Ok(())
+    }
 }
 
 impl Storage for InMemoryStorage {
@@ -41,7 +52,7 @@ impl Storage for InMemoryStorage {
             .lock()
             .unwrap()
             .tables
-            .insert(id.clone(), table.catalog.clone());
+            .insert(id.clone(), (*table.catalog).clone());
         self.tables.lock().unwrap().insert(id, table);
         Ok(())
     }
@@ -59,6 +70,18 @@ impl Storage for InMemoryStorage {
         self.catalog.lock().unwrap().clone()
     }
 
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog> {
+        self.catalog.lock().unwrap().get_table_by_name(name)
+    }
+
+    /// The exact statistics computed from the stored batches.
+    fn table_stats(&self, table: &str) -> TableStats {
+        match self.tables.lock().unwrap().get(table) {
+            Some(table) => TableStats::from_batches(&table.catalog.column_ids, &table.data),
+            None => TableStats::default(),
+        }
+    }
+
     fn show_tables(&self) -> Result<RecordBatch, StorageError> {
         let schema = Arc::new(Schema::new(vec![
             Field::new("table_name", DataType::Utf8, false),
@@ -85,8 +108,9 @@ impl Storage for InMemoryStorage {
 #[derive(Clone)]
 pub struct InMemoryTable {
     _id: TableId,
-    catalog: TableCatalog,
-    data: Vec<RecordBatch>,
+    catalog: Arc<TableCatalog>,
+    /// shared by the transactions in flight, truncate replaces it rather than clearing in place.
+    data: Arc<Vec<RecordBatch>>,
 }
 
 impl InMemoryTable {
@@ -94,11 +118,15 @@ impl InMemoryTable {
         let catalog = Self::infer_catalog(id.clone(), data.first().cloned());
         Ok(Self {
             _id: id,
-            data,
-            catalog,
+            data: Arc::new(data),
+            catalog: Arc::new(catalog),
         })
     }
 
+    pub fn catalog(&self) -> Arc<TableCatalog> {
+        self.catalog.clone()
+    }
+
     fn infer_catalog(id: String, batch: Option<RecordBatch>) -> TableCatalog {
         let mut columns = BTreeMap::new();
         let mut column_ids = Vec::new();
@@ -109,6 +137,7 @@ impl InMemoryTable {
                 columns.insert(
                     field_name.clone(),
                     ColumnCatalog {
+                        table_id: id.clone(),
                         id: field_name.clone(),
                         desc: ColumnDesc {
                             name: field_name,
@@ -133,6 +162,22 @@ impl Table for InMemoryTable {
     fn read(&self) -> Result<Self::TransactionType, StorageError> {
         InMemoryTransaction::start(self)
     }
+
+    /// each batch is one partition
+    fn partitions(&self) -> usize {
+        self.data.len().max(1)
+    }
+
+    fn read_partition(
+        &self,
+        partition: usize,
+        _projection: Vec<usize>,
+    ) -> Result<Self::TransactionType, StorageError> {
+        Ok(InMemoryTransaction {
+            cursor: 0,
+            data: self.data.get(partition).cloned().into_iter().collect(),
+        })
+    }
 }
 
 pub struct InMemoryTransaction {
@@ -144,7 +189,7 @@ impl InMemoryTransaction {
     pub fn start(table: &InMemoryTable) -> Result<Self, StorageError> {
         Ok(Self {
             cursor: 0,
-            data: table.data.clone(),
+            data: table.data.to_vec(),
         })
     }
 }
@@ -163,9 +208,10 @@ impl Transaction for InMemoryTransaction {
 
 #[cfg(test)]
 mod storage_test {
+    use std::assert_matches::assert_matches;
     use std::sync::Arc;
 
-    use arrow::array::Int32Array;
+    use arrow::array::{Int32Array, Int64Array};
     use arrow::datatypes::{DataType, Field, Schema};
 
     use super::*;
@@ -224,4 +270,69 @@ mod storage_test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_in_memory_storage_truncate_keeps_catalog() -> Result<(), StorageError> {
+        let id = "test".to_string();
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(id.clone(), build_record_batch()?)?;
+
+        let catalog = storage.get_table(id.clone())?.catalog();
+        let columns = catalog.get_all_columns();
+        let table_before = storage.get_table(id.clone())?;
+        storage.truncate_table(&id)?;
+
+        let table = storage.get_table(id.clone())?;
+        assert!(Arc::ptr_eq(&catalog, &table.catalog()));
+        assert_eq!(table.catalog().get_all_columns(), columns);
+        assert_eq!(
+            storage.get_table_schema(&id).unwrap().get_all_columns(),
+            columns
+        );
+        assert!(table.read()?.next_batch()?.is_none());
+        // the table got before truncate still reads the old data
+        assert_eq!(table_before.read()?.next_batch()?.unwrap().num_rows(), 3);
+
+        assert_matches!(
+            storage.truncate_table("not_exist"),
+            Err(StorageError::TableNotFound(_))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_memory_storage_table_stats() -> Result<(), StorageError> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("state", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("CA"),
+                    Some("CO"),
+                    Some("CA"),
+                    None,
+                ])),
+            ],
+        )?;
+        let storage = InMemoryStorage::new();
+        storage.create_mem_table(
+            "employee".to_string(),
+            vec![batch.slice(0, 3), batch.slice(3, 1)],
+        )?;
+
+        let stats = storage.table_stats("employee");
+        assert_eq!(stats.row_count, 4);
+        let column_stats = |id: &str| stats.column_stats[id];
+        assert_eq!(column_stats("id").null_count, 0);
+        assert_eq!(column_stats("id").distinct_count, 4);
+        assert_eq!(column_stats("state").null_count, 1);
+        assert_eq!(column_stats("state").distinct_count, 2);
+        assert_eq!(storage.table_stats("not_exist"), TableStats::default());
+        assert_eq!(storage.table_cardinalities()["employee"], 4);
+        Ok(())
+    }
 }
@@ -1,20 +1,26 @@
 mod csv;
+mod json;
 mod memory;
+mod stats;
+use std::collections::HashMap;
 use std::io;
 use std::sync::Arc;
 
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 pub use csv::*;
+pub use json::*;
 pub use memory::*;
+pub use stats::*;
 
-use crate::catalog::RootCatalog;
+use crate::catalog::{RootCatalog, SchemaProvider, TableCatalog, TableId};
 
 #[derive(Clone)]
 pub enum StorageImpl {
     CsvStorage(Arc<CsvStorage>),
     #[allow(dead_code)]
     InMemoryStorage(Arc<InMemoryStorage>),
+    JsonStorage(Arc<JsonStorage>),
 }
 
 pub trait Storage: Sync + Send + 'static {
@@ -28,13 +34,62 @@ pub trait Storage: Sync + Send + 'static {
 
     fn get_catalog(&self) -> RootCatalog;
 
+    /// Get the schema of one table without loading its data, the lazy or remote storages should
+    /// override it rather than building the whole catalog.
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog> {
+        self.get_catalog().get_table_by_name(name)
+    }
+
     fn show_tables(&self) -> Result<RecordBatch, StorageError>;
+
+    /// The statistics of the table, the default one if the table is not found.
+    fn table_stats(&self, table: &str) -> TableStats;
+
+    /// The approximate number of rows of the tables known by storage, used by the optimizer to
+    /// estimate the cardinality of plans.
+    fn table_cardinalities(&self) -> HashMap<TableId, usize> {
+        self.get_catalog()
+            .tables
+            .keys()
+            .map(|id| (id.clone(), self.table_stats(id).row_count))
+            .collect()
+    }
+}
+
+impl<S: Storage> SchemaProvider for S {
+    fn get_table_schema(&self, name: &str) -> Option<TableCatalog> {
+        Storage::get_table_schema(self, name)
+    }
 }
 
 pub trait Table: Sync + Send + Clone + 'static {
     type TransactionType: Transaction;
 
     fn read(&self) -> Result<Self::TransactionType, StorageError>;
+
+    /// Read the columns at the given indices only. The storage that can't push projection down
+    /// reads all columns, and the unneeded ones are dropped by the scan after read.
+    fn read_with_projection(
+        &self,
+        _projection: Vec<usize>,
+    ) -> Result<Self::TransactionType, StorageError> {
+        self.read()
+    }
+
+    /// The number of partitions which can be read independently, e.g. the batches of memory
+    /// table. The storage without partitions is read as a whole in one partition.
+    fn partitions(&self) -> usize {
+        1
+    }
+
+    /// Read the columns at the given indices of one partition only.
+    fn read_partition(
+        &self,
+        _partition: usize,
+        projection: Vec<usize>,
+    ) -> Result<Self::TransactionType, StorageError> {
+        self.read_with_projection(projection)
+    }
 }
 
 // currently we use a transaction to hold csv reader
@@ -52,4 +107,7 @@ pub enum StorageError {
 
     #[error("table not found: {0}")]
     TableNotFound(String),
+
+    #[error("unsupported nested json field: {0}")]
+    UnsupportedJsonField(String),
 }
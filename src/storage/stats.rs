@@ -0,0 +1,55 @@
+use std::collections::{HashMap, HashSet};
+
+use arrow::record_batch::RecordBatch;
+
+use crate::types::ScalarValue;
+
+/// The statistics of a table for the optimizer to estimate the cardinality of plans. The numbers
+/// are approximate unless the storage computes them from the whole data.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableStats {
+    pub row_count: usize,
+    /// the statistics of each column by column id, absent if unknown
+    pub column_stats: HashMap<String, ColumnStats>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ColumnStats {
+    pub null_count: usize,
+    /// the number of distinct non-NULL values
+    pub distinct_count: usize,
+}
+
+impl TableStats {
+    /// Compute the exact statistics from all batches of the table.
+    pub fn from_batches(column_ids: &[String], batches: &[RecordBatch]) -> Self {
+        let row_count = batches.iter().map(|batch| batch.num_rows()).sum();
+        let column_stats = column_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let mut null_count = 0;
+                let mut values = HashSet::new();
+                for batch in batches {
+                    let array = batch.column(i);
+                    null_count += array.null_count();
+                    for row in 0..array.len() {
+                        let value = ScalarValue::try_from_array(array, row);
+                        if !value.is_null() {
+                            values.insert(value);
+                        }
+                    }
+                }
+                let stats = ColumnStats {
+                    null_count,
+                    distinct_count: values.len(),
+                };
+                (id.clone(), stats)
+            })
+            .collect();
+        Self {
+            row_count,
+            column_stats,
+        }
+    }
+}
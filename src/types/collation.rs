@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// The rule of the session to compare strings, which applies to the comparisons, IN, BETWEEN,
+/// ORDER BY, join keys and `count(DISTINCT)` of strings, but not to GROUP BY, min and max. The
+/// strings are compared by their sort keys, so that any comparison of the sort keys byte-wise
+/// follows the collation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collation {
+    /// compare by bytes
+    Binary,
+    /// compare case insensitively
+    NoCase,
+    /// compare case and accent insensitively, the Latin letters with diacritics are compared as
+    /// their base letters, e.g. `É` equals to `e`
+    Unicode,
+}
+
+impl Default for Collation {
+    fn default() -> Self {
+        Collation::Binary
+    }
+}
+
+impl Collation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "binary" | "c" => Some(Collation::Binary),
+            "nocase" => Some(Collation::NoCase),
+            "unicode" => Some(Collation::Unicode),
+            _ => None,
+        }
+    }
+
+    /// The key of the string whose byte-wise order is the order of the collation.
+    pub fn sort_key<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self {
+            Collation::Binary => Cow::Borrowed(s),
+            Collation::NoCase => Cow::Owned(s.to_lowercase()),
+            Collation::Unicode => Cow::Owned(s.to_lowercase().chars().map(fold_accent).collect()),
+        }
+    }
+}
+
+impl fmt::Display for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Collation::Binary => write!(f, "binary"),
+            Collation::NoCase => write!(f, "nocase"),
+            Collation::Unicode => write!(f, "unicode"),
+        }
+    }
+}
+
+/// Fold the lowercase Latin letter with diacritics of Latin-1 Supplement and Latin Extended-A to
+/// its base letter, other chars are kept as is.
+fn fold_accent(c: char) -> char {
+    match c {
+        'à'..='å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod collation_test {
+    use super::*;
+
+    #[test]
+    fn test_collation_sort_key() {
+        assert_eq!(Collation::Binary.sort_key("Éric"), "Éric");
+        assert_eq!(Collation::NoCase.sort_key("Éric"), "éric");
+        assert_eq!(Collation::Unicode.sort_key("Éric Žižek"), "eric zizek");
+        assert_eq!(Collation::from_name("NOCASE"), Some(Collation::NoCase));
+        assert_eq!(Collation::from_name("icu"), None);
+    }
+}
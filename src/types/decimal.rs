@@ -0,0 +1,161 @@
+use sqlparser::ast::BinaryOperator;
+
+/// The max precision of Decimal128, which holds 38 decimal digits.
+pub const MAX_DECIMAL_PRECISION: u8 = 38;
+
+/// The minimal scale kept when the precision of result type overflows.
+const MIN_ADJUSTED_SCALE: u8 = 6;
+
+/// Resolve the `(precision, scale)` of arithmetic result, follow the rules of SQL Server:
+///
+/// | op    | scale                 | precision                           |
+/// |-------|-----------------------|-------------------------------------|
+/// | + -   | max(s1, s2)           | max(p1 - s1, p2 - s2) + scale + 1   |
+/// | *     | s1 + s2               | p1 + p2 + 1                         |
+/// | /     | max(6, s1 + p2 + 1)   | p1 - s1 + s2 + scale                |
+/// | %     | max(s1, s2)           | min(p1 - s1, p2 - s2) + scale       |
+///
+/// If the precision is greater than 38, the scale is reduced to keep the integral part but not
+/// less than 6.
+pub fn decimal_arithmetic_type(op: &BinaryOperator, left: (u8, u8), right: (u8, u8)) -> (u8, u8) {
+    let ((p1, s1), (p2, s2)) = (left, right);
+    let (precision, scale) = match op {
+        BinaryOperator::Multiply => (p1 + p2 + 1, s1 + s2),
+        BinaryOperator::Divide => {
+            let scale = MIN_ADJUSTED_SCALE.max(s1 + p2 + 1);
+            (p1 - s1 + s2 + scale, scale)
+        }
+        BinaryOperator::Modulo => {
+            let scale = s1.max(s2);
+            ((p1 - s1).min(p2 - s2) + scale, scale)
+        }
+        _ => {
+            let scale = s1.max(s2);
+            ((p1 - s1).max(p2 - s2) + scale + 1, scale)
+        }
+    };
+    adjust_precision_scale(precision, scale)
+}
+
+fn adjust_precision_scale(precision: u8, scale: u8) -> (u8, u8) {
+    if precision <= MAX_DECIMAL_PRECISION {
+        return (precision, scale);
+    }
+    let integral = precision - scale;
+    let min_scale = scale.min(MIN_ADJUSTED_SCALE);
+    let scale = MAX_DECIMAL_PRECISION
+        .saturating_sub(integral)
+        .max(min_scale);
+    (MAX_DECIMAL_PRECISION, scale)
+}
+
+/// The decimal type that an integer type can be casted to without losing digits.
+pub fn integer_decimal_type(bits: u8) -> (u8, u8) {
+    match bits {
+        32 => (10, 0),
+        _ => (19, 0),
+    }
+}
+
+/// Parse the decimal literal like `-12.50` into `(value, precision, scale)`, e.g.
+/// `(-1250, 4, 2)`.
+pub fn parse_decimal(s: &str) -> Option<(i128, u8, u8)> {
+    let s = s.trim();
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (integral, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integral.is_empty() && fraction.is_empty()
+        || !integral
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let value = format!("{}{}", integral, fraction)
+        .parse::<i128>()
+        .unwrap_or_default();
+    let scale = fraction.len() as u8;
+    let precision = (integral.trim_start_matches('0').len() as u8 + scale).max(1);
+    if precision > MAX_DECIMAL_PRECISION {
+        return None;
+    }
+    Some((if negative { -value } else { value }, precision, scale))
+}
+
+/// Format the decimal value with the scale, e.g. `(-1250, 2)` is `-12.50`.
+pub fn format_decimal(value: i128, scale: u8) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let digits = format!("{:0>width$}", digits, width = scale as usize + 1);
+    let (integral, fraction) = digits.split_at(digits.len() - scale as usize);
+    format!("{}{}.{}", sign, integral, fraction)
+}
+
+/// Change the scale of decimal value, the dropped digits are rounded half away from zero.
+/// Returns None if overflow.
+pub fn rescale_decimal(value: i128, from_scale: u8, to_scale: u8) -> Option<i128> {
+    if to_scale >= from_scale {
+        value.checked_mul(10i128.checked_pow((to_scale - from_scale) as u32)?)
+    } else {
+        let divisor = 10i128.checked_pow((from_scale - to_scale) as u32)?;
+        Some(div_round(value, divisor))
+    }
+}
+
+/// Integer division that rounds half away from zero.
+pub fn div_round(dividend: i128, divisor: i128) -> i128 {
+    let quotient = dividend / divisor;
+    let remainder = dividend % divisor;
+    if remainder.unsigned_abs() * 2 >= divisor.unsigned_abs() {
+        if (dividend < 0) != (divisor < 0) {
+            quotient - 1
+        } else {
+            quotient + 1
+        }
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod decimal_test {
+    use super::*;
+
+    #[test]
+    fn test_decimal_arithmetic_type() {
+        let (l, r) = ((10, 2), (5, 3));
+        assert_eq!(
+            decimal_arithmetic_type(&BinaryOperator::Plus, l, r),
+            (12, 3)
+        );
+        assert_eq!(
+            decimal_arithmetic_type(&BinaryOperator::Multiply, l, r),
+            (16, 5)
+        );
+        assert_eq!(
+            decimal_arithmetic_type(&BinaryOperator::Divide, l, r),
+            (19, 8)
+        );
+        assert_eq!(
+            decimal_arithmetic_type(&BinaryOperator::Multiply, (38, 10), (38, 10)),
+            (38, 6)
+        );
+    }
+
+    #[test]
+    fn test_parse_and_format_decimal() {
+        assert_eq!(parse_decimal("-12.50"), Some((-1250, 4, 2)));
+        assert_eq!(parse_decimal("0.05"), Some((5, 2, 2)));
+        assert_eq!(parse_decimal("abc"), None);
+        assert_eq!(format_decimal(-1250, 2), "-12.50");
+        assert_eq!(format_decimal(5, 2), "0.05");
+        assert_eq!(rescale_decimal(1255, 2, 1), Some(126));
+        assert_eq!(rescale_decimal(-1255, 2, 1), Some(-126));
+    }
+}
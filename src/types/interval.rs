@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use super::temporal::{civil_from_days, days_from_civil, days_in_month};
+
+const NANOS_PER_MICRO: i64 = 1_000;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 24 * 60 * 60 * NANOS_PER_SECOND as i128;
+const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+/// the days of a month when comparing intervals, the same as PostgreSQL
+const DAYS_PER_MONTH: i128 = 30;
+
+/// The interval of months, days and nanoseconds, the layout of arrow `IntervalMonthDayNano`.
+///
+/// The fields are kept apart in arithmetic, as a month is not a fixed number of days: `1 month`
+/// plus `30 days` is `1 month 30 days`, which is resolved against the calendar only when added to
+/// a timestamp.
+///
+/// Comparison follows PostgreSQL: the intervals are compared by their total length, where a month
+/// counts as 30 days and a day as 24 hours. So `1 month` equals `30 days`, and equality, ordering
+/// and hash are all consistent with that rule.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IntervalMonthDayNano {
+    pub months: i32,
+    pub days: i32,
+    pub nanos: i64,
+}
+
+impl IntervalMonthDayNano {
+    pub fn new(months: i32, days: i32, nanos: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanos,
+        }
+    }
+
+    /// Unpack from the arrow native value, months in the high 32 bits, then days in the next 32
+    /// bits and nanos in the low 64 bits.
+    pub fn from_i128(v: i128) -> Self {
+        Self {
+            months: (v >> 96) as i32,
+            days: (v >> 64) as i32,
+            nanos: v as i64,
+        }
+    }
+
+    pub fn to_i128(self) -> i128 {
+        ((self.months as u32 as u128) << 96
+            | (self.days as u32 as u128) << 64
+            | self.nanos as u64 as u128) as i128
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_add(other.months)?,
+            days: self.days.checked_add(other.days)?,
+            nanos: self.nanos.checked_add(other.nanos)?,
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            nanos: self.nanos.checked_neg()?,
+        })
+    }
+
+    /// The total length in nanoseconds used by comparison, which never overflows i128.
+    fn total_nanos(&self) -> i128 {
+        (self.months as i128 * DAYS_PER_MONTH + self.days as i128) * NANOS_PER_DAY
+            + self.nanos as i128
+    }
+}
+
+impl PartialEq for IntervalMonthDayNano {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_nanos() == other.total_nanos()
+    }
+}
+
+impl Eq for IntervalMonthDayNano {}
+
+impl PartialOrd for IntervalMonthDayNano {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IntervalMonthDayNano {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_nanos().cmp(&other.total_nanos())
+    }
+}
+
+impl Hash for IntervalMonthDayNano {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_nanos().hash(state);
+    }
+}
+
+/// Formatted like `1 mons 2 days 03:04:05.5`, the time part is omitted if zero.
+impl fmt::Display for IntervalMonthDayNano {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mons {} days", self.months, self.days)?;
+        if self.nanos == 0 {
+            return Ok(());
+        }
+        let sign = if self.nanos < 0 { "-" } else { "" };
+        let nanos = self.nanos.unsigned_abs();
+        let secs = nanos / NANOS_PER_SECOND as u64;
+        let fraction = nanos % NANOS_PER_SECOND as u64;
+        write!(
+            f,
+            " {}{:02}:{:02}:{:02}",
+            sign,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        )?;
+        if fraction != 0 {
+            let fraction = format!("{:09}", fraction);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the interval string of `quantity unit` pairs like `1 year 2 months 3 days 4 hours`, the
+/// quantity is an integer which may be negative, and the unit is case insensitive and may be
+/// plural.
+pub fn parse_interval(s: &str) -> Option<IntervalMonthDayNano> {
+    let mut interval = IntervalMonthDayNano::default();
+    let mut tokens = s.split_whitespace();
+    let mut parsed = false;
+    while let Some(quantity) = tokens.next() {
+        let quantity = quantity.parse::<i64>().ok()?;
+        let unit = tokens.next()?.to_lowercase();
+        let unit = unit.strip_suffix('s').unwrap_or(&unit);
+        let field = match unit {
+            "year" => IntervalMonthDayNano::new(quantity.checked_mul(12)?.try_into().ok()?, 0, 0),
+            "month" | "mon" => IntervalMonthDayNano::new(quantity.try_into().ok()?, 0, 0),
+            "week" => IntervalMonthDayNano::new(0, quantity.checked_mul(7)?.try_into().ok()?, 0),
+            "day" => IntervalMonthDayNano::new(0, quantity.try_into().ok()?, 0),
+            "hour" => {
+                IntervalMonthDayNano::new(0, 0, quantity.checked_mul(3600 * NANOS_PER_SECOND)?)
+            }
+            "minute" | "min" => {
+                IntervalMonthDayNano::new(0, 0, quantity.checked_mul(60 * NANOS_PER_SECOND)?)
+            }
+            "second" | "sec" => {
+                IntervalMonthDayNano::new(0, 0, quantity.checked_mul(NANOS_PER_SECOND)?)
+            }
+            "millisecond" => IntervalMonthDayNano::new(0, 0, quantity.checked_mul(1_000_000)?),
+            "microsecond" => {
+                IntervalMonthDayNano::new(0, 0, quantity.checked_mul(NANOS_PER_MICRO)?)
+            }
+            _ => return None,
+        };
+        interval = interval.checked_add(field)?;
+        parsed = true;
+    }
+    parsed.then(|| interval)
+}
+
+/// Add the interval to the timestamp of microseconds since UNIX epoch. The months are added first
+/// on the calendar, and the day is clamped to the end of the target month, e.g. `2020-01-31` plus
+/// `1 month` is `2020-02-29`. Then the days and nanos are added, where the nanos are truncated to
+/// microseconds. Returns None on overflow.
+pub fn add_interval_to_timestamp(micros: i64, interval: IntervalMonthDayNano) -> Option<i64> {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let time = micros.rem_euclid(MICROS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let total_months = year
+        .checked_mul(12)?
+        .checked_add(month as i64 - 1)?
+        .checked_add(interval.months as i64)?;
+    let year = total_months.div_euclid(12);
+    let month = total_months.rem_euclid(12) as u32 + 1;
+    let day = day.min(days_in_month(year, month));
+    let days = days_from_civil(year, month, day).checked_add(interval.days as i64)?;
+    days.checked_mul(MICROS_PER_DAY)?
+        .checked_add(time)?
+        .checked_add(interval.nanos / NANOS_PER_MICRO)
+}
+
+#[cfg(test)]
+mod interval_test {
+    use super::*;
+    use crate::types::parse_timestamp;
+
+    #[test]
+    fn test_interval_add_keeps_months_apart_from_days() {
+        let a = parse_interval("1 month 20 days 12 hours").unwrap();
+        let b = parse_interval("1 MONTH 15 days 13 hours").unwrap();
+        let sum = a.checked_add(b).unwrap();
+        // 35 days and 25 hours are not carried into months or days
+        assert_eq!(
+            (sum.months, sum.days, sum.nanos),
+            (2, 35, 25 * 3600 * NANOS_PER_SECOND)
+        );
+        assert_eq!(sum.to_string(), "2 mons 35 days 25:00:00");
+        let diff = a.checked_sub(b).unwrap();
+        assert_eq!(
+            (diff.months, diff.days, diff.nanos),
+            (0, 5, -3600 * NANOS_PER_SECOND)
+        );
+        assert_eq!(IntervalMonthDayNano::from_i128(sum.to_i128()), sum);
+        assert_eq!(
+            IntervalMonthDayNano::from_i128(diff.to_i128()).nanos,
+            diff.nanos
+        );
+        assert!(IntervalMonthDayNano::new(i32::MAX, 0, 0)
+            .checked_add(IntervalMonthDayNano::new(1, 0, 0))
+            .is_none());
+    }
+
+    #[test]
+    fn test_interval_compare_by_total_length() {
+        let month = parse_interval("1 month").unwrap();
+        let days = parse_interval("30 days").unwrap();
+        assert_eq!(month, days);
+        assert!(parse_interval("29 days 25 hours").unwrap() > month);
+        assert!(parse_interval("-1 year").unwrap() < days);
+        assert_eq!(parse_interval("1 fortnight"), None);
+        assert_eq!(parse_interval(""), None);
+    }
+
+    #[test]
+    fn test_add_interval_to_timestamp() {
+        let ts = parse_timestamp("2020-01-31 10:00:00").unwrap();
+        let interval = parse_interval("1 month 1 day 2 hours").unwrap();
+        assert_eq!(
+            add_interval_to_timestamp(ts, interval),
+            parse_timestamp("2020-03-01 12:00:00")
+        );
+        let ts = parse_timestamp("1969-12-31 23:00:00").unwrap();
+        assert_eq!(
+            add_interval_to_timestamp(ts, parse_interval("-13 months").unwrap()),
+            parse_timestamp("1968-11-30 23:00:00")
+        );
+    }
+}
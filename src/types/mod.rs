@@ -1,9 +1,22 @@
+mod collation;
+mod decimal;
+mod interval;
+mod random;
+mod temporal;
+
 use core::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use arrow::array::*;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
 use arrow::error::ArrowError;
+use arrow::temporal_conversions::{date32_to_datetime, timestamp_us_to_datetime};
+pub use collation::*;
+pub use decimal::*;
+pub use interval::*;
+pub use random::*;
+pub use temporal::*;
 
 macro_rules! typed_cast {
     ($array:expr, $index:expr, $ARRAYTYPE:ident, $SCALAR:ident) => {{
@@ -32,6 +45,14 @@ pub enum ScalarValue {
     Int64(Option<i64>),
     /// utf-8 encoded string.
     String(Option<String>),
+    /// 128bit decimal with (precision, scale), e.g. `Decimal128(Some(1250), 4, 2)` is 12.50
+    Decimal128(Option<i128>, u8, u8),
+    /// number of days since UNIX epoch
+    Date32(Option<i32>),
+    /// number of microseconds since UNIX epoch, without timezone
+    Timestamp(Option<i64>),
+    /// interval of months, days and nanoseconds, see `IntervalMonthDayNano` for its arithmetic
+    IntervalMonthDayNano(Option<IntervalMonthDayNano>),
 }
 
 impl ScalarValue {
@@ -43,9 +64,29 @@ impl ScalarValue {
             ScalarValue::Int32(_) => DataType::Int32,
             ScalarValue::Int64(_) => DataType::Int64,
             ScalarValue::String(_) => DataType::Utf8,
+            ScalarValue::Decimal128(_, p, s) => DataType::Decimal(*p as usize, *s as usize),
+            ScalarValue::Date32(_) => DataType::Date32,
+            ScalarValue::Timestamp(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+            ScalarValue::IntervalMonthDayNano(_) => DataType::Interval(IntervalUnit::MonthDayNano),
         }
     }
 
+    pub fn is_null(&self) -> bool {
+        matches!(
+            self,
+            ScalarValue::Null
+                | ScalarValue::Boolean(None)
+                | ScalarValue::Float64(None)
+                | ScalarValue::Int32(None)
+                | ScalarValue::Int64(None)
+                | ScalarValue::String(None)
+                | ScalarValue::Decimal128(None, _, _)
+                | ScalarValue::Date32(None)
+                | ScalarValue::Timestamp(None)
+                | ScalarValue::IntervalMonthDayNano(None)
+        )
+    }
+
     pub fn from(data_type: &DataType) -> Self {
         match data_type {
             DataType::Null => ScalarValue::Null,
@@ -54,6 +95,12 @@ impl ScalarValue {
             DataType::Int32 => ScalarValue::Int32(None),
             DataType::Int64 => ScalarValue::Int64(None),
             DataType::Utf8 => ScalarValue::String(None),
+            DataType::Decimal(p, s) => ScalarValue::Decimal128(None, *p as u8, *s as u8),
+            DataType::Date32 => ScalarValue::Date32(None),
+            DataType::Timestamp(TimeUnit::Microsecond, None) => ScalarValue::Timestamp(None),
+            DataType::Interval(IntervalUnit::MonthDayNano) => {
+                ScalarValue::IntervalMonthDayNano(None)
+            }
             _ => panic!("Unsupported data type: {}", data_type),
         }
     }
@@ -72,11 +119,50 @@ impl ScalarValue {
             DataType::Int64 => typed_cast!(array, index, Int64Array, Int64),
             DataType::Int32 => typed_cast!(array, index, Int32Array, Int32),
             DataType::Utf8 => typed_cast!(array, index, StringArray, String),
+            DataType::Decimal(p, s) => {
+                let array = array.as_any().downcast_ref::<DecimalArray>().unwrap();
+                ScalarValue::Decimal128(Some(array.value(index).as_i128()), *p as u8, *s as u8)
+            }
+            DataType::Date32 => typed_cast!(array, index, Date32Array, Date32),
+            DataType::Timestamp(TimeUnit::Microsecond, None) => {
+                typed_cast!(array, index, TimestampMicrosecondArray, Timestamp)
+            }
+            DataType::Interval(IntervalUnit::MonthDayNano) => {
+                let array = array
+                    .as_any()
+                    .downcast_ref::<IntervalMonthDayNanoArray>()
+                    .unwrap();
+                ScalarValue::IntervalMonthDayNano(Some(IntervalMonthDayNano::from_i128(
+                    array.value(index),
+                )))
+            }
             _ => panic!("Unsupported data type: {}", array.data_type()),
         }
     }
 }
 
+/// The float is hashed by its bits so that the scalar values could be the keys of hash set, e.g. in
+/// distinct aggregates. Note NaN is never equal to itself, so each NaN is a distinct key.
+impl Eq for ScalarValue {}
+
+impl Hash for ScalarValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ScalarValue::Null => {}
+            ScalarValue::Boolean(v) => v.hash(state),
+            ScalarValue::Float64(v) => v.map(f64::to_bits).hash(state),
+            ScalarValue::Int32(v) => v.hash(state),
+            ScalarValue::Int64(v) => v.hash(state),
+            ScalarValue::String(v) => v.hash(state),
+            ScalarValue::Decimal128(v, p, s) => (v, p, s).hash(state),
+            ScalarValue::Date32(v) => v.hash(state),
+            ScalarValue::Timestamp(v) => v.hash(state),
+            ScalarValue::IntervalMonthDayNano(v) => v.hash(state),
+        }
+    }
+}
+
 macro_rules! format_option {
     ($F:expr, $EXPR:expr) => {{
         match $EXPR {
@@ -95,6 +181,10 @@ impl fmt::Display for ScalarValue {
             ScalarValue::Int32(v) => format_option!(f, v),
             ScalarValue::Int64(v) => format_option!(f, v),
             ScalarValue::String(v) => format_option!(f, v),
+            ScalarValue::Decimal128(v, _, s) => format_option!(f, v.map(|v| format_decimal(v, *s))),
+            ScalarValue::Date32(v) => format_option!(f, v.map(|v| date32_to_datetime(v).date())),
+            ScalarValue::Timestamp(v) => format_option!(f, v.map(timestamp_us_to_datetime)),
+            ScalarValue::IntervalMonthDayNano(v) => format_option!(f, v),
         }
     }
 }
@@ -152,6 +242,19 @@ pub fn build_scalar_value_array(scalar_value: &ScalarValue, capacity: usize) ->
         ScalarValue::Int32(i) => Arc::new(Int32Array::from(vec![*i; capacity])),
         ScalarValue::Int64(i) => Arc::new(Int64Array::from(vec![*i; capacity])),
         ScalarValue::String(s) => Arc::new(StringArray::from(vec![s.as_deref(); capacity])),
+        ScalarValue::Decimal128(v, p, s) => Arc::new(
+            vec![*v; capacity]
+                .into_iter()
+                .collect::<DecimalArray>()
+                .with_precision_and_scale(*p as usize, *s as usize)
+                .expect("invalid decimal precision and scale"),
+        ),
+        ScalarValue::Date32(d) => Arc::new(Date32Array::from(vec![*d; capacity])),
+        ScalarValue::Timestamp(t) => Arc::new(TimestampMicrosecondArray::from(vec![*t; capacity])),
+        ScalarValue::IntervalMonthDayNano(v) => Arc::new(IntervalMonthDayNanoArray::from(vec![
+                v.map(IntervalMonthDayNano::to_i128);
+                capacity
+            ])),
     }
 }
 
@@ -162,6 +265,14 @@ pub fn build_scalar_value_builder(data_type: &DataType) -> Box<dyn ArrayBuilder>
         DataType::Int32 => Box::new(Int32Builder::new(0)),
         DataType::Int64 => Box::new(Int64Builder::new(0)),
         DataType::Utf8 => Box::new(StringBuilder::new(0)),
+        DataType::Decimal(p, s) => Box::new(DecimalBuilder::new(0, *p, *s)),
+        DataType::Date32 => Box::new(Date32Builder::new(0)),
+        DataType::Timestamp(TimeUnit::Microsecond, None) => {
+            Box::new(TimestampMicrosecondBuilder::new(0))
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            Box::new(IntervalMonthDayNanoBuilder::new(0))
+        }
         _ => panic!("Unsupported data type: {}", data_type),
     }
 }
@@ -201,6 +312,31 @@ pub fn append_scalar_value_for_builder(
             .downcast_mut::<StringBuilder>()
             .unwrap()
             .append_option(v.as_ref())?,
+        ScalarValue::Decimal128(v, _, _) => {
+            let builder = builder
+                .as_any_mut()
+                .downcast_mut::<DecimalBuilder>()
+                .unwrap();
+            match v {
+                Some(v) => builder.append_value(*v)?,
+                None => builder.append_null()?,
+            }
+        }
+        ScalarValue::Date32(v) => builder
+            .as_any_mut()
+            .downcast_mut::<Date32Builder>()
+            .unwrap()
+            .append_option(*v)?,
+        ScalarValue::Timestamp(v) => builder
+            .as_any_mut()
+            .downcast_mut::<TimestampMicrosecondBuilder>()
+            .unwrap()
+            .append_option(*v)?,
+        ScalarValue::IntervalMonthDayNano(v) => builder
+            .as_any_mut()
+            .downcast_mut::<IntervalMonthDayNanoBuilder>()
+            .unwrap()
+            .append_option(v.map(IntervalMonthDayNano::to_i128))?,
     }
     Ok(())
 }
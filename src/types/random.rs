@@ -0,0 +1,47 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use arrow::array::{ArrayRef, Float64Array};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The random number generator of a session, shared by all `random()` calls in it.
+/// A session created with a seed produces the same sequence of values on every run.
+#[derive(Clone)]
+pub struct SessionRng(Arc<Mutex<StdRng>>);
+
+impl SessionRng {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(StdRng::from_entropy())))
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Arc::new(Mutex::new(StdRng::seed_from_u64(seed))))
+    }
+
+    /// draw `len` floats in `[0, 1)`
+    pub fn next_array(&self, len: usize) -> ArrayRef {
+        let mut rng = self.0.lock().unwrap();
+        let values = (0..len).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+        Arc::new(Float64Array::from(values))
+    }
+}
+
+impl Default for SessionRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// two handles are equal only if they draw from the same generator.
+impl PartialEq for SessionRng {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for SessionRng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SessionRng")
+    }
+}
@@ -0,0 +1,148 @@
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Parse the date string like `2020-01-01` into the number of days since UNIX epoch.
+pub fn parse_date(s: &str) -> Option<i32> {
+    let mut parts = s.trim().splitn(3, '-');
+    let year = parts.next()?.parse::<i64>().ok()?;
+    let month = parts.next()?.parse::<u32>().ok()?;
+    let day = parts.next()?.parse::<u32>().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) as i32)
+}
+
+/// Parse the timestamp string like `2020-01-01 12:30:00` or `2020-01-01T12:30:00.123` without
+/// timezone into the number of microseconds since UNIX epoch, the time part is optional.
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (date, time) = match s.split_once(|c| c == ' ' || c == 'T') {
+        Some((date, time)) => (date, time.trim()),
+        None => (s, "00:00:00"),
+    };
+    let days = parse_date(date)? as i64;
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut parts = time.splitn(3, ':');
+    let hour = parts.next()?.parse::<i64>().ok()?;
+    let minute = parts.next()?.parse::<i64>().ok()?;
+    let second = parts.next().unwrap_or("0").parse::<i64>().ok()?;
+    if hour > 23 || minute > 59 || second > 59 || fraction.len() > 6 {
+        return None;
+    }
+    let micros = match fraction {
+        "" => 0,
+        f => format!("{:0<6}", f).parse::<i64>().ok()?,
+    };
+    let seconds = days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second;
+    Some(seconds * MICROS_PER_SECOND + micros)
+}
+
+/// Parse the fixed-offset time zone like `UTC`, `+08:00`, `-0530` or `+8` into the seconds east of
+/// UTC, the named zones of tz database are not supported.
+pub fn parse_time_zone(s: &str) -> Option<i32> {
+    let s = s.trim();
+    if ["utc", "gmt", "z"].contains(&s.to_lowercase().as_str()) {
+        return Some(0);
+    }
+    let (sign, offset) = match (s.strip_prefix('+'), s.strip_prefix('-')) {
+        (Some(offset), _) => (1, offset),
+        (_, Some(offset)) => (-1, offset),
+        _ => return None,
+    };
+    let (hour, minute) = match offset.split_once(':') {
+        Some((hour, minute)) => (hour, minute),
+        None if offset.len() == 4 => offset.split_at(2),
+        None => (offset, "0"),
+    };
+    if hour.is_empty() || hour.len() > 2 || minute.is_empty() || minute.len() > 2 {
+        return None;
+    }
+    let (hour, minute) = (hour.parse::<i32>().ok()?, minute.parse::<i32>().ok()?);
+    if hour > 14 || minute > 59 {
+        return None;
+    }
+    Some(sign * (hour * 3600 + minute * 60))
+}
+
+/// Format the seconds east of UTC as `+08:00`, which is the time zone of arrow timestamp type.
+pub fn format_time_zone(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    format!("{}{:02}:{:02}", sign, offset / 3600, offset % 3600 / 60)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The days since UNIX epoch of the proleptic Gregorian date, see
+/// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub(super) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The proleptic Gregorian date of the days since UNIX epoch, the inverse of `days_from_civil`, see
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(super) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod temporal_test {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_and_timestamp() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2020-03-01"), Some(18322));
+        assert_eq!(parse_date("1969-12-31"), Some(-1));
+        assert_eq!(parse_date("2021-02-29"), None);
+        assert_eq!(parse_timestamp("1970-01-01 00:00:01"), Some(1_000_000));
+        assert_eq!(
+            parse_timestamp("1970-01-02T00:00:00.5"),
+            Some(86_400_500_000)
+        );
+        assert_eq!(parse_timestamp("not a timestamp"), None);
+        assert_eq!(civil_from_days(18322), (2020, 3, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn test_parse_time_zone() {
+        assert_eq!(parse_time_zone("UTC"), Some(0));
+        assert_eq!(parse_time_zone("+08:00"), Some(8 * 3600));
+        assert_eq!(parse_time_zone("-0530"), Some(-(5 * 3600 + 30 * 60)));
+        assert_eq!(parse_time_zone("+8"), Some(8 * 3600));
+        assert_eq!(parse_time_zone("America/New_York"), None);
+        assert_eq!(parse_time_zone("+25:00"), None);
+        assert_eq!(format_time_zone(-(5 * 3600 + 30 * 60)), "-05:30");
+        assert_eq!(format_time_zone(0), "+00:00");
+    }
+}
@@ -1,10 +1,16 @@
-use arrow::datatypes::DataType;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use arrow::array::ArrayRef;
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, SchemaRef};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use arrow::util::display::array_value_to_string;
 use arrow::util::pretty::print_batches;
 
 use crate::optimizer::PlanNode;
+use crate::types::ScalarValue;
 
 pub fn pretty_batches(batches: &Vec<RecordBatch>) {
     _ = print_batches(batches.as_slice());
@@ -53,16 +59,90 @@ pub fn record_batch_to_string(batch: &RecordBatch) -> Result<String, ArrowError>
     Ok(output)
 }
 
+/// Render a single row of the batch as `col=value` pairs, e.g. `id=1, first_name=Bill`, which is
+/// used in error messages for debugging.
+pub fn debug_row(batch: &RecordBatch, idx: usize) -> String {
+    let schema = batch.schema();
+    schema
+        .fields()
+        .iter()
+        .zip(batch.columns().iter())
+        .map(|(field, column)| {
+            let value = ScalarValue::try_from_array(column, idx);
+            format!("{}={}", field.name(), value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Cast each column of the batch to the type of the corresponding field in target schema, used to
+/// align the batches of INSERT and UNION to the same schema.
+pub fn cast_batch_to_schema(
+    batch: &RecordBatch,
+    target_schema: SchemaRef,
+) -> Result<RecordBatch, ArrowError> {
+    if batch.num_columns() != target_schema.fields().len() {
+        return Err(ArrowError::SchemaError(format!(
+            "column count mismatch, batch has {} columns but target schema has {} fields",
+            batch.num_columns(),
+            target_schema.fields().len()
+        )));
+    }
+    let columns = batch
+        .columns()
+        .iter()
+        .zip(target_schema.fields().iter())
+        .map(|(column, field)| cast(column, field.data_type()))
+        .collect::<Result<Vec<ArrayRef>, ArrowError>>()?;
+    RecordBatch::try_new(target_schema, columns)
+}
+
+/// The file of a unique path in the temp dir, which is removed when dropped. The path is made
+/// unique by the process id and a counter, so the concurrent tests and spills don't collide.
+pub(crate) struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Reserve a unique path ending with the suffix, the file is created by the caller.
+    pub(crate) fn new(suffix: &str) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "sql-query-engine-{}-{}{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            suffix
+        );
+        Self(std::env::temp_dir().join(name))
+    }
+
+    /// Create the file of a unique path with the content.
+    #[cfg(test)]
+    pub(crate) fn with_content(suffix: &str, content: &str) -> std::io::Result<Self> {
+        let file = Self::new(suffix);
+        std::fs::write(&file.0, content)?;
+        Ok(file)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.0).ok();
+    }
+}
+
 #[cfg(test)]
 mod util_test {
     use std::sync::Arc;
 
-    use arrow::array::{Int64Array, StringArray};
+    use arrow::array::{Int32Array, Int64Array, StringArray};
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::error::ArrowError;
     use arrow::record_batch::RecordBatch;
 
-    use crate::util::record_batch_to_string;
+    use crate::util::{cast_batch_to_schema, debug_row, record_batch_to_string};
 
     fn build_record_batch() -> Result<RecordBatch, ArrowError> {
         let schema = Arc::new(Schema::new(vec![
@@ -91,4 +171,33 @@ mod util_test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_cast_batch_to_schema() -> Result<(), ArrowError> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))],
+        )?;
+        let target_schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+
+        let output = cast_batch_to_schema(&batch, target_schema.clone())?;
+
+        assert_eq!(output.schema(), target_schema);
+        let column = output
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(*column, Int64Array::from(vec![Some(1), None, Some(3)]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_row() -> Result<(), ArrowError> {
+        let record_batch = build_record_batch()?;
+
+        assert_eq!(debug_row(&record_batch, 1), "id=2, first_name=Gregg");
+        Ok(())
+    }
 }